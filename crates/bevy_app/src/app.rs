@@ -5,10 +5,11 @@ use crate::{
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
     component::RequiredComponentsError,
+    error::{DefaultErrorHandler, ErrorHandler},
     event::{event_update_system, EventCursor},
     intern::Interned,
     prelude::*,
-    schedule::{ScheduleBuildSettings, ScheduleLabel},
+    schedule::{AccessPolicy, ScheduleBuildSettings, ScheduleLabel},
     system::{IntoObserverSystem, SystemId, SystemInput},
 };
 #[cfg(feature = "trace")]
@@ -371,6 +372,23 @@ impl App {
         self
     }
 
+    /// Sets the [`ErrorHandler`] used for errors returned by systems and commands of the main app,
+    /// when no more specific handler is set.
+    ///
+    /// This inserts a [`DefaultErrorHandler`] resource. See the [`error`](bevy_ecs::error) module for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// use bevy_ecs::error::warn;
+    ///
+    /// App::new().set_error_handler(warn);
+    /// ```
+    pub fn set_error_handler(&mut self, error_handler: ErrorHandler) -> &mut Self {
+        self.insert_resource(DefaultErrorHandler(error_handler))
+    }
+
     /// Inserts the [`Resource`], initialized with its default value, into the app,
     /// if there is no existing instance of `R`.
     ///
@@ -558,6 +576,86 @@ impl App {
         self
     }
 
+    /// Installs one or more [`Plugins`] in a sandbox.
+    ///
+    /// Every system the plugins add with [`add_systems`](Self::add_systems) while they are being built
+    /// is put in `set`, and the [`AccessPolicy`] of `set` is replaced by `policy`. Schedules that contain
+    /// a system violating the policy fail to build, which catches plugins whose systems access more data
+    /// than they are expected to.
+    ///
+    /// Exclusive systems and systems with deferred parameters are only allowed if the policy allows them.
+    /// Observers added with [`add_observer`](Self::add_observer) are checked against the policy as soon
+    /// as they are added, and panic if they violate it.
+    ///
+    /// Only [`Plugin::build`] is sandboxed; systems added in [`Plugin::finish`] or [`Plugin::cleanup`],
+    /// and systems or observers added by accessing the [`Schedules`] or the [`World`] directly are not
+    /// restricted.
+    ///
+    /// This is not a security boundary. The plugins have full access to the [`App`], so they can change
+    /// the policy with [`App::set_access_policy`], or access the [`World`] directly. Only add plugins
+    /// that you trust.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// use bevy_ecs::schedule::{AccessLevel, AccessPolicy};
+    ///
+    /// #[derive(Resource, Default)]
+    /// struct Score(u32);
+    ///
+    /// #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    /// struct ModSystems;
+    ///
+    /// fn cheat(mut score: ResMut<Score>) {
+    ///     score.0 = u32::MAX;
+    /// }
+    ///
+    /// fn cheat_plugin(app: &mut App) {
+    ///     app.add_systems(Update, cheat);
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.init_resource::<Score>().add_sandboxed_plugins(
+    ///     ModSystems,
+    ///     AccessPolicy::deny_all().with_resource::<Score>(AccessLevel::Read),
+    ///     cheat_plugin,
+    /// );
+    /// // Panics, because `cheat` writes to `Score`.
+    /// app.update();
+    /// ```
+    pub fn add_sandboxed_plugins<M>(
+        &mut self,
+        set: impl SystemSet,
+        policy: AccessPolicy,
+        plugins: impl Plugins<M>,
+    ) -> &mut Self {
+        let set = set.intern();
+        self.world_mut()
+            .resource_mut::<Schedules>()
+            .set_access_policy(set, policy);
+        self.main_mut().sandbox_sets.push(set);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            self.add_plugins(plugins);
+        }));
+        self.main_mut().sandbox_sets.pop();
+        if let Err(payload) = result {
+            resume_unwind(payload);
+        }
+        self
+    }
+
+    /// Sets the [`AccessPolicy`] of the systems in `set`, replacing any previous policy.
+    ///
+    /// See [`Schedules::set_access_policy`] for more information.
+    pub fn set_access_policy(&mut self, set: impl SystemSet, policy: AccessPolicy) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<Schedules>()
+            .set_access_policy(set, policy);
+        self
+    }
+
     /// Registers the type `T` in the [`AppTypeRegistry`] resource,
     /// adding reflect data as specified in the [`Reflect`](bevy_reflect::Reflect) derive:
     /// ```ignore (No serde "derive" feature)
@@ -604,6 +702,51 @@ impl App {
         self
     }
 
+    /// Records the events of type `E` into the [`EventLog`](bevy_ecs::event::EventLog) resource, along with
+    /// the frame they were sent on.
+    ///
+    /// `E` is also registered in the [`AppTypeRegistry`], so that the log can be saved with the
+    /// `EventLogSerializer` of the `serialize` feature of `bevy_ecs`.
+    /// See the [`log`](bevy_ecs::event::log) module for more information.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{event::EventLog, prelude::*};
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Event, Reflect)]
+    /// struct Jump;
+    ///
+    /// let mut app = App::new();
+    /// app.record_events::<Jump>();
+    /// app.world_mut().send_event(Jump);
+    /// app.update();
+    /// assert_eq!(app.world().resource::<EventLog>().events().len(), 1);
+    /// ```
+    #[cfg(feature = "bevy_reflect")]
+    pub fn record_events<E>(&mut self) -> &mut Self
+    where
+        E: Event + bevy_reflect::PartialReflect + bevy_reflect::GetTypeRegistration,
+    {
+        self.main_mut().record_events::<E>();
+        self
+    }
+
+    /// Sends the events of type `E` of the [`EventReplay`](bevy_ecs::event::EventReplay) resource, on the
+    /// frames they were recorded on.
+    ///
+    /// The events of a frame are sent in [`First`], right after the events are updated, so that they are
+    /// read by the systems of the frame like the original events. Insert the [`EventReplay`](bevy_ecs::event::EventReplay)
+    /// before the first update to replay the events on the same frames.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn replay_events<E>(&mut self) -> &mut Self
+    where
+        E: Event + bevy_reflect::FromReflect + bevy_reflect::GetTypeRegistration,
+    {
+        self.main_mut().replay_events::<E>();
+        self
+    }
+
     /// Registers the given function into the [`AppFunctionRegistry`] resource.
     ///
     /// The given function will internally be stored as a [`DynamicFunction`]
@@ -1281,11 +1424,22 @@ impl App {
     ///     }
     /// });
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if called by a plugin added with [`App::add_sandboxed_plugins`], and the observer violates
+    /// the [`AccessPolicy`] of the sandbox.
     pub fn add_observer<E: Event, B: Bundle, M>(
         &mut self,
         observer: impl IntoObserverSystem<E, B, M>,
     ) -> &mut Self {
-        self.world_mut().add_observer(observer);
+        if self.main().sandbox_sets.is_empty() {
+            self.world_mut().add_observer(observer);
+        } else {
+            let mut system = IntoObserverSystem::into_system(observer);
+            self.main_mut().check_sandbox_policies(&mut system);
+            self.world_mut().add_observer(system);
+        }
         self
     }
 }
@@ -1784,4 +1938,206 @@ mod tests {
         assert_eq!(test_events.len(), 2); // Events are double-buffered, so we see 2 + 0 = 2
         assert_eq!(test_events.iter_current_update_events().count(), 0);
     }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn record_and_replay_events() {
+        use bevy_ecs::{
+            event::{EventLog, EventReader, EventReplay},
+            system::Local,
+        };
+        use bevy_reflect::Reflect;
+
+        #[derive(Event, Reflect, Clone, Debug, PartialEq)]
+        struct Input(u32);
+
+        #[derive(Resource, Default)]
+        struct Received(Vec<(u32, Input)>);
+
+        let mut app = App::new();
+        app.record_events::<Input>();
+        for frame in 0..3 {
+            if frame != 1 {
+                app.world_mut().send_event(Input(frame));
+            }
+            app.update();
+        }
+        let log = app.world_mut().remove_resource::<EventLog>().unwrap();
+        assert_eq!(log.frame(), 3);
+
+        let mut app = App::new();
+        app.replay_events::<Input>()
+            .init_resource::<Received>()
+            .insert_resource(EventReplay::new(log))
+            .add_systems(
+                Update,
+                |mut frame: Local<u32>,
+                 mut inputs: EventReader<Input>,
+                 mut received: ResMut<Received>| {
+                    received
+                        .0
+                        .extend(inputs.read().map(|input| (*frame, input.clone())));
+                    *frame += 1;
+                },
+            );
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(
+            app.world().resource::<Received>().0,
+            [(0, Input(0)), (2, Input(2))]
+        );
+        assert!(app.world().resource::<EventReplay>().is_finished());
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn record_events_in_sub_app() {
+        use crate::Last;
+        use bevy_ecs::event::EventLog;
+        use bevy_reflect::Reflect;
+
+        #[derive(Event, Reflect, Clone)]
+        struct Input;
+
+        #[derive(Event, Reflect, Clone)]
+        struct Other;
+
+        let mut sub_app = SubApp::new();
+        sub_app.update_schedule = Some(Last.intern());
+        sub_app.init_resource::<bevy_ecs::reflect::AppTypeRegistry>();
+        sub_app.record_events::<Input>().record_events::<Other>();
+        sub_app.world_mut().send_event(Input);
+        sub_app.update();
+        sub_app.update();
+
+        let log = sub_app.world().resource::<EventLog>();
+        assert_eq!(log.frame(), 2);
+        assert_eq!(log.events().len(), 1);
+    }
+
+    #[test]
+    fn sandboxed_plugins() {
+        use crate::Startup;
+        use bevy_ecs::{
+            schedule::{AccessLevel, AccessPolicy, ScheduleBuildError, SystemSet},
+            system::Res,
+        };
+
+        #[derive(Resource, Default)]
+        struct Allowed(u32);
+
+        #[derive(Resource, Default)]
+        struct Protected(u32);
+
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Sandbox;
+
+        fn read_protected(app: &mut App) {
+            app.add_systems(
+                Update,
+                |mut allowed: ResMut<Allowed>, protected: Res<Protected>| {
+                    allowed.0 = protected.0;
+                },
+            );
+        }
+
+        fn write_protected(app: &mut App) {
+            app.add_systems(Startup, |mut protected: ResMut<Protected>| {
+                protected.0 += 1;
+            });
+        }
+
+        let policy = AccessPolicy::deny_all()
+            .with_resource::<Allowed>(AccessLevel::Write)
+            .with_resource::<Protected>(AccessLevel::Read);
+
+        let mut app = App::new();
+        app.init_resource::<Allowed>()
+            .insert_resource(Protected(3))
+            .add_sandboxed_plugins(Sandbox, policy.clone(), read_protected)
+            // Systems added outside of the sandbox aren't restricted.
+            .add_plugins(write_protected);
+        app.update();
+        assert_eq!(app.world().resource::<Allowed>().0, 4);
+
+        let mut app = App::new();
+        app.init_resource::<Allowed>()
+            .init_resource::<Protected>()
+            .add_sandboxed_plugins(Sandbox, policy, write_protected);
+        let result = app
+            .world_mut()
+            .try_schedule_scope(Startup, |world, schedule| schedule.initialize(world));
+        assert!(matches!(
+            result,
+            Ok(Err(ScheduleBuildError::AccessPolicyViolation(_)))
+        ));
+    }
+
+    #[test]
+    fn sandboxed_plugins_check_exclusive_systems_and_observers() {
+        use crate::Startup;
+        use bevy_ecs::{
+            observer::Trigger,
+            schedule::{AccessLevel, AccessPolicy, ScheduleBuildError, SystemSet},
+            system::Res,
+        };
+        use core::panic::AssertUnwindSafe;
+        use std::panic::catch_unwind;
+
+        #[derive(Resource, Default)]
+        struct Protected(u32);
+
+        #[derive(Event)]
+        struct Cheat;
+
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Sandbox;
+
+        fn policy() -> AccessPolicy {
+            AccessPolicy::deny_all().with_resource::<Protected>(AccessLevel::Read)
+        }
+
+        let mut app = App::new();
+        app.init_resource::<Protected>().add_sandboxed_plugins(
+            Sandbox,
+            policy(),
+            |app: &mut App| {
+                app.add_systems(Startup, |world: &mut World| {
+                    world.resource_mut::<Protected>().0 += 1;
+                });
+            },
+        );
+        let result = app
+            .world_mut()
+            .try_schedule_scope(Startup, |world, schedule| schedule.initialize(world));
+        assert!(matches!(
+            result,
+            Ok(Err(ScheduleBuildError::AccessPolicyViolation(_)))
+        ));
+
+        // Observers that respect the policy are allowed.
+        let mut app = App::new();
+        app.init_resource::<Protected>().add_sandboxed_plugins(
+            Sandbox,
+            policy(),
+            |app: &mut App| {
+                app.add_observer(|_: Trigger<Cheat>, _: Res<Protected>| {});
+            },
+        );
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut app = App::new();
+            app.init_resource::<Protected>().add_sandboxed_plugins(
+                Sandbox,
+                policy(),
+                |app: &mut App| {
+                    app.add_observer(|_: Trigger<Cheat>, mut protected: ResMut<Protected>| {
+                        protected.0 += 1;
+                    });
+                },
+            );
+        }));
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,499 @@
+use core::{alloc::Layout, any::TypeId};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use bevy_ecs::{
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+};
+use bevy_reflect::{GetTypeRegistration, TypeRegistry};
+use bevy_utils::{
+    tracing::{error, info, warn},
+    HashMap, HashSet,
+};
+use derive_more::derive::{Display, Error, From};
+
+use crate::{App, First, Plugin, Update};
+
+/// The name of the function that a hot-reloadable library exports, see [`hot_reload_entry!`](crate::hot_reload_entry).
+pub const HOT_RELOAD_ENTRY_POINT: &str = "bevy_hot_reload";
+
+/// The signature of the function that adds the systems of a hot-reloadable library.
+pub type HotReloadEntry = fn(&mut HotReloadContext);
+
+/// Exports `$entry` as the entry point of a hot-reloadable library.
+///
+/// `$entry` must be a function with the [`HotReloadEntry`] signature.
+///
+/// ```ignore (Only valid in a dylib crate)
+/// use bevy::app::{hot_reload_entry, HotReloadContext, Update};
+///
+/// fn gameplay(context: &mut HotReloadContext) {
+///     context.add_systems(Update, move_player);
+/// }
+///
+/// hot_reload_entry!(gameplay);
+/// ```
+#[macro_export]
+macro_rules! hot_reload_entry {
+    ($entry:path) => {
+        #[no_mangle]
+        pub fn bevy_hot_reload(context: &mut $crate::HotReloadContext) {
+            $entry(context);
+        }
+    };
+}
+
+/// Loads the systems of a library, and reloads them whenever the library changes.
+///
+/// This makes it possible to iterate on gameplay code without restarting the app:
+/// 1. Move the systems into their own crate with `crate-type = ["dylib"]`, and export a
+///    [`HotReloadEntry`] with [`hot_reload_entry!`](crate::hot_reload_entry).
+/// 2. Build the app and the library with the `dynamic_linking` feature, so they share a single copy of Bevy.
+/// 3. Add this plugin with the path of the built library, then rebuild the library while the app is running.
+///
+/// When the library changes, the systems it added are replaced by the ones of the new version,
+/// which are initialized like any other [`Schedule`]. The types it [registered](HotReloadContext::register_type)
+/// replace their previous registrations in the [`AppTypeRegistry`].
+///
+/// Components and resources are kept as they are in memory, with the same [`ComponentId`](bevy_ecs::component::ComponentId),
+/// so only changes that keep the layout and meaning of the existing values are supported, like changing
+/// systems or methods. Changing the memory layout of a component or resource that already exists in the
+/// [`World`] fails with [`HotReloadError::IncompatibleType`], and requires a restart. Changes that keep the
+/// layout but change the meaning of fields, like swapping two fields of the same type, are not detected.
+///
+/// Libraries are never unloaded, as values, vtables and function pointers created by them may still be in
+/// use anywhere in the app, even after the [`HotReload`] resource is removed. Each reload keeps the previous
+/// version of the library in memory until the app exits.
+///
+/// ```no_run
+/// # use bevy_app::{App, HotReloadPlugin, Update, FixedUpdate};
+/// App::new()
+///     .add_plugins(HotReloadPlugin::new("target/debug/libgameplay.so").with_schedule(FixedUpdate))
+///     .run();
+/// ```
+pub struct HotReloadPlugin {
+    source: HotReloadSource,
+    schedules: Vec<InternedScheduleLabel>,
+}
+
+impl HotReloadPlugin {
+    /// Creates a plugin that loads the library at `path`.
+    ///
+    /// By default, the library can only add systems to [`Update`].
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            source: HotReloadSource::Library(path.into()),
+            schedules: vec![Update.intern()],
+        }
+    }
+
+    /// Creates a plugin that adds the systems of `entry`, which is linked into the app.
+    ///
+    /// This is useful to ship the systems of a hot-reloadable library in release builds.
+    pub fn from_entry(entry: HotReloadEntry) -> Self {
+        Self {
+            source: HotReloadSource::Static(entry),
+            schedules: vec![Update.intern()],
+        }
+    }
+
+    /// Allows the library to add systems to `schedule`.
+    pub fn with_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedules.push(schedule.intern());
+        self
+    }
+}
+
+impl Plugin for HotReloadPlugin {
+    fn build(&self, app: &mut App) {
+        for &label in &self.schedules {
+            app.add_systems(label, run_hot_reload_schedule(label).in_set(HotReloadSet));
+        }
+        app.add_systems(First, check_hot_reload.in_set(HotReloadSet))
+            .insert_resource(HotReload {
+                source: self.source.clone(),
+                schedules: self.schedules.clone(),
+                loaded_schedules: Vec::new(),
+                owned_types: Vec::new(),
+                loaded_modified: None,
+                pending_modified: None,
+                reload_requested: false,
+                generation: 0,
+            });
+        if let Err(error) = HotReload::reload(app.world_mut()) {
+            error!("Failed to load hot-reloadable systems: {error}");
+        }
+    }
+}
+
+/// The [`SystemSet`] of the systems that check for and run hot-reloadable systems.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HotReloadSet;
+
+/// The label of the [`Schedule`] that contains the hot-reloadable systems added to the wrapped schedule.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HotReloadSchedule(pub InternedScheduleLabel);
+
+/// Where the systems of a [`HotReloadPlugin`] come from.
+#[derive(Clone, Debug)]
+pub enum HotReloadSource {
+    /// A dynamic library that exports a [`HotReloadEntry`].
+    Library(PathBuf),
+    /// A [`HotReloadEntry`] that is linked into the app.
+    Static(HotReloadEntry),
+}
+
+/// Collects the systems and types of a hot-reloadable library.
+#[derive(Default)]
+pub struct HotReloadContext {
+    schedules: HashMap<InternedScheduleLabel, Schedule>,
+    type_registry: TypeRegistry,
+    layouts: HashMap<TypeId, Layout>,
+}
+
+impl HotReloadContext {
+    /// Adds systems to `schedule`, which must have been allowed with [`HotReloadPlugin::with_schedule`].
+    pub fn add_systems<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        let label = schedule.intern();
+        self.schedules
+            .entry(label)
+            .or_insert_with(|| Schedule::new(HotReloadSchedule(label)))
+            .add_systems(systems);
+        self
+    }
+
+    /// Registers the type `T` and its dependencies in the [`AppTypeRegistry`].
+    ///
+    /// If `T` is a component or resource that exists in the [`World`], reloading fails if its layout changed.
+    pub fn register_type<T: GetTypeRegistration>(&mut self) -> &mut Self {
+        self.type_registry.register::<T>();
+        self.layouts.insert(TypeId::of::<T>(), Layout::new::<T>());
+        self
+    }
+}
+
+/// An error that occurs when hot-reloading systems.
+#[derive(Debug, Error, Display, From)]
+pub enum HotReloadError {
+    /// The library couldn't be copied.
+    #[display("failed to copy the library: {_0}")]
+    Io(std::io::Error),
+    /// The library or its entry point couldn't be loaded.
+    #[display("failed to load the library: {_0}")]
+    Library(libloading::Error),
+    /// A component or resource changed its memory layout.
+    #[display("the layout of `{type_path}` changed, which requires a restart")]
+    #[from(ignore)]
+    IncompatibleType {
+        /// The path of the type.
+        type_path: &'static str,
+    },
+}
+
+/// Tracks the systems and types loaded by a [`HotReloadPlugin`].
+#[derive(Resource)]
+pub struct HotReload {
+    source: HotReloadSource,
+    schedules: Vec<InternedScheduleLabel>,
+    loaded_schedules: Vec<InternedScheduleLabel>,
+    owned_types: Vec<TypeId>,
+    loaded_modified: Option<SystemTime>,
+    pending_modified: Option<SystemTime>,
+    reload_requested: bool,
+    generation: u32,
+}
+
+impl HotReload {
+    /// Returns where the systems are loaded from.
+    pub fn source(&self) -> &HotReloadSource {
+        &self.source
+    }
+
+    /// Changes where the systems are loaded from, and reloads them.
+    pub fn set_source(&mut self, source: HotReloadSource) {
+        self.source = source;
+        self.reload_requested = true;
+    }
+
+    /// Reloads the systems at the start of the next frame, even if the library didn't change.
+    pub fn request_reload(&mut self) {
+        self.reload_requested = true;
+    }
+
+    /// Returns how many times the systems have been loaded.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Loads the systems and types of the current [source](Self::source), replacing the previously
+    /// loaded ones.
+    ///
+    /// If an error is returned, the previously loaded systems are left untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`HotReload`] resource doesn't exist.
+    pub fn reload(world: &mut World) -> Result<(), HotReloadError> {
+        world.resource_scope(|world, mut hot_reload: Mut<HotReload>| hot_reload.reload_in(world))
+    }
+
+    fn reload_in(&mut self, world: &mut World) -> Result<(), HotReloadError> {
+        let entry = match &self.source {
+            HotReloadSource::Static(entry) => *entry,
+            HotReloadSource::Library(path) => {
+                self.loaded_modified = modified(path);
+                load_library(path, self.generation)?
+            }
+        };
+        let mut context = HotReloadContext::default();
+        entry(&mut context);
+
+        let components = world.components();
+        for (&type_id, &layout) in &context.layouts {
+            let id = components
+                .get_id(type_id)
+                .or_else(|| components.get_resource_id(type_id));
+            if id
+                .and_then(|id| components.get_info(id))
+                .is_some_and(|info| info.layout() != layout)
+            {
+                return Err(HotReloadError::IncompatibleType {
+                    type_path: context
+                        .type_registry
+                        .get(type_id)
+                        .map_or("<unknown>", |registration| {
+                            registration.type_info().type_path()
+                        }),
+                });
+            }
+        }
+
+        let registry = world.get_resource_or_init::<AppTypeRegistry>().clone();
+        let previously_owned: HashSet<TypeId> = self.owned_types.drain(..).collect();
+        {
+            let mut registry = registry.write();
+            for registration in context.type_registry.iter() {
+                let type_id = registration.type_id();
+                // Types that are registered by the app itself are left untouched.
+                if registry.contains(type_id) && !previously_owned.contains(&type_id) {
+                    continue;
+                }
+                self.owned_types.push(type_id);
+                registry.overwrite_registration(registration.clone());
+            }
+        }
+
+        let registry = registry.read();
+        for &type_id in &self.owned_types {
+            let registration = registry.get(type_id).unwrap();
+            if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                reflect_component.register_component(world);
+            }
+            if let Some(reflect_resource) = registration.data::<ReflectResource>() {
+                reflect_resource.register_resource(world);
+            }
+        }
+
+        let mut schedules = world.resource_mut::<Schedules>();
+        for label in self.loaded_schedules.drain(..) {
+            schedules.remove(HotReloadSchedule(label));
+        }
+        for (label, schedule) in context.schedules {
+            if !self.schedules.contains(&label) {
+                warn!("Hot-reloadable systems can't be added to {label:?}, use `HotReloadPlugin::with_schedule` to allow it");
+                continue;
+            }
+            schedules.insert(schedule);
+            self.loaded_schedules.push(label);
+        }
+
+        self.generation += 1;
+        info!(
+            "Loaded hot-reloadable systems (generation {})",
+            self.generation
+        );
+        Ok(())
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Returns the path that version `generation` of the library at `path` is copied to before being loaded.
+fn library_copy_path(path: &Path, generation: u32) -> PathBuf {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    std::env::temp_dir().join(format!(
+        "bevy-hot-reload-{}-{generation}-{file_name}",
+        std::process::id()
+    ))
+}
+
+#[allow(
+    unsafe_code,
+    reason = "Loading a library runs its initialization code, and its entry point can't be type checked."
+)]
+/// Loads the library at `path` and returns its entry point. The library is never unloaded.
+fn load_library(path: &Path, generation: u32) -> Result<HotReloadEntry, HotReloadError> {
+    // Most platforms return the already loaded library when loading the same path again,
+    // so each version is loaded from its own copy.
+    let copy = library_copy_path(path, generation);
+    fs::copy(path, &copy)?;
+    let loaded = (|| {
+        // SAFETY: Hot-reloadable libraries are built for this app, and are trusted like the app itself.
+        let library = unsafe { libloading::Library::new(&copy) }?;
+        // SAFETY: `hot_reload_entry!` exports the entry point with the `HotReloadEntry` signature.
+        let entry = *unsafe { library.get::<HotReloadEntry>(HOT_RELOAD_ENTRY_POINT.as_bytes()) }?;
+        Ok::<_, libloading::Error>((library, entry))
+    })();
+    match loaded {
+        Ok((library, entry)) => {
+            // Unloading the library would invalidate everything that it created and that is still in use.
+            core::mem::forget(library);
+            // Loaded libraries stay mapped after their file is removed on Unix. Other platforms lock
+            // the file while it is loaded, so its copy is left behind.
+            if cfg!(unix) {
+                let _ = fs::remove_file(&copy);
+            }
+            Ok(entry)
+        }
+        Err(error) => {
+            let _ = fs::remove_file(&copy);
+            Err(error.into())
+        }
+    }
+}
+
+fn run_hot_reload_schedule(label: InternedScheduleLabel) -> impl FnMut(&mut World) {
+    move |world| {
+        // The library may not have added any systems to this schedule.
+        let _ = world.try_run_schedule(HotReloadSchedule(label));
+    }
+}
+
+/// Reloads the systems when their library changed, or when a reload was [requested](HotReload::request_reload).
+fn check_hot_reload(world: &mut World) {
+    let mut hot_reload = world.resource_mut::<HotReload>();
+    let mut should_reload = core::mem::take(&mut hot_reload.reload_requested);
+    if let HotReloadSource::Library(path) = &hot_reload.source {
+        let modified = modified(path);
+        // Wait until the library stops changing, so that a library that is still being written isn't loaded.
+        let previous = core::mem::replace(&mut hot_reload.pending_modified, modified);
+        should_reload |=
+            modified.is_some() && modified != hot_reload.loaded_modified && previous == modified;
+    }
+    if should_reload {
+        if let Err(error) = HotReload::reload(world) {
+            error!("Failed to hot-reload systems: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+    use bevy_reflect::Reflect;
+
+    use super::library_copy_path;
+    use crate::{
+        App, HotReload, HotReloadContext, HotReloadError, HotReloadPlugin, HotReloadSource, Update,
+    };
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Counter(u32);
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Step(u32);
+
+    fn first_version(context: &mut HotReloadContext) {
+        context
+            .register_type::<Counter>()
+            .register_type::<Step>()
+            .add_systems(
+                Update,
+                |step: Res<Step>, mut counters: Query<&mut Counter>| {
+                    for mut counter in &mut counters {
+                        counter.0 += step.0;
+                    }
+                },
+            );
+    }
+
+    fn second_version(context: &mut HotReloadContext) {
+        context
+            .register_type::<Counter>()
+            .register_type::<Step>()
+            .add_systems(
+                Update,
+                |step: Res<Step>, mut counters: Query<&mut Counter>| {
+                    for mut counter in &mut counters {
+                        counter.0 *= step.0;
+                    }
+                },
+            );
+    }
+
+    #[test]
+    fn reload_preserves_state() {
+        let mut app = App::new();
+        app.add_plugins(HotReloadPlugin::from_entry(first_version))
+            .insert_resource(Step(2));
+        let entity = app.world_mut().spawn(Counter(1)).id();
+
+        app.update();
+        assert_eq!(app.world().get::<Counter>(entity), Some(&Counter(3)));
+
+        app.world_mut()
+            .resource_mut::<HotReload>()
+            .set_source(HotReloadSource::Static(second_version));
+        app.update();
+        assert_eq!(app.world().resource::<HotReload>().generation(), 2);
+        assert_eq!(app.world().resource::<Step>().0, 2);
+        assert_eq!(app.world().get::<Counter>(entity), Some(&Counter(6)));
+    }
+
+    #[test]
+    fn failed_load_keeps_systems() {
+        let mut app = App::new();
+        app.add_plugins(HotReloadPlugin::from_entry(first_version))
+            .insert_resource(Step(2));
+        let entity = app.world_mut().spawn(Counter(1)).id();
+
+        // A file that isn't a library fails to load, and its copy is removed.
+        let path = std::env::temp_dir().join(format!(
+            "bevy-hot-reload-test-{}-not-a-library",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"not a library").unwrap();
+        app.world_mut()
+            .resource_mut::<HotReload>()
+            .set_source(HotReloadSource::Library(path.clone()));
+        let result = HotReload::reload(app.world_mut());
+        assert!(matches!(result, Err(HotReloadError::Library(_))));
+        assert!(!library_copy_path(&path, 1).exists());
+        std::fs::remove_file(&path).unwrap();
+
+        // A missing library fails to be copied.
+        let result = HotReload::reload(app.world_mut());
+        assert!(matches!(result, Err(HotReloadError::Io(_))));
+
+        // The systems of the previous version are still loaded.
+        assert_eq!(app.world().resource::<HotReload>().generation(), 1);
+        app.world_mut().resource_mut::<HotReload>().reload_requested = false;
+        app.update();
+        assert_eq!(app.world().get::<Counter>(entity), Some(&Counter(3)));
+    }
+}
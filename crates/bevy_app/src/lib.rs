@@ -1,7 +1,8 @@
 // `rustdoc_internals` is needed for `#[doc(fake_variadics)]`
 #![allow(internal_features)]
 #![cfg_attr(any(docsrs, docsrs_dep), feature(doc_auto_cfg, rustdoc_internals))]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "hot_reload"), forbid(unsafe_code))]
+#![cfg_attr(feature = "hot_reload", deny(unsafe_code))]
 #![doc(
     html_logo_url = "https://bevyengine.org/assets/icon.png",
     html_favicon_url = "https://bevyengine.org/assets/icon.png"
@@ -12,21 +13,29 @@
 extern crate alloc;
 
 mod app;
+#[cfg(feature = "hot_reload")]
+mod hot_reload;
 mod main_schedule;
 mod panic_handler;
 mod plugin;
 mod plugin_group;
+mod resource_changes;
 mod schedule_runner;
+mod storage_compaction;
 mod sub_app;
 #[cfg(not(target_arch = "wasm32"))]
 mod terminal_ctrl_c_handler;
 
 pub use app::*;
+#[cfg(feature = "hot_reload")]
+pub use hot_reload::*;
 pub use main_schedule::*;
 pub use panic_handler::*;
 pub use plugin::*;
 pub use plugin_group::*;
+pub use resource_changes::*;
 pub use schedule_runner::*;
+pub use storage_compaction::*;
 pub use sub_app::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use terminal_ctrl_c_handler::*;
@@ -0,0 +1,41 @@
+//! This module provides the plugin that triggers [`OnChangeResource`] observers in
+//! [Bevy](https://bevyengine.org) apps.
+
+use crate::{App, Last, Plugin};
+use bevy_ecs::observer::trigger_resource_changes;
+#[cfg(doc)]
+use bevy_ecs::observer::OnChangeResource;
+
+/// Runs [`trigger_resource_changes`] in [`Last`], so that observers of [`OnChangeResource`] run once
+/// per frame for each resource that changed.
+///
+/// The other resource triggers, like `OnInsertResource`, don't need this plugin. It isn't added by
+/// default since [`trigger_resource_changes`] is an exclusive system.
+///
+/// ```
+/// # use bevy_app::{App, ResourceChangePlugin};
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::observer::OnChangeResource;
+///
+/// #[derive(Resource, Default)]
+/// struct Settings {
+///     vsync: bool,
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(ResourceChangePlugin)
+///     .init_resource::<Settings>()
+///     .world_mut()
+///     .add_resource_observer::<Settings, _, _>(|_: Trigger<OnChangeResource>| {
+///         println!("settings changed");
+///     });
+/// app.update();
+/// ```
+#[derive(Default)]
+pub struct ResourceChangePlugin;
+
+impl Plugin for ResourceChangePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Last, trigger_resource_changes);
+    }
+}
@@ -0,0 +1,92 @@
+//! This module provides the plugin that shrinks the storages of the world in
+//! [Bevy](https://bevyengine.org) apps.
+
+use crate::{App, Last, Plugin};
+use bevy_ecs::{
+    prelude::*,
+    storage::{compact_storage, CompactionPolicy},
+};
+
+/// Runs [`compact_storage`] in [`Last`] every [`interval`](Self::interval) frames, shrinking the
+/// storages of the world following its [`CompactionPolicy`] resource.
+///
+/// Storages only shrink when enough of their capacity is unused, so this is cheap when nothing was
+/// despawned. It isn't added by default since shrinking reallocates storages, which is wasted work
+/// for apps whose entity count stays about the same.
+///
+/// ```
+/// # use bevy_app::{App, StorageCompactionPlugin};
+/// # use bevy_ecs::storage::CompactionPolicy;
+/// let mut app = App::new();
+/// app.add_plugins(StorageCompactionPlugin {
+///     policy: CompactionPolicy {
+///         min_spare_ratio: 0.75,
+///         ..Default::default()
+///     },
+///     interval: 60,
+/// });
+/// app.update();
+/// ```
+#[derive(Clone, Debug)]
+pub struct StorageCompactionPlugin {
+    /// The policy inserted as a resource. It can be changed later through the [`CompactionPolicy`] resource.
+    pub policy: CompactionPolicy,
+    /// The number of frames between two compactions.
+    pub interval: u32,
+}
+
+impl Default for StorageCompactionPlugin {
+    fn default() -> Self {
+        Self {
+            policy: CompactionPolicy::default(),
+            interval: 600,
+        }
+    }
+}
+
+impl Plugin for StorageCompactionPlugin {
+    fn build(&self, app: &mut App) {
+        let interval = self.interval.max(1);
+        app.insert_resource(self.policy.clone()).add_systems(
+            Last,
+            compact_storage.run_if(move |mut frames: Local<u32>| {
+                *frames += 1;
+                if *frames < interval {
+                    return false;
+                }
+                *frames = 0;
+                true
+            }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageCompactionPlugin;
+    use crate::App;
+    use bevy_ecs::{prelude::*, storage::CompactionPolicy};
+
+    #[derive(Component)]
+    #[allow(dead_code)]
+    struct A(u64);
+
+    #[test]
+    fn compacts_every_interval() {
+        let mut app = App::new();
+        app.add_plugins(StorageCompactionPlugin {
+            policy: CompactionPolicy::ALL,
+            interval: 2,
+        });
+        let world = app.world_mut();
+        let entities: Vec<Entity> = (0..100).map(|i| world.spawn(A(i)).id()).collect();
+        for entity in entities {
+            world.despawn(entity);
+        }
+
+        app.update();
+        assert_ne!(app.world().memory_stats().spare_bytes(), 0);
+        app.update();
+        assert_eq!(app.world().memory_stats().spare_bytes(), 0);
+    }
+}
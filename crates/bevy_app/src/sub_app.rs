@@ -2,7 +2,7 @@ use crate::{App, AppLabel, InternedAppLabel, Plugin, Plugins, PluginsState};
 use bevy_ecs::{
     event::EventRegistry,
     prelude::*,
-    schedule::{InternedScheduleLabel, ScheduleBuildSettings, ScheduleLabel},
+    schedule::{InternedScheduleLabel, InternedSystemSet, ScheduleBuildSettings, ScheduleLabel},
     system::{SystemId, SystemInput},
 };
 
@@ -68,6 +68,8 @@ pub struct SubApp {
     /// Panics if an update is attempted while plugins are building.
     pub(crate) plugin_build_depth: usize,
     pub(crate) plugins_state: PluginsState,
+    /// Sets that systems are added to while sandboxed plugins are building.
+    pub(crate) sandbox_sets: Vec<InternedSystemSet>,
     /// The schedule that will be run by [`update`](Self::update).
     pub update_schedule: Option<InternedScheduleLabel>,
     /// A function that gives mutable access to two app worlds. This is primarily
@@ -91,6 +93,7 @@ impl Default for SubApp {
             plugin_names: HashSet::default(),
             plugin_build_depth: 0,
             plugins_state: PluginsState::Adding,
+            sandbox_sets: Vec::new(),
             update_schedule: None,
             extract: None,
         }
@@ -183,12 +186,38 @@ impl SubApp {
         schedule: impl ScheduleLabel,
         systems: impl IntoSystemConfigs<M>,
     ) -> &mut Self {
+        let mut systems = systems.into_configs();
+        for &set in &self.sandbox_sets {
+            systems.in_set_inner(set);
+        }
         let mut schedules = self.world.resource_mut::<Schedules>();
         schedules.add_systems(schedule, systems);
 
         self
     }
 
+    /// Panics if `system` violates the [`AccessPolicy`](bevy_ecs::schedule::AccessPolicy) of a sandbox
+    /// that plugins are currently being built in.
+    pub(crate) fn check_sandbox_policies(&mut self, system: &mut impl System) {
+        system.initialize(&mut self.world);
+        let schedules = self.world.resource::<Schedules>();
+        let mut message = String::new();
+        for &set in &self.sandbox_sets {
+            let Some(policy) = schedules.access_policy(set) else {
+                continue;
+            };
+            for violation in policy.system_violations(self.world.components(), system) {
+                message.push_str(&format!(
+                    " -- {} in set `{set:?}` {violation}\n",
+                    system.name()
+                ));
+            }
+        }
+        if !message.is_empty() {
+            panic!("Observers violate the access policy of their sandbox.\n{message}");
+        }
+    }
+
     /// See [`App::register_system`].
     pub fn register_system<I, O, M>(
         &mut self,
@@ -414,6 +443,51 @@ impl SubApp {
         self
     }
 
+    /// See [`App::record_events`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn record_events<E>(&mut self) -> &mut Self
+    where
+        E: Event + bevy_reflect::PartialReflect + bevy_reflect::GetTypeRegistration,
+    {
+        use bevy_ecs::event::{advance_event_log, record_events, EventLog};
+
+        self.add_event::<E>().register_type::<E>();
+        self.world.init_resource::<EventLog>();
+        if !self.has_system_in(crate::Last, advance_event_log) {
+            self.add_systems(
+                crate::Last,
+                advance_event_log.run_if(resource_exists::<EventLog>),
+            );
+        }
+        self.add_systems(crate::Last, record_events::<E>.before(advance_event_log))
+    }
+
+    /// See [`App::replay_events`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn replay_events<E>(&mut self) -> &mut Self
+    where
+        E: Event + bevy_reflect::FromReflect + bevy_reflect::GetTypeRegistration,
+    {
+        use bevy_ecs::event::{advance_event_replay, replay_events, EventReplay, EventUpdates};
+
+        self.add_event::<E>().register_type::<E>();
+        if !self.has_system_in(crate::Last, advance_event_replay) {
+            self.add_systems(
+                crate::Last,
+                advance_event_replay.run_if(resource_exists::<EventReplay>),
+            );
+        }
+        self.add_systems(crate::First, replay_events::<E>.after(EventUpdates))
+    }
+
+    /// Returns `true` if the schedule with the given label contains the system `system`, or is ordered
+    /// relative to it.
+    #[cfg(feature = "bevy_reflect")]
+    fn has_system_in<M>(&self, label: impl ScheduleLabel, system: impl IntoSystemSet<M>) -> bool {
+        self.get_schedule(label)
+            .is_some_and(|schedule| schedule.graph().contains_set(system.into_system_set()))
+    }
+
     /// See [`App::register_function`].
     #[cfg(feature = "reflect_functions")]
     pub fn register_function<F, Marker>(&mut self, function: F) -> &mut Self
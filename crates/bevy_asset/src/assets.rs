@@ -1,6 +1,6 @@
 use crate::{
-    self as bevy_asset, Asset, AssetEvent, AssetHandleProvider, AssetId, AssetServer, Handle,
-    UntypedHandle,
+    self as bevy_asset, Asset, AssetBudget, AssetEvent, AssetHandleProvider, AssetId, AssetMemory,
+    AssetMemoryUsage, AssetServer, CachedAsset, Handle, UntypedHandle,
 };
 use alloc::sync::Arc;
 use bevy_ecs::{
@@ -8,7 +8,7 @@ use bevy_ecs::{
     system::{Res, ResMut, Resource},
 };
 use bevy_reflect::{Reflect, TypePath};
-use bevy_utils::HashMap;
+use bevy_utils::{tracing::warn, HashMap};
 use core::{any::TypeId, iter::Enumerate, marker::PhantomData, sync::atomic::AtomicU32};
 use crossbeam_channel::{Receiver, Sender};
 use derive_more::derive::{Display, Error};
@@ -290,6 +290,11 @@ pub struct Assets<A: Asset> {
     /// Assets managed by the `Assets` struct with live strong `Handle`s
     /// originating from `get_strong_handle`.
     duplicate_handles: HashMap<AssetId<A>, u16>,
+    /// The memory accounting of this collection, if the asset type reports its memory usage.
+    memory: Option<AssetMemory<A>>,
+    /// Assets kept alive until they are evicted to stay within the [`AssetBudget`].
+    cache: HashMap<AssetId<A>, CachedAsset<A>>,
+    eviction_pass: u64,
 }
 
 impl<A: Asset> Default for Assets<A> {
@@ -303,6 +308,9 @@ impl<A: Asset> Default for Assets<A> {
             hash_map: Default::default(),
             queued_events: Default::default(),
             duplicate_handles: Default::default(),
+            memory: None,
+            cache: Default::default(),
+            eviction_pass: 0,
         }
     }
 }
@@ -451,6 +459,10 @@ impl<A: Asset> Assets<A> {
     pub fn remove_untracked(&mut self, id: impl Into<AssetId<A>>) -> Option<A> {
         let id: AssetId<A> = id.into();
         self.duplicate_handles.remove(&id);
+        self.cache.remove(&id);
+        if let Some(memory) = &mut self.memory {
+            memory.remove(id);
+        }
         match id {
             AssetId::Index { index, .. } => self.dense_storage.remove_still_alive(index),
             AssetId::Uuid { uuid } => self.hash_map.remove(&uuid),
@@ -471,10 +483,82 @@ impl<A: Asset> Assets<A> {
             AssetId::Uuid { uuid } => self.hash_map.remove(&uuid).is_some(),
         };
         if existed {
+            if let Some(memory) = &mut self.memory {
+                memory.remove(id);
+            }
             self.queued_events.push(AssetEvent::Removed { id });
         }
     }
 
+    /// Enables memory accounting for this collection, using [`AssetMemoryUsage`]. The memory usage of an asset is
+    /// updated when its [`AssetEvent::Added`] or [`AssetEvent::Modified`] event is sent.
+    pub fn track_memory_usage(&mut self)
+    where
+        A: AssetMemoryUsage,
+    {
+        if self.memory.is_some() {
+            return;
+        }
+        let mut memory = AssetMemory::new(A::memory_usage);
+        for (id, asset) in self.iter() {
+            memory.update(id, asset);
+        }
+        self.memory = Some(memory);
+    }
+
+    /// Sets the [`AssetBudget`] of this collection, and enables memory accounting.
+    pub fn set_budget(&mut self, budget: AssetBudget)
+    where
+        A: AssetMemoryUsage,
+    {
+        self.track_memory_usage();
+        let memory = self.memory.as_mut().unwrap();
+        if budget.cache_loaded {
+            memory.added.extend(self.dense_storage.ids());
+        }
+        memory.budget = Some(budget);
+    }
+
+    /// Returns the [`AssetBudget`] of this collection, if it has one.
+    pub fn budget(&self) -> Option<&AssetBudget> {
+        self.memory.as_ref()?.budget.as_ref()
+    }
+
+    /// Returns the number of bytes used by the assets in this collection, as reported by [`AssetMemoryUsage`].
+    /// Returns `None` if memory accounting is not enabled with [`Assets::track_memory_usage`] or [`Assets::set_budget`].
+    pub fn memory_usage(&self) -> Option<usize> {
+        Some(self.memory.as_ref()?.total)
+    }
+
+    /// Returns the number of bytes used by the asset with the given `id`, as reported by [`AssetMemoryUsage`].
+    pub fn asset_memory_usage(&self, id: impl Into<AssetId<A>>) -> Option<usize> {
+        self.memory.as_ref()?.sizes.get(&id.into()).copied()
+    }
+
+    /// Keeps the asset of the given strong `handle` alive after all other strong handles to it are dropped, until it
+    /// is evicted to stay within the [`AssetBudget`] of this collection. The asset is never evicted if this
+    /// collection has no budget. Weak handles are ignored.
+    pub fn cache(&mut self, handle: &Handle<A>) {
+        if handle.is_strong() {
+            self.cache
+                .entry(handle.id())
+                .or_insert_with(|| CachedAsset {
+                    handle: handle.clone(),
+                    last_used: self.eviction_pass,
+                });
+        }
+    }
+
+    /// Removes the asset with the given `id` from the cache, which drops it if there are no other strong handles to it.
+    pub fn uncache(&mut self, id: impl Into<AssetId<A>>) {
+        self.cache.remove(&id.into());
+    }
+
+    /// Returns `true` if the asset with the given `id` is kept alive by the cache of this collection.
+    pub fn is_cached(&self, id: impl Into<AssetId<A>>) -> bool {
+        self.cache.contains_key(&id.into())
+    }
+
     /// Returns `true` if there are no assets in this collection.
     pub fn is_empty(&self) -> bool {
         self.dense_storage.is_empty() && self.hash_map.is_empty()
@@ -556,13 +640,103 @@ impl<A: Asset> Assets<A> {
         }
     }
 
+    /// A system that caches newly loaded assets, and evicts cached assets that are no longer used while this
+    /// collection is over its [`AssetBudget`].
+    pub fn evict_assets(mut assets: ResMut<Self>, asset_server: Res<AssetServer>) {
+        let assets = &mut *assets;
+        let Some(memory) = &mut assets.memory else {
+            return;
+        };
+        let Some(budget) = &memory.budget else {
+            return;
+        };
+
+        assets.eviction_pass += 1;
+        let pass = assets.eviction_pass;
+        for id in memory.added.drain(..) {
+            if budget.cache_loaded && asset_server.get_path(id).is_some() {
+                if let Some(handle) = asset_server.get_id_handle(id) {
+                    assets.cache.entry(id).or_insert(CachedAsset {
+                        handle,
+                        last_used: pass,
+                    });
+                }
+            }
+        }
+        for cached in assets.cache.values_mut() {
+            if cached.is_used() {
+                cached.last_used = pass;
+            }
+        }
+
+        if memory.total <= budget.max_bytes {
+            memory.exhausted = false;
+            return;
+        }
+        let mut candidates: Vec<_> = assets
+            .cache
+            .iter()
+            .filter(|(_, cached)| !cached.is_used())
+            .map(|(id, cached)| (cached.last_used, *id))
+            .collect();
+        candidates.sort_unstable_by_key(|(last_used, _)| *last_used);
+        // Dropping the cached handles frees the assets in `track_assets`, which updates the accounting
+        let mut total = memory.total;
+        for (_, id) in candidates {
+            if total <= budget.max_bytes {
+                break;
+            }
+            total -= memory.sizes.get(&id).copied().unwrap_or(0);
+            assets.cache.remove(&id);
+        }
+        if total > budget.max_bytes && !memory.exhausted {
+            warn!(
+                "Assets<{}> uses {total} bytes, over its budget of {} bytes, but no cached asset is left to evict",
+                core::any::type_name::<A>(),
+                budget.max_bytes
+            );
+        }
+        memory.exhausted = total > budget.max_bytes;
+    }
+
     /// A system that applies accumulated asset change events to the [`Events`] resource.
     ///
     /// [`Events`]: bevy_ecs::event::Events
     pub fn asset_events(mut assets: ResMut<Self>, mut events: EventWriter<AssetEvent<A>>) {
+        assets.update_memory_usage();
         events.send_batch(assets.queued_events.drain(..));
     }
 
+    /// Updates the memory accounting for the assets added or modified since the last [`Assets::asset_events`].
+    fn update_memory_usage(&mut self) {
+        let Some(memory) = &mut self.memory else {
+            return;
+        };
+        for event in &self.queued_events {
+            let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = *event else {
+                continue;
+            };
+            let asset = match id {
+                AssetId::Index { index, .. } => self.dense_storage.get(index),
+                AssetId::Uuid { uuid } => self.hash_map.get(&uuid),
+            };
+            if let Some(asset) = asset {
+                memory.update(id, asset);
+                let cache_loaded = memory.budget.as_ref().is_some_and(|b| b.cache_loaded);
+                if cache_loaded && matches!(event, AssetEvent::Added { .. }) {
+                    memory.added.push(id);
+                }
+            }
+        }
+    }
+
+    /// A run condition for [`evict_assets`]. The system will not run if this collection has no budget.
+    ///
+    /// [`evict_assets`]: Self::evict_assets
+    pub(crate) fn evict_assets_condition(assets: Res<Self>) -> bool {
+        assets.budget().is_some()
+    }
+
     /// A run condition for [`asset_events`]. The system will not run if there are no events to
     /// flush.
     ///
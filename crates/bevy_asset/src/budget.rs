@@ -0,0 +1,109 @@
+use crate::{Asset, AssetId, Handle};
+use alloc::sync::Arc;
+use bevy_utils::HashMap;
+
+/// Reports the memory used by an [`Asset`], which enables memory accounting and [`AssetBudget`]s for its
+/// [`Assets`](crate::Assets) collection.
+///
+/// ```
+/// # use bevy_asset::{Asset, AssetMemoryUsage};
+/// # use bevy_reflect::TypePath;
+/// #[derive(Asset, TypePath)]
+/// struct Heightmap {
+///     heights: Vec<f32>,
+/// }
+///
+/// impl AssetMemoryUsage for Heightmap {
+///     fn memory_usage(&self) -> usize {
+///         size_of::<Self>() + self.heights.capacity() * size_of::<f32>()
+///     }
+/// }
+/// ```
+pub trait AssetMemoryUsage {
+    /// Returns the number of bytes used by this asset, including the heap allocations it owns.
+    fn memory_usage(&self) -> usize;
+}
+
+/// A memory budget for an [`Assets`](crate::Assets) collection, set with
+/// [`AssetApp::set_asset_budget`](crate::AssetApp::set_asset_budget) or [`Assets::set_budget`](crate::Assets::set_budget).
+///
+/// Assets in the collection can be kept in a cache after their last strong [`Handle`] is dropped, either by
+/// calling [`Assets::cache`](crate::Assets::cache) or by setting [`cache_loaded`](Self::cache_loaded). Cached assets
+/// stay in memory (and are returned by [`AssetServer::load`](crate::AssetServer::load) without reloading them) until
+/// the collection goes over budget. Then the cached assets that are only referenced by the cache are evicted, least
+/// recently used first, until the collection is back within budget. An evicted asset is reloaded from its path
+/// the next time it is loaded through the [`AssetServer`](crate::AssetServer).
+///
+/// Assets with live strong handles outside the cache are never evicted, so the collection can stay over budget
+/// if they alone exceed it.
+#[derive(Clone, Debug)]
+pub struct AssetBudget {
+    /// The maximum number of bytes, as reported by [`AssetMemoryUsage`], used by the assets in the collection.
+    pub max_bytes: usize,
+    /// If `true`, every asset loaded from a path by the [`AssetServer`](crate::AssetServer) is cached.
+    pub cache_loaded: bool,
+}
+
+impl AssetBudget {
+    /// Creates a new [`AssetBudget`] of `max_bytes` that caches every asset loaded from a path.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            cache_loaded: true,
+        }
+    }
+}
+
+/// The memory accounting of an [`Assets`](crate::Assets) collection.
+pub(crate) struct AssetMemory<A: Asset> {
+    pub(crate) size_of: fn(&A) -> usize,
+    pub(crate) sizes: HashMap<AssetId<A>, usize>,
+    pub(crate) total: usize,
+    pub(crate) budget: Option<AssetBudget>,
+    /// Assets added since the last eviction, to be cached if [`AssetBudget::cache_loaded`] is set.
+    pub(crate) added: Vec<AssetId<A>>,
+    /// Set while the collection is over budget without any cached asset left to evict.
+    pub(crate) exhausted: bool,
+}
+
+impl<A: Asset> AssetMemory<A> {
+    pub(crate) fn new(size_of: fn(&A) -> usize) -> Self {
+        Self {
+            size_of,
+            sizes: HashMap::default(),
+            total: 0,
+            budget: None,
+            added: Vec::new(),
+            exhausted: false,
+        }
+    }
+
+    pub(crate) fn update(&mut self, id: AssetId<A>, asset: &A) {
+        let size = (self.size_of)(asset);
+        let old = self.sizes.insert(id, size).unwrap_or(0);
+        self.total = self.total - old + size;
+    }
+
+    pub(crate) fn remove(&mut self, id: AssetId<A>) {
+        if let Some(size) = self.sizes.remove(&id) {
+            self.total -= size;
+        }
+    }
+}
+
+/// An asset kept alive by the cache of an [`Assets`](crate::Assets) collection.
+pub(crate) struct CachedAsset<A: Asset> {
+    pub(crate) handle: Handle<A>,
+    /// The last eviction pass in which the asset had strong handles outside the cache.
+    pub(crate) last_used: u64,
+}
+
+impl<A: Asset> CachedAsset<A> {
+    /// Returns `true` if the asset has strong handles outside the cache.
+    pub(crate) fn is_used(&self) -> bool {
+        match &self.handle {
+            Handle::Strong(handle) => Arc::strong_count(handle) > 1,
+            Handle::Weak(_) => false,
+        }
+    }
+}
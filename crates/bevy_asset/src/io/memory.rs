@@ -198,7 +198,7 @@ impl Data {
     fn path(&self) -> &Path {
         &self.path
     }
-    fn value(&self) -> &[u8] {
+    pub(crate) fn value(&self) -> &[u8] {
         match &self.value {
             Value::Vec(vec) => vec,
             Value::Static(value) => value,
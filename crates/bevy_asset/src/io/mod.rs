@@ -12,6 +12,8 @@ pub mod embedded;
 pub mod file;
 pub mod gated;
 pub mod memory;
+#[cfg(all(feature = "asset_pack", not(target_arch = "wasm32")))]
+pub mod pack;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
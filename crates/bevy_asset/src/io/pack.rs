@@ -0,0 +1,692 @@
+//! Asset packs: archives that store the assets and meta files of a whole [`AssetSource`](crate::io::AssetSource).
+//!
+//! Shipping a single pack is much faster to install and open than shipping thousands of loose files.
+//! Packs are usually created from the output of the [`AssetProcessor`](crate::processor::AssetProcessor)
+//! with [`AssetProcessor::write_pack`](crate::processor::AssetProcessor::write_pack), and read with
+//! a [`PackAssetReader`]:
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_asset::{AssetApp, io::{AssetSource, AssetSourceId}};
+//! # let mut app = App::new();
+//! app.register_asset_source(
+//!     AssetSourceId::Default,
+//!     AssetSource::build().with_reader(AssetSource::get_pack_reader(["assets.pack"])),
+//! );
+//! ```
+//!
+//! A pack starts with a header, followed by the data of each entry, an index of the entries, and a footer.
+//! Each entry can be compressed, and can store a hash of its content that is verified when it is read.
+
+use crate::io::{
+    get_meta_path, AssetReader, AssetReaderError, ErasedAssetReader, MissingAssetSourceError,
+    MissingProcessedAssetReaderError, PathStream, Reader, VecReader,
+};
+use alloc::{collections::BTreeSet, sync::Arc};
+use async_fs::File;
+use bevy_utils::HashMap;
+use derive_more::derive::{Display, Error, From};
+use futures_lite::{AsyncReadExt, AsyncSeekExt, StreamExt};
+use std::{
+    io::{ErrorKind, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+const MAGIC: &[u8; 8] = b"BEVYPACK";
+const VERSION: u32 = 1;
+const HEADER_SIZE: u64 = 12;
+const FOOTER_SIZE: u64 = 20;
+/// The maximum ratio between the decompressed and compressed size of an LZ4 block.
+const MAX_LZ4_RATIO: u64 = 255;
+
+/// How the data of a pack entry is compressed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PackCompression {
+    /// The data is stored as is.
+    None,
+    /// The data is compressed with LZ4, which is fast to decompress.
+    ///
+    /// Entries that don't get smaller when compressed are stored as is.
+    #[default]
+    Lz4,
+}
+
+impl PackCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            PackCompression::None => 0,
+            PackCompression::Lz4 => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, PackError> {
+        match byte {
+            0 => Ok(PackCompression::None),
+            1 => Ok(PackCompression::Lz4),
+            _ => Err(PackError::Invalid("unknown compression")),
+        }
+    }
+}
+
+/// Settings used to write a pack.
+#[derive(Clone, Copy, Debug)]
+pub struct PackSettings {
+    /// How the entries are compressed.
+    pub compression: PackCompression,
+    /// Whether a hash of each entry is stored and verified when the entry is read.
+    pub hash: bool,
+}
+
+impl Default for PackSettings {
+    fn default() -> Self {
+        Self {
+            compression: PackCompression::Lz4,
+            hash: true,
+        }
+    }
+}
+
+/// An error that occurs while reading or writing a pack.
+#[derive(Error, Display, Debug, From)]
+pub enum PackError {
+    /// Encountered an I/O error.
+    #[display("encountered an I/O error: {_0}")]
+    Io(std::io::Error),
+    /// The pack is not in a supported format.
+    #[display("invalid pack: {_0}")]
+    #[error(ignore)]
+    #[from(ignore)]
+    Invalid(&'static str),
+}
+
+impl From<PackError> for AssetReaderError {
+    fn from(error: PackError) -> Self {
+        match error {
+            PackError::Io(error) => error.into(),
+            PackError::Invalid(message) => {
+                std::io::Error::new(ErrorKind::InvalidData, message).into()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EntryKind {
+    Asset,
+    Meta,
+}
+
+#[derive(Clone, Debug)]
+struct PackEntry {
+    pack: usize,
+    offset: u64,
+    stored_len: u64,
+    len: u64,
+    compression: PackCompression,
+    hash: Option<[u8; 32]>,
+}
+
+/// Writes assets and meta files into a pack.
+///
+/// ```
+/// # use bevy_asset::io::pack::{PackCompression, PackWriter};
+/// # use std::path::Path;
+/// let mut writer = PackWriter::new(Vec::new(), true).unwrap();
+/// writer
+///     .add_asset(Path::new("text/hello.txt"), b"hello", PackCompression::None)
+///     .unwrap();
+/// let pack: Vec<u8> = writer.finish().unwrap();
+/// ```
+pub struct PackWriter<W: Write> {
+    writer: W,
+    offset: u64,
+    hash: bool,
+    entries: Vec<(String, EntryKind, PackEntry)>,
+}
+
+impl<W: Write> PackWriter<W> {
+    /// Creates a writer that writes a pack to `writer`.
+    ///
+    /// If `hash` is true, a hash of each entry is stored and verified when the entry is read.
+    pub fn new(mut writer: W, hash: bool) -> Result<Self, PackError> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            offset: HEADER_SIZE,
+            hash,
+            entries: Vec::new(),
+        })
+    }
+
+    /// Adds the asset at `path` to the pack.
+    pub fn add_asset(
+        &mut self,
+        path: &Path,
+        bytes: &[u8],
+        compression: PackCompression,
+    ) -> Result<(), PackError> {
+        self.add(path, EntryKind::Asset, bytes, compression)
+    }
+
+    /// Adds the meta file of the asset at `path` to the pack.
+    pub fn add_meta(
+        &mut self,
+        path: &Path,
+        bytes: &[u8],
+        compression: PackCompression,
+    ) -> Result<(), PackError> {
+        self.add(path, EntryKind::Meta, bytes, compression)
+    }
+
+    fn add(
+        &mut self,
+        path: &Path,
+        kind: EntryKind,
+        bytes: &[u8],
+        compression: PackCompression,
+    ) -> Result<(), PackError> {
+        let compressed = match compression {
+            PackCompression::None => None,
+            PackCompression::Lz4 => {
+                Some(lz4_flex::block::compress(bytes)).filter(|data| data.len() < bytes.len())
+            }
+        };
+        let (data, compression) = match &compressed {
+            Some(data) => (data.as_slice(), compression),
+            None => (bytes, PackCompression::None),
+        };
+        self.writer.write_all(data)?;
+        self.entries.push((
+            normalize_path(path),
+            kind,
+            PackEntry {
+                pack: 0,
+                offset: self.offset,
+                stored_len: data.len() as u64,
+                len: bytes.len() as u64,
+                compression,
+                hash: self.hash.then(|| *blake3::hash(bytes).as_bytes()),
+            },
+        ));
+        self.offset += data.len() as u64;
+        Ok(())
+    }
+
+    /// Writes the index of the pack, and returns the inner writer.
+    pub fn finish(mut self) -> Result<W, PackError> {
+        let mut index = Vec::new();
+        for (path, kind, entry) in &self.entries {
+            index.push(match kind {
+                EntryKind::Asset => 0,
+                EntryKind::Meta => 1,
+            });
+            index.push(entry.compression.to_byte());
+            index.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            index.extend_from_slice(path.as_bytes());
+            index.extend_from_slice(&entry.offset.to_le_bytes());
+            index.extend_from_slice(&entry.stored_len.to_le_bytes());
+            index.extend_from_slice(&entry.len.to_le_bytes());
+            match &entry.hash {
+                Some(hash) => {
+                    index.push(1);
+                    index.extend_from_slice(hash);
+                }
+                None => index.push(0),
+            }
+        }
+        self.writer.write_all(&index)?;
+        self.writer.write_all(&self.offset.to_le_bytes())?;
+        self.writer
+            .write_all(&(self.entries.len() as u32).to_le_bytes())?;
+        self.writer.write_all(MAGIC)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Writes every asset and meta file that `reader` can read into `writer`.
+pub async fn write_pack<W: Write>(
+    reader: &dyn ErasedAssetReader,
+    writer: W,
+    settings: PackSettings,
+) -> Result<W, WritePackError> {
+    async fn add_path<W: Write>(
+        reader: &dyn ErasedAssetReader,
+        writer: &mut PackWriter<W>,
+        path: PathBuf,
+        compression: PackCompression,
+    ) -> Result<(), WritePackError> {
+        if reader.is_directory(&path).await? {
+            let mut paths = reader.read_directory(&path).await?;
+            while let Some(child) = paths.next().await {
+                Box::pin(add_path(reader, writer, child, compression)).await?;
+            }
+            return Ok(());
+        }
+        let mut asset = reader.read(&path).await?;
+        let mut bytes = Vec::new();
+        Reader::read_to_end(&mut asset, &mut bytes)
+            .await
+            .map_err(AssetReaderError::from)?;
+        writer.add_asset(&path, &bytes, compression)?;
+        match reader.read_meta_bytes(&path).await {
+            Ok(meta) => writer.add_meta(&path, &meta, compression)?,
+            Err(AssetReaderError::NotFound(_)) => {}
+            Err(error) => return Err(error.into()),
+        }
+        Ok(())
+    }
+
+    let mut pack = PackWriter::new(writer, settings.hash)?;
+    add_path(reader, &mut pack, PathBuf::new(), settings.compression).await?;
+    Ok(pack.finish()?)
+}
+
+/// An error that occurs while writing a pack with [`write_pack`].
+#[derive(Error, Display, Debug, From)]
+pub enum WritePackError {
+    /// Failed to read an asset.
+    #[display("failed to read an asset: {_0}")]
+    Read(AssetReaderError),
+    /// Failed to write the pack.
+    #[display("failed to write the pack: {_0}")]
+    Pack(PackError),
+    /// The asset source doesn't exist.
+    #[display("{_0}")]
+    MissingSource(MissingAssetSourceError),
+    /// The asset source isn't processed.
+    #[display("{_0}")]
+    MissingProcessedReader(MissingProcessedAssetReaderError),
+}
+
+impl From<std::io::Error> for WritePackError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Pack(error.into())
+    }
+}
+
+#[derive(Default)]
+struct PackIndex {
+    assets: HashMap<PathBuf, PackEntry>,
+    metas: HashMap<PathBuf, PackEntry>,
+    directories: HashMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl PackIndex {
+    async fn load(packs: &[PathBuf]) -> Result<Self, AssetReaderError> {
+        let mut index = PackIndex::default();
+        index.directories.insert(PathBuf::new(), BTreeSet::new());
+        // Entries of later packs replace the entries of earlier ones, so packs can be patched.
+        for (pack, path) in packs.iter().enumerate() {
+            let mut file = File::open(path).await?;
+            let mut header = [0; HEADER_SIZE as usize];
+            file.read_exact(&mut header).await?;
+            if &header[..8] != MAGIC {
+                return Err(PackError::Invalid("missing header").into());
+            }
+            if header[8..] != VERSION.to_le_bytes() {
+                return Err(PackError::Invalid("unsupported version").into());
+            }
+
+            let end = file.seek(SeekFrom::End(-(FOOTER_SIZE as i64))).await?;
+            let mut footer = [0; FOOTER_SIZE as usize];
+            file.read_exact(&mut footer).await?;
+            if &footer[12..] != MAGIC {
+                return Err(PackError::Invalid("missing footer").into());
+            }
+            let index_offset = u64::from_le_bytes(footer[..8].try_into().unwrap());
+            let count = u32::from_le_bytes(footer[8..12].try_into().unwrap());
+            // The index is between the entries and the footer, so its length is bounded by the file.
+            let index_len = end
+                .checked_sub(index_offset)
+                .filter(|_| index_offset >= HEADER_SIZE)
+                .ok_or(PackError::Invalid("index out of bounds"))?;
+            file.seek(SeekFrom::Start(index_offset)).await?;
+            let mut bytes = vec![0; index_len as usize];
+            file.read_exact(&mut bytes).await?;
+
+            let mut bytes = bytes.as_slice();
+            for _ in 0..count {
+                let (kind, path, entry) = read_entry(&mut bytes, pack, index_offset)?;
+                match kind {
+                    EntryKind::Asset => {
+                        index.add_to_directories(&path);
+                        index.assets.insert(path, entry);
+                    }
+                    EntryKind::Meta => {
+                        index.metas.insert(path, entry);
+                    }
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    fn add_to_directories(&mut self, path: &Path) {
+        let mut child = path;
+        while let Some(parent) = child.parent() {
+            let children = self.directories.entry(parent.to_owned()).or_default();
+            if !children.insert(child.to_owned()) {
+                break;
+            }
+            child = parent;
+        }
+    }
+}
+
+/// Reads an entry of the index of a pack, whose entries are stored before `index_offset`.
+fn read_entry(
+    bytes: &mut &[u8],
+    pack: usize,
+    index_offset: u64,
+) -> Result<(EntryKind, PathBuf, PackEntry), PackError> {
+    fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], PackError> {
+        if bytes.len() < len {
+            return Err(PackError::Invalid("truncated index"));
+        }
+        let (taken, rest) = bytes.split_at(len);
+        *bytes = rest;
+        Ok(taken)
+    }
+    fn take_u64(bytes: &mut &[u8]) -> Result<u64, PackError> {
+        Ok(u64::from_le_bytes(take(bytes, 8)?.try_into().unwrap()))
+    }
+
+    let kind = match take(bytes, 1)?[0] {
+        0 => EntryKind::Asset,
+        1 => EntryKind::Meta,
+        _ => return Err(PackError::Invalid("unknown entry kind")),
+    };
+    let compression = PackCompression::from_byte(take(bytes, 1)?[0])?;
+    let path_len = u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap());
+    let path = core::str::from_utf8(take(bytes, path_len as usize)?)
+        .map_err(|_| PackError::Invalid("path is not valid UTF-8"))?;
+    let offset = take_u64(bytes)?;
+    let stored_len = take_u64(bytes)?;
+    let len = take_u64(bytes)?;
+    let hash = match take(bytes, 1)?[0] {
+        0 => None,
+        _ => Some(take(bytes, 32)?.try_into().unwrap()),
+    };
+    // Entries are read into buffers of these sizes, so they must be bounded by the file.
+    if offset < HEADER_SIZE
+        || offset
+            .checked_add(stored_len)
+            .is_none_or(|end| end > index_offset)
+    {
+        return Err(PackError::Invalid("entry out of bounds"));
+    }
+    let max_len = match compression {
+        PackCompression::None => stored_len,
+        PackCompression::Lz4 => stored_len.saturating_mul(MAX_LZ4_RATIO),
+    };
+    if len > max_len {
+        return Err(PackError::Invalid("entry is too large"));
+    }
+    Ok((
+        kind,
+        PathBuf::from(path),
+        PackEntry {
+            pack,
+            offset,
+            stored_len,
+            len,
+            compression,
+            hash,
+        },
+    ))
+}
+
+/// Pack paths always use `/` as separator, regardless of the platform that wrote them.
+fn normalize_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// An [`AssetReader`] that reads assets from one or more pack files, written by a [`PackWriter`].
+///
+/// The index of the packs is read when the first asset is read. When several packs contain the same
+/// asset, the last one wins, so packs can be patched by adding a pack that only contains the changed assets.
+///
+/// [`PackAssetReader`] can be cloned. Clones share the same index.
+#[derive(Clone)]
+pub struct PackAssetReader {
+    packs: Arc<[PathBuf]>,
+    index: Arc<async_lock::OnceCell<PackIndex>>,
+}
+
+impl PackAssetReader {
+    /// Creates a reader for the packs at the given `paths`.
+    pub fn new<P: Into<PathBuf>>(paths: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            packs: paths.into_iter().map(Into::into).collect(),
+            index: Arc::new(async_lock::OnceCell::new()),
+        }
+    }
+
+    /// Returns the paths of the packs, in priority order.
+    pub fn packs(&self) -> &[PathBuf] {
+        &self.packs
+    }
+
+    async fn index(&self) -> Result<&PackIndex, AssetReaderError> {
+        self.index
+            .get_or_try_init(|| PackIndex::load(&self.packs))
+            .await
+    }
+
+    /// Reads the content of `entry`, whose offset and lengths were checked against the size of its pack
+    /// when the index was loaded.
+    async fn read_entry(&self, entry: &PackEntry) -> Result<VecReader, AssetReaderError> {
+        let mut file = File::open(&self.packs[entry.pack]).await?;
+        file.seek(SeekFrom::Start(entry.offset)).await?;
+        let mut bytes = vec![0; entry.stored_len as usize];
+        file.read_exact(&mut bytes).await?;
+        if entry.compression == PackCompression::Lz4 {
+            bytes = lz4_flex::block::decompress(&bytes, entry.len as usize)
+                .map_err(|_| PackError::Invalid("corrupted entry"))?;
+        }
+        if entry
+            .hash
+            .is_some_and(|hash| blake3::hash(&bytes).as_bytes() != &hash)
+        {
+            return Err(PackError::Invalid("entry doesn't match its hash").into());
+        }
+        Ok(VecReader::new(bytes))
+    }
+}
+
+impl AssetReader for PackAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let entry = self
+            .index()
+            .await?
+            .assets
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+        self.read_entry(entry).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let entry = self
+            .index()
+            .await?
+            .metas
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(get_meta_path(path)))?;
+        self.read_entry(entry).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let children = self
+            .index()
+            .await?
+            .directories
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?
+            .clone();
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(self.index().await?.directories.contains_key(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_pack, PackAssetReader, PackCompression, PackSettings, PackWriter};
+    use crate::io::{
+        memory::{Dir, MemoryAssetReader},
+        AssetReader, AssetReaderError, Reader,
+    };
+    use futures_lite::{future::block_on, StreamExt};
+    use std::path::{Path, PathBuf};
+
+    /// A pack file in the temp dir, which is removed when dropped.
+    struct TempPack(PathBuf);
+
+    impl TempPack {
+        fn new(name: &str, bytes: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "bevy-asset-pack-{}-{name}.pack",
+                std::process::id()
+            ));
+            std::fs::write(&path, bytes).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempPack {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn read(reader: &PackAssetReader, path: &str) -> Result<Vec<u8>, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(bytes)
+        })
+    }
+
+    #[test]
+    fn write_and_read_pack() {
+        let dir = Dir::default();
+        let text = "text ".repeat(100);
+        dir.insert_asset_text(Path::new("a.txt"), &text);
+        dir.insert_meta_text(Path::new("a.txt"), "meta");
+        dir.insert_asset_text(Path::new("nested/deep/b.txt"), "b");
+        let source = MemoryAssetReader { root: dir };
+
+        let bytes = block_on(write_pack(&source, Vec::new(), PackSettings::default())).unwrap();
+        assert!(bytes.len() < text.len());
+        let pack = TempPack::new("write_and_read", &bytes);
+        let reader = PackAssetReader::new([&pack.0]);
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), text.as_bytes());
+        assert_eq!(read(&reader, "nested/deep/b.txt").unwrap(), b"b");
+        assert_eq!(
+            block_on(reader.read_meta_bytes(Path::new("a.txt"))).unwrap(),
+            b"meta"
+        );
+        assert!(matches!(
+            read(&reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        assert!(block_on(reader.is_directory(Path::new("nested"))).unwrap());
+        assert!(!block_on(reader.is_directory(Path::new("a.txt"))).unwrap());
+        let root: Vec<PathBuf> = block_on(async {
+            reader
+                .read_directory(Path::new(""))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        assert_eq!(root, [PathBuf::from("a.txt"), PathBuf::from("nested")]);
+    }
+
+    #[test]
+    fn later_packs_override_earlier_ones() {
+        let mut base = PackWriter::new(Vec::new(), true).unwrap();
+        base.add_asset(Path::new("a.txt"), b"old", PackCompression::None)
+            .unwrap();
+        base.add_asset(Path::new("b.txt"), b"b", PackCompression::Lz4)
+            .unwrap();
+        let mut base = base.finish().unwrap();
+
+        let mut patch = PackWriter::new(Vec::new(), false).unwrap();
+        patch
+            .add_asset(Path::new("a.txt"), b"new", PackCompression::None)
+            .unwrap();
+        let patch = patch.finish().unwrap();
+
+        let (base_pack, patch_pack) =
+            (TempPack::new("base", &base), TempPack::new("patch", &patch));
+        let reader = PackAssetReader::new([&base_pack.0, &patch_pack.0]);
+        assert_eq!(read(&reader, "a.txt").unwrap(), b"new");
+        assert_eq!(read(&reader, "b.txt").unwrap(), b"b");
+
+        // Corrupt the content of `a.txt` in the base pack.
+        let position = base.windows(3).position(|bytes| bytes == b"old").unwrap();
+        base[position] = b'x';
+        let corrupted_pack = TempPack::new("corrupted", &base);
+        let reader = PackAssetReader::new([&corrupted_pack.0]);
+        assert!(matches!(
+            read(&reader, "a.txt"),
+            Err(AssetReaderError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn out_of_bounds_entries_are_rejected() {
+        let mut writer = PackWriter::new(Vec::new(), false).unwrap();
+        writer
+            .add_asset(Path::new("a.txt"), &[b'a'; 100], PackCompression::Lz4)
+            .unwrap();
+        let pack = writer.finish().unwrap();
+        let footer = pack.len() - 20;
+        let index_offset = u64::from_le_bytes(pack[footer..footer + 8].try_into().unwrap());
+        // The offset, stored length and length follow the kind, compression and path of the entry.
+        let fields = index_offset as usize + 1 + 1 + 4 + "a.txt".len();
+
+        let corrupt = |position: usize, value: u64| {
+            let mut pack = pack.clone();
+            pack[position..position + 8].copy_from_slice(&value.to_le_bytes());
+            let pack = TempPack::new(&format!("out_of_bounds_{position}_{value}"), &pack);
+            let reader = PackAssetReader::new([&pack.0]);
+            read(&reader, "a.txt")
+        };
+        // Index before the header.
+        assert!(matches!(corrupt(footer, 0), Err(AssetReaderError::Io(_))));
+        // Entry past the index.
+        assert!(matches!(
+            corrupt(fields, index_offset),
+            Err(AssetReaderError::Io(_))
+        ));
+        assert!(matches!(
+            corrupt(fields + 8, u64::MAX),
+            Err(AssetReaderError::Io(_))
+        ));
+        // Decompressed length larger than LZ4 allows.
+        assert!(matches!(
+            corrupt(fields + 16, u64::MAX),
+            Err(AssetReaderError::Io(_))
+        ));
+        assert_eq!(corrupt(fields + 16, 100).unwrap(), [b'a'; 100]);
+    }
+}
@@ -466,6 +466,20 @@ impl AssetSource {
         }
     }
 
+    /// Returns a builder function for a [`PackAssetReader`](crate::io::pack::PackAssetReader) that reads the packs at `paths`,
+    /// which are relative to the asset root. Later packs take priority over earlier ones.
+    ///
+    /// All readers built by the returned function share the same pack index.
+    #[cfg(all(feature = "asset_pack", not(target_arch = "wasm32")))]
+    pub fn get_pack_reader<P: AsRef<std::path::Path>>(
+        paths: impl IntoIterator<Item = P>,
+    ) -> impl FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync {
+        let base_path = super::file::get_base_path();
+        let reader =
+            super::pack::PackAssetReader::new(paths.into_iter().map(|path| base_path.join(path)));
+        move || Box::new(reader.clone())
+    }
+
     /// Returns a builder function for this platform's default [`AssetWriter`](crate::io::AssetWriter). `path` is the relative path to
     /// the asset root. This will return [`None`] if this platform does not support writing assets by default.
     pub fn get_default_writer(
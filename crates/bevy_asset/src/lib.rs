@@ -167,6 +167,7 @@ pub mod prelude {
 }
 
 mod assets;
+mod budget;
 mod direct_access_ext;
 mod event;
 mod folder;
@@ -181,6 +182,7 @@ mod server;
 
 pub use assets::*;
 pub use bevy_asset_macros::Asset;
+pub use budget::*;
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
@@ -244,6 +246,14 @@ pub struct AssetPlugin {
     pub mode: AssetMode,
     /// How/If asset meta files should be checked.
     pub meta_check: AssetMetaCheck,
+    /// If set, the [`AssetProcessor`] will store processed assets in a content-addressed [`ProcessorCache`] at this
+    /// file path (relative to the project root), and reuse them for identical inputs instead of processing them again.
+    /// This can point at a directory shared between checkouts or machines.
+    ///
+    /// This only has an effect in [`AssetMode::Processed`] with the `asset_processor` cargo feature enabled.
+    ///
+    /// [`ProcessorCache`]: processor::ProcessorCache
+    pub processor_cache_path: Option<String>,
 }
 
 /// Controls whether or not assets are pre-processed before being loaded.
@@ -297,6 +307,7 @@ impl Default for AssetPlugin {
             processed_file_path: Self::DEFAULT_PROCESSED_FILE_PATH.to_string(),
             watch_for_changes_override: None,
             meta_check: AssetMetaCheck::default(),
+            processor_cache_path: None,
         }
     }
 }
@@ -344,6 +355,9 @@ impl Plugin for AssetPlugin {
                     {
                         let mut builders = app.world_mut().resource_mut::<AssetSourceBuilders>();
                         let processor = AssetProcessor::new(&mut builders);
+                        if let Some(path) = &self.processor_cache_path {
+                            processor.set_cache(Some(processor::ProcessorCache::new(path)));
+                        }
                         let mut sources = builders.build_sources(false, watch);
                         sources.gate_on_processor(processor.data.clone());
                         // the main asset server shares loaders with the processor asset server
@@ -489,6 +503,9 @@ pub trait AssetApp {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Sets the [`AssetBudget`] of the [`Assets`] collection of `A`, which must already be initialized with
+    /// [`AssetApp::init_asset`].
+    fn set_asset_budget<A: Asset + AssetMemoryUsage>(&mut self, budget: AssetBudget) -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -567,6 +584,12 @@ impl AssetApp for App {
                     .in_set(AssetEvents),
             )
             .add_systems(PreUpdate, Assets::<A>::track_assets.in_set(TrackAssets))
+            .add_systems(
+                PreUpdate,
+                Assets::<A>::evict_assets
+                    .run_if(Assets::<A>::evict_assets_condition)
+                    .before(TrackAssets),
+            )
     }
 
     fn register_asset_reflect<A>(&mut self) -> &mut Self
@@ -592,6 +615,13 @@ impl AssetApp for App {
             .preregister_loader::<L>(extensions);
         self
     }
+
+    fn set_asset_budget<A: Asset + AssetMemoryUsage>(&mut self, budget: AssetBudget) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<Assets<A>>()
+            .set_budget(budget);
+        self
+    }
 }
 
 /// A system set that holds all "track asset" operations.
@@ -616,8 +646,8 @@ mod tests {
             AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets,
+        Asset, AssetApp, AssetBudget, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent,
+        AssetMemoryUsage, AssetPath, AssetPlugin, AssetServer, Assets,
     };
     use alloc::sync::Arc;
     use bevy_app::{App, Update};
@@ -644,6 +674,12 @@ mod tests {
         pub sub_texts: Vec<Handle<SubText>>,
     }
 
+    impl AssetMemoryUsage for CoolText {
+        fn memory_usage(&self) -> usize {
+            self.text.len()
+        }
+    }
+
     #[derive(Asset, TypePath, Debug)]
     pub struct SubText {
         text: String,
@@ -1730,6 +1766,178 @@ mod tests {
         });
     }
 
+    #[test]
+    fn dependency_graph() {
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+        let c_path = "c.cool.ron";
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+        dir.insert_asset_text(Path::new(c_path), c_ron);
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<CoolText> = asset_server.load(a_path);
+        run_app_until(&mut app, |_| {
+            asset_server
+                .is_loaded_with_dependencies(&handle)
+                .then_some(())
+        });
+
+        let graph = asset_server.get_dependency_graph(&handle).unwrap();
+        let root = graph.root();
+        assert_eq!(root.id, handle.id().untyped());
+        assert_eq!(root.byte_size, Some(a_ron.len() as u64));
+        assert_eq!(graph.dependencies(root).count(), 2);
+        assert_eq!(graph.recursive_dependencies(root).len(), 2);
+        assert_eq!(
+            graph.recursive_byte_size(root),
+            (a_ron.len() + b_ron.len() + c_ron.len()) as u64
+        );
+
+        let c_id = asset_server.get_path_id(c_path).unwrap();
+        let c = graph.get(c_id).unwrap();
+        assert!(c.load_state.is_loaded());
+        assert_eq!(graph.dependants(c).count(), 2);
+
+        let graph = asset_server.get_path_dependency_graph(c_path).unwrap();
+        let root = graph.root();
+        assert_eq!(root.id, c_id);
+        assert!(graph.recursive_dependencies(root).is_empty());
+        let mut dependants: Vec<_> = graph
+            .recursive_dependants(root)
+            .into_iter()
+            .map(|node| node.path.as_ref().unwrap().to_string())
+            .collect();
+        dependants.sort();
+        assert_eq!(dependants, [a_path, b_path]);
+        assert!(graph.to_dot().contains("\\nLoaded\"];"));
+        assert!(ron::to_string(&graph)
+            .unwrap()
+            .contains("id:Index(index:(generation:0,"));
+    }
+
+    #[test]
+    fn asset_budget_eviction() {
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "aaaa",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "bbbb",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader)
+        .set_asset_budget::<CoolText>(AssetBudget::new(8));
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load(a_path);
+        let b: Handle<CoolText> = asset_server.load(b_path);
+        let a_id = a.id();
+        run_app_until(&mut app, |world| {
+            let assets = world.resource::<Assets<CoolText>>();
+            (assets.memory_usage() == Some(8)).then_some(())
+        });
+
+        // Loaded assets are cached on the next update
+        app.update();
+        assert!(app.world().resource::<Assets<CoolText>>().is_cached(a_id));
+
+        // Within budget, the cache keeps `a` alive after its last handle is dropped
+        drop(a);
+        for _ in 0..3 {
+            app.update();
+        }
+        let assets = app.world().resource::<Assets<CoolText>>();
+        assert!(assets.is_cached(a_id));
+        assert_eq!(assets.asset_memory_usage(a_id), Some(4));
+
+        // Over budget, the unused `a` is evicted, but `b` is kept since it is still in use
+        app.world_mut()
+            .resource_mut::<Assets<CoolText>>()
+            .set_budget(AssetBudget::new(6));
+        for _ in 0..3 {
+            app.update();
+        }
+        let assets = app.world().resource::<Assets<CoolText>>();
+        assert!(!assets.contains(a_id));
+        assert!(assets.contains(&b));
+        assert_eq!(assets.memory_usage(), Some(4));
+
+        // Evicted assets are reloaded through the asset server
+        let a: Handle<CoolText> = asset_server.load(a_path);
+        run_app_until(&mut app, |world| {
+            let assets = world.resource::<Assets<CoolText>>();
+            assets.get(&a).map(|a| assert_eq!(a.text, "aaaa"))
+        });
+    }
+
     #[test]
     fn ignore_system_ambiguities_on_assets() {
         let mut app = App::new();
@@ -0,0 +1,212 @@
+use super::ErasedProcessor;
+use crate::meta::AssetHash;
+use core::sync::atomic::{AtomicU64, Ordering};
+use futures_io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// A content-addressed store of processed assets, shared across [`AssetProcessor`](super::AssetProcessor) runs.
+///
+/// Entries are keyed by the asset's [`ProcessedInfo::hash`](crate::meta::ProcessedInfo::hash) (a hash of the
+/// source asset bytes and its meta), the type and [`VERSION`](super::Process::VERSION) of its
+/// [`Process`](super::Process) implementation, and the version of `bevy_asset`. The asset's path is not part of
+/// the key, so identical assets at different paths share an entry. When an asset's key is already in the cache,
+/// and the process dependencies recorded in the cached meta are unchanged, the cached output is copied to the
+/// processed [`AssetSource`](crate::io::AssetSource) instead of running the [`Process`](super::Process)
+/// implementation.
+///
+/// Because entries are never invalidated, the cache directory can be shared between checkouts, branches and
+/// machines. Increase the [`VERSION`](super::Process::VERSION) of a processor whose output changed, so that its
+/// previous outputs aren't reused. Entries are written atomically, so multiple processors can use the same directory at once.
+#[derive(Clone, Debug)]
+pub struct ProcessorCache {
+    root: PathBuf,
+}
+
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl ProcessorCache {
+    /// Creates a new [`ProcessorCache`] that stores entries in the directory at `path`. Relative paths are
+    /// resolved against the project root, like [`AssetPlugin::file_path`](crate::AssetPlugin::file_path).
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let base_path = crate::io::file::get_base_path();
+        #[cfg(target_arch = "wasm32")]
+        let base_path = PathBuf::new();
+        Self {
+            root: base_path.join(path),
+        }
+    }
+
+    /// The directory this cache stores entries in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the cache key for an asset whose source bytes and meta hash to `hash`, when processed by `processor`.
+    pub fn key(hash: AssetHash, processor: &dyn ErasedProcessor) -> AssetHash {
+        let mut hasher = blake3::Hasher::new();
+        // Each part is prefixed with its length, so that different parts can't produce the same bytes.
+        for part in [env!("CARGO_PKG_VERSION"), processor.type_name()] {
+            hasher.update(&(part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        hasher.update(&processor.version().to_le_bytes());
+        hasher.update(&hash);
+        *hasher.finalize().as_bytes()
+    }
+
+    fn entry_path(&self, key: &AssetHash) -> PathBuf {
+        let hex: String = key.iter().map(|byte| format!("{byte:02x}")).collect();
+        self.root.join(&hex[..2]).join(hex)
+    }
+
+    /// Reads the entry for `key`, returning its processed asset bytes and processed meta bytes, or `None`
+    /// if the entry does not exist.
+    pub async fn get(&self, key: &AssetHash) -> std::io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+        let mut bytes = match async_fs::read(self.entry_path(key)).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let invalid = || std::io::Error::new(ErrorKind::InvalidData, "invalid cache entry");
+        let meta_len = bytes
+            .get(..8)
+            .and_then(|len| Some(u64::from_le_bytes(len.try_into().ok()?)))
+            .and_then(|len| usize::try_from(len).ok())
+            .ok_or_else(invalid)?;
+        let meta_end = 8usize.checked_add(meta_len).ok_or_else(invalid)?;
+        if meta_end > bytes.len() {
+            return Err(invalid());
+        }
+        let asset = bytes.split_off(meta_end);
+        let meta = bytes.split_off(8);
+        Ok(Some((asset, meta)))
+    }
+
+    /// Writes the processed `asset` and `meta` bytes to the entry for `key`, replacing any existing entry.
+    pub async fn insert(&self, key: &AssetHash, asset: &[u8], meta: &[u8]) -> std::io::Result<()> {
+        let path = self.entry_path(key);
+        if let Some(parent) = path.parent() {
+            async_fs::create_dir_all(parent).await?;
+        }
+        let mut bytes = Vec::with_capacity(8 + meta.len() + asset.len());
+        bytes.extend_from_slice(&(meta.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(meta);
+        bytes.extend_from_slice(asset);
+        // Write to a unique temporary file first, so other processors sharing this cache never observe a
+        // partially written entry.
+        let temp_path = path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        async_fs::write(&temp_path, &bytes).await?;
+        if let Err(err) = async_fs::rename(&temp_path, &path).await {
+            let _ = async_fs::remove_file(&temp_path).await;
+            return Err(err);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProcessorCache;
+    use bevy_tasks::block_on;
+    use std::path::PathBuf;
+
+    /// Returns an empty directory for a test cache.
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "bevy_asset_processor_cache_{name}_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn get_returns_inserted_entry() {
+        let dir = cache_dir("round_trip");
+        let cache = ProcessorCache::new(&dir);
+        let key = [1; 32];
+        assert!(block_on(cache.get(&key)).unwrap().is_none());
+
+        block_on(cache.insert(&key, b"asset", b"meta")).unwrap();
+        let (asset, meta) = block_on(cache.get(&key)).unwrap().unwrap();
+        assert_eq!(asset, b"asset");
+        assert_eq!(meta, b"meta");
+        assert!(block_on(cache.get(&[2; 32])).unwrap().is_none());
+
+        block_on(cache.insert(&key, b"", b"new meta")).unwrap();
+        let (asset, meta) = block_on(cache.get(&key)).unwrap().unwrap();
+        assert!(asset.is_empty());
+        assert_eq!(meta, b"new meta");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn corrupt_entries_are_errors() {
+        let dir = cache_dir("corrupt");
+        let cache = ProcessorCache::new(&dir);
+        let key = [3; 32];
+        block_on(cache.insert(&key, b"asset", b"meta")).unwrap();
+        let path = cache.entry_path(&key);
+
+        // Truncated before the end of the meta.
+        std::fs::write(&path, [4, 0, 0, 0, 0, 0, 0, 0, b'm']).unwrap();
+        assert!(block_on(cache.get(&key)).is_err());
+        // Truncated inside the meta length.
+        std::fs::write(&path, [4, 0]).unwrap();
+        assert!(block_on(cache.get(&key)).is_err());
+        // A meta length that overflows.
+        std::fs::write(&path, u64::MAX.to_le_bytes()).unwrap();
+        assert!(block_on(cache.get(&key)).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+    #[test]
+    fn identical_assets_are_cached() {
+        use crate::{
+            io::memory::Dir,
+            processor::{
+                tests::{create_app, lock_processor},
+                AssetProcessor, ProcessOutcome,
+            },
+        };
+        use std::path::Path;
+
+        let _lock = lock_processor();
+        let dir = cache_dir("processor");
+        let run = |path: &str| {
+            let source = Dir::default();
+            source.insert_asset_text(Path::new(path), "text");
+            let processed = Dir::default();
+            let app = create_app(source, processed.clone());
+            let processor = app.world().resource::<AssetProcessor>().clone();
+            processor.set_cache(Some(ProcessorCache::new(&dir)));
+            processor.process_assets();
+            let report = block_on(processor.report());
+            assert_eq!(report.assets.len(), 1);
+            assert_eq!(
+                processed.get_asset(Path::new(path)).unwrap().value(),
+                b"TEXT"
+            );
+            report.assets[0].clone()
+        };
+
+        let first = run("a.txt");
+        assert_eq!(first.outcome, ProcessOutcome::Processed);
+        // The same content at another path, processed from scratch, reuses the cached output.
+        let second = run("b.txt");
+        assert_eq!(second.outcome, ProcessOutcome::Cached);
+        let (first, second) = (
+            first.processed_info.unwrap(),
+            second.processed_info.unwrap(),
+        );
+        assert_eq!(first.hash, second.hash);
+        assert_eq!(first.full_hash, second.full_hash);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -37,17 +37,21 @@
 //!
 //! In most cases, [`LoadTransformAndSave`] should be sufficient.
 
+mod cache;
 mod log;
 mod process;
+mod report;
 
+pub use cache::*;
 pub use log::*;
 pub use process::*;
+pub use report::*;
 
 use crate::{
     io::{
         AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent, AssetSourceId,
         AssetSources, AssetWriterError, ErasedAssetReader, ErasedAssetWriter,
-        MissingAssetSourceError,
+        MissingAssetSourceError, Writer,
     },
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
@@ -102,6 +106,8 @@ pub struct AssetProcessorData {
     processors: RwLock<HashMap<&'static str, Arc<dyn ErasedProcessor>>>,
     /// Default processors for file extensions
     default_processors: RwLock<HashMap<Box<str>, &'static str>>,
+    /// The content-addressed cache of processed assets, if enabled
+    cache: RwLock<Option<ProcessorCache>>,
     state: async_lock::RwLock<ProcessorState>,
     sources: AssetSources,
     initialized_sender: async_broadcast::Sender<()>,
@@ -167,6 +173,52 @@ impl AssetProcessor {
         &self.data.sources
     }
 
+    /// Waits until the processor has finished processing, then packs the processed assets of `source` into a
+    /// pack file at `path`, which can be read with a [`PackAssetReader`](crate::io::pack::PackAssetReader).
+    ///
+    /// This is meant to be used when preparing a shipping build, to avoid shipping thousands of loose processed files.
+    #[cfg(all(feature = "asset_pack", not(target_arch = "wasm32")))]
+    pub async fn write_pack<'a>(
+        &self,
+        source: impl Into<AssetSourceId<'a>>,
+        path: &Path,
+        settings: crate::io::pack::PackSettings,
+    ) -> Result<(), crate::io::pack::WritePackError> {
+        self.data.wait_until_finished().await;
+        let reader = self.get_source(source)?.processed_reader()?;
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        crate::io::pack::write_pack(reader, file, settings).await?;
+        Ok(())
+    }
+
+    /// Sets the [`ProcessorCache`] used to reuse processed output for identical inputs. Pass `None` to disable caching.
+    pub fn set_cache(&self, cache: Option<ProcessorCache>) {
+        *self.data.cache.write() = cache;
+    }
+
+    /// Returns the [`ProcessorCache`] used to reuse processed output for identical inputs, if one is set.
+    pub fn cache(&self) -> Option<ProcessorCache> {
+        self.data.cache.read().clone()
+    }
+
+    /// Returns a [`ProcessReport`] of the outcome of the last attempt to process each asset.
+    pub async fn report(&self) -> ProcessReport {
+        let infos = self.data.asset_infos.read().await;
+        let mut assets: Vec<_> = infos
+            .infos
+            .iter()
+            .filter_map(|(path, info)| {
+                Some(ProcessReportEntry {
+                    path: path.to_string(),
+                    outcome: info.outcome.clone()?,
+                    processed_info: info.processed_info.clone(),
+                })
+            })
+            .collect();
+        assets.sort_by(|a, b| a.path.cmp(&b.path));
+        ProcessReport { assets }
+    }
+
     /// Logs an unrecoverable error. On the next run of the processor, all assets will be regenerated. This should only be used as a last resort.
     /// Every call to this should be considered with scrutiny and ideally replaced with something more granular.
     async fn log_unrecoverable(&self) {
@@ -872,15 +924,41 @@ impl AssetProcessor {
         // TODO: this class of failure can be recovered via re-processing + smarter log validation that allows for duplicate transactions in the event of failures
         self.log_begin_processing(asset_path).await;
         if let Some(processor) = processor {
+            let cache = self
+                .cache()
+                .map(|cache| (ProcessorCache::key(new_hash, &*processor), cache));
+            if let Some((key, cache)) = &cache {
+                if let Some(processed_info) = self
+                    .write_cached(cache, key, asset_path, processed_writer)
+                    .await?
+                {
+                    self.log_end_processing(asset_path).await;
+                    return Ok(ProcessResult::Cached(processed_info));
+                }
+            }
+
             let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
+            // When caching, the processed bytes are buffered so they can also be written to the cache
+            let mut processed_bytes = Vec::new();
+            let output: &mut Writer = if cache.is_some() {
+                &mut processed_bytes
+            } else {
+                &mut *writer
+            };
             let mut processed_meta = {
                 let mut context =
                     ProcessContext::new(self, asset_path, &asset_bytes, &mut new_processed_info);
-                processor
-                    .process(&mut context, source_meta, &mut *writer)
-                    .await?
+                processor.process(&mut context, source_meta, output).await?
             };
 
+            if cache.is_some() {
+                writer.write_all(&processed_bytes).await.map_err(|e| {
+                    ProcessError::AssetWriterError {
+                        path: asset_path.clone(),
+                        err: AssetWriterError::Io(e),
+                    }
+                })?;
+            }
             writer
                 .flush()
                 .await
@@ -903,6 +981,11 @@ impl AssetProcessor {
                 .write_meta_bytes(path, &meta_bytes)
                 .await
                 .map_err(writer_err)?;
+            if let Some((key, cache)) = &cache {
+                if let Err(err) = cache.insert(key, &processed_bytes, &meta_bytes).await {
+                    warn!("Failed to write {asset_path} to the processor cache: {err}");
+                }
+            }
         } else {
             processed_writer
                 .write_bytes(path, &asset_bytes)
@@ -920,6 +1003,61 @@ impl AssetProcessor {
         Ok(ProcessResult::Processed(new_processed_info))
     }
 
+    /// Writes the cached output for `key` to the processed [`AssetSource`], if it exists and every process dependency
+    /// it was produced from is unchanged. Returns the cached [`ProcessedInfo`] if the cached output was used.
+    async fn write_cached(
+        &self,
+        cache: &ProcessorCache,
+        key: &AssetHash,
+        asset_path: &AssetPath<'static>,
+        processed_writer: &dyn ErasedAssetWriter,
+    ) -> Result<Option<ProcessedInfo>, ProcessError> {
+        let (asset_bytes, meta_bytes) = match cache.get(key).await {
+            Ok(Some(entry)) => entry,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                warn!("Failed to read {asset_path} from the processor cache: {err}");
+                return Ok(None);
+            }
+        };
+        let Some(processed_info) = ron::de::from_bytes::<ProcessedInfoMinimal>(&meta_bytes)
+            .ok()
+            .and_then(|minimal| minimal.processed_info)
+        else {
+            warn!("The processor cache entry for {asset_path} has invalid meta");
+            return Ok(None);
+        };
+        for dependency in &processed_info.process_dependencies {
+            self.data
+                .wait_until_processed(dependency.path.clone())
+                .await;
+            let infos = self.data.asset_infos.read().await;
+            let live_hash = infos
+                .get(&dependency.path)
+                .and_then(|i| i.processed_info.as_ref())
+                .map(|i| i.full_hash);
+            if live_hash != Some(dependency.full_hash) {
+                return Ok(None);
+            }
+        }
+
+        let writer_err = |err| ProcessError::AssetWriterError {
+            path: asset_path.clone(),
+            err,
+        };
+        let path = asset_path.path();
+        processed_writer
+            .write_bytes(path, &asset_bytes)
+            .await
+            .map_err(writer_err)?;
+        processed_writer
+            .write_meta_bytes(path, &meta_bytes)
+            .await
+            .map_err(writer_err)?;
+        debug!("Reused cached output for {asset_path}");
+        Ok(Some(processed_info))
+    }
+
     async fn validate_transaction_log_and_recover(&self) {
         if let Err(err) = ProcessorTransactionLog::validate().await {
             let state_is_valid = match err {
@@ -1027,6 +1165,7 @@ impl AssetProcessorData {
             processors: Default::default(),
             asset_infos: Default::default(),
             default_processors: Default::default(),
+            cache: Default::default(),
         }
     }
 
@@ -1120,6 +1259,7 @@ impl<T: Process> Process for InstrumentedAssetProcessor<T> {
 #[derive(Debug, Clone)]
 pub enum ProcessResult {
     Processed(ProcessedInfo),
+    Cached(ProcessedInfo),
     SkippedNotChanged,
     Ignored,
 }
@@ -1139,6 +1279,8 @@ pub(crate) struct ProcessorAssetInfo {
     /// Paths of assets that depend on this asset when they are being processed.
     dependents: HashSet<AssetPath<'static>>,
     status: Option<ProcessStatus>,
+    /// The outcome of the last attempt to process the asset.
+    outcome: Option<ProcessOutcome>,
     /// A lock that controls read/write access to processed asset files. The lock is shared for both the asset bytes and the meta bytes.
     /// _This lock must be locked whenever a read or write to processed assets occurs_
     /// There are scenarios where processed assets (and their metadata) are being read and written in multiple places at once:
@@ -1164,6 +1306,7 @@ impl Default for ProcessorAssetInfo {
             dependents: Default::default(),
             file_transaction_lock: Default::default(),
             status: None,
+            outcome: None,
             status_sender,
             status_receiver,
         }
@@ -1235,8 +1378,11 @@ impl ProcessorAssetInfos {
         asset_path: AssetPath<'static>,
         result: Result<ProcessResult, ProcessError>,
     ) {
+        let cached = matches!(result, Ok(ProcessResult::Cached(_)));
         match result {
-            Ok(ProcessResult::Processed(processed_info)) => {
+            Ok(
+                ProcessResult::Processed(processed_info) | ProcessResult::Cached(processed_info),
+            ) => {
                 debug!("Finished processing \"{:?}\"", asset_path);
                 // clean up old dependents
                 let old_processed_info = self
@@ -1253,6 +1399,11 @@ impl ProcessorAssetInfos {
                 }
                 let info = self.get_or_insert(asset_path);
                 info.processed_info = Some(processed_info);
+                info.outcome = Some(if cached {
+                    ProcessOutcome::Cached
+                } else {
+                    ProcessOutcome::Processed
+                });
                 info.update_status(ProcessStatus::Processed).await;
                 let dependents = info.dependents.iter().cloned().collect::<Vec<_>>();
                 for path in dependents {
@@ -1268,16 +1419,26 @@ impl ProcessorAssetInfos {
                 // Therefore this relies on hot-reloading in the app to pickup the "latest" version of the asset
                 // If "block until latest state is reflected" is required, we can easily add a less granular
                 // "block until first pass finished" mode
+                info.outcome = Some(ProcessOutcome::SkippedUnchanged);
                 info.update_status(ProcessStatus::Processed).await;
             }
             Ok(ProcessResult::Ignored) => {
                 debug!("Skipping processing (ignored) \"{:?}\"", asset_path);
+                if let Some(info) = self.get_mut(&asset_path) {
+                    info.outcome = Some(ProcessOutcome::Ignored);
+                }
             }
             Err(ProcessError::ExtensionRequired) => {
                 // Skip assets without extensions
+                if let Some(info) = self.get_mut(&asset_path) {
+                    info.outcome = Some(ProcessOutcome::MissingLoader);
+                }
             }
             Err(ProcessError::MissingAssetLoaderForExtension(_)) => {
                 trace!("No loader found for {asset_path}");
+                if let Some(info) = self.get_mut(&asset_path) {
+                    info.outcome = Some(ProcessOutcome::MissingLoader);
+                }
             }
             Err(ProcessError::AssetReaderError {
                 err: AssetReaderError::NotFound(_),
@@ -1285,9 +1446,13 @@ impl ProcessorAssetInfos {
             }) => {
                 // if there is no asset source, no processing can be done
                 trace!("No need to process asset {asset_path} because it does not exist");
+                if let Some(info) = self.get_mut(&asset_path) {
+                    info.outcome = Some(ProcessOutcome::NotFound);
+                }
             }
             Err(err) => {
                 error!("Failed to process asset {asset_path}: {err}");
+                let message = err.to_string();
                 // if this failed because a dependency could not be loaded, make sure it is reprocessed if that dependency is reprocessed
                 if let ProcessError::AssetLoadError(AssetLoadError::AssetLoaderError(dependency)) =
                     err
@@ -1302,6 +1467,7 @@ impl ProcessorAssetInfos {
                 }
 
                 let info = self.get_mut(&asset_path).expect("info should exist");
+                info.outcome = Some(ProcessOutcome::Failed(message));
                 info.update_status(ProcessStatus::Failed).await;
             }
         }
@@ -1420,3 +1586,245 @@ pub enum InitializeError {
     #[display("Failed to validate asset log: {_0}")]
     ValidateLogError(ValidateLogError),
 }
+
+#[cfg(all(test, not(target_arch = "wasm32"), feature = "multi_threaded"))]
+pub(crate) mod tests {
+    use super::{Process, ProcessContext, ProcessError};
+    use crate::{
+        self as bevy_asset,
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId, AssetWriter, AssetWriterError, Reader, Writer,
+        },
+        meta::AssetMeta,
+        Asset, AssetApp, AssetLoader, AssetMode, AssetPlugin, LoadContext,
+    };
+    use bevy_app::App;
+    use bevy_core::TaskPoolPlugin;
+    use bevy_reflect::TypePath;
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use futures_io::AsyncWrite;
+    use futures_lite::AsyncWriteExt;
+    use std::{
+        path::{Path, PathBuf},
+        sync::{Mutex, MutexGuard},
+    };
+
+    /// The processor writes its transaction log to the same file in every test, so they can't run in parallel.
+    static PROCESSOR_LOCK: Mutex<()> = Mutex::new(());
+
+    pub(crate) fn lock_processor() -> MutexGuard<'static, ()> {
+        PROCESSOR_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// An [`AssetWriter`] that writes to a memory [`Dir`] once the written asset is flushed.
+    struct MemoryAssetWriter {
+        root: Dir,
+    }
+
+    struct MemoryWriter {
+        root: Dir,
+        path: PathBuf,
+        is_meta: bool,
+        bytes: Vec<u8>,
+    }
+
+    impl AsyncWrite for MemoryWriter {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.get_mut().bytes.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            let this = self.get_mut();
+            if this.is_meta {
+                this.root.insert_meta(&this.path, this.bytes.clone());
+            } else {
+                this.root.insert_asset(&this.path, this.bytes.clone());
+            }
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            self.poll_flush(cx)
+        }
+    }
+
+    impl MemoryAssetWriter {
+        fn writer(&self, path: &Path, is_meta: bool) -> Box<Writer> {
+            Box::new(MemoryWriter {
+                root: self.root.clone(),
+                path: path.to_owned(),
+                is_meta,
+                bytes: Vec::new(),
+            })
+        }
+    }
+
+    impl AssetWriter for MemoryAssetWriter {
+        async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+            Ok(self.writer(path, false))
+        }
+
+        async fn write_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+            Ok(self.writer(path, true))
+        }
+
+        async fn remove<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+            self.root.remove_asset(path);
+            Ok(())
+        }
+
+        async fn remove_meta<'a>(&'a self, _path: &'a Path) -> Result<(), AssetWriterError> {
+            Ok(())
+        }
+
+        async fn rename<'a>(
+            &'a self,
+            old_path: &'a Path,
+            new_path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            if let Some(data) = self.root.remove_asset(old_path) {
+                self.root.insert_asset(new_path, data.value().to_vec());
+            }
+            Ok(())
+        }
+
+        async fn rename_meta<'a>(
+            &'a self,
+            _old_path: &'a Path,
+            _new_path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            Ok(())
+        }
+
+        async fn create_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+            self.root.get_or_insert_dir(path);
+            Ok(())
+        }
+
+        async fn remove_directory<'a>(&'a self, _path: &'a Path) -> Result<(), AssetWriterError> {
+            Ok(())
+        }
+
+        async fn remove_empty_directory<'a>(
+            &'a self,
+            _path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            Ok(())
+        }
+
+        async fn remove_assets_in_directory<'a>(
+            &'a self,
+            _path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Asset, TypePath)]
+    pub(crate) struct Text(#[allow(dead_code)] pub(crate) String);
+
+    pub(crate) struct TextLoader;
+
+    impl AssetLoader for TextLoader {
+        type Asset = Text;
+        type Settings = ();
+        type Error = std::io::Error;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            _settings: &(),
+            _load_context: &mut LoadContext<'_>,
+        ) -> Result<Text, Self::Error> {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            String::from_utf8(bytes)
+                .map(Text)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    /// Upper-cases text assets, and fails on assets that contain `fail`.
+    pub(crate) struct UppercaseProcessor;
+
+    impl Process for UppercaseProcessor {
+        type Settings = ();
+        type OutputLoader = TextLoader;
+
+        async fn process(
+            &self,
+            context: &mut ProcessContext<'_>,
+            _meta: AssetMeta<(), Self>,
+            writer: &mut Writer,
+        ) -> Result<(), ProcessError> {
+            let text = core::str::from_utf8(context.asset_bytes())
+                .map_err(|err| ProcessError::AssetTransformError(err.into()))?;
+            if text.contains("fail") {
+                return Err(ProcessError::AssetTransformError("asked to fail".into()));
+            }
+            writer
+                .write_all(text.to_uppercase().as_bytes())
+                .await
+                .map_err(|err| ProcessError::AssetTransformError(err.into()))?;
+            Ok(())
+        }
+    }
+
+    /// Creates an app that processes the `.txt` assets of `source` into `processed` with [`UppercaseProcessor`].
+    pub(crate) fn create_app(source: Dir, processed: Dir) -> App {
+        let mut app = App::new();
+        let source_writer = source.clone();
+        let processed_writer = processed.clone();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: source.clone(),
+                    })
+                })
+                .with_writer(move |_| {
+                    Some(Box::new(MemoryAssetWriter {
+                        root: source_writer.clone(),
+                    }))
+                })
+                .with_processed_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: processed.clone(),
+                    })
+                })
+                .with_processed_writer(move |_| {
+                    Some(Box::new(MemoryAssetWriter {
+                        root: processed_writer.clone(),
+                    }))
+                }),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                mode: AssetMode::Processed,
+                ..Default::default()
+            },
+        ))
+        .init_asset::<Text>()
+        .register_asset_loader(TextLoader)
+        .register_asset_processor(UppercaseProcessor)
+        .set_default_asset_processor::<UppercaseProcessor>("txt");
+        app
+    }
+}
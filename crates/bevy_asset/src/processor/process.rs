@@ -25,6 +25,10 @@ pub trait Process: Send + Sync + Sized + 'static {
     type Settings: Settings + Default + Serialize + for<'a> Deserialize<'a>;
     /// The [`AssetLoader`] that will be used to load the final processed asset.
     type OutputLoader: AssetLoader;
+    /// The version of this processor, which is part of the keys of the [`ProcessorCache`](super::ProcessorCache).
+    /// Increase it when a change to the processor changes its output, so that outputs cached by previous versions
+    /// aren't reused.
+    const VERSION: u32 = 0;
     /// Processes the asset stored on `context` in some way using the settings stored on `meta`. The results are written to `writer`. The
     /// final written processed asset is loadable using [`Process::OutputLoader`]. This load will use the returned [`AssetLoader::Settings`].
     fn process(
@@ -230,6 +234,10 @@ pub trait ErasedProcessor: Send + Sync {
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError>;
     /// Returns the default type-erased [`AssetMeta`] for the underlying [`Process`] impl.
     fn default_meta(&self) -> Box<dyn AssetMetaDyn>;
+    /// Returns the type name of the underlying [`Process`] impl.
+    fn type_name(&self) -> &'static str;
+    /// Returns the [`Process::VERSION`] of the underlying [`Process`] impl.
+    fn version(&self) -> u32;
 }
 
 impl<P: Process> ErasedProcessor for P {
@@ -264,6 +272,14 @@ impl<P: Process> ErasedProcessor for P {
             settings: P::Settings::default(),
         }))
     }
+
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<P>()
+    }
+
+    fn version(&self) -> u32 {
+        P::VERSION
+    }
 }
 
 /// Provides scoped data access to the [`AssetProcessor`].
@@ -0,0 +1,229 @@
+use crate::meta::ProcessedInfo;
+use serde::{Deserialize, Serialize};
+
+/// The outcome of the last attempt to process an asset.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum ProcessOutcome {
+    /// The asset was processed and written to the processed [`AssetSource`](crate::io::AssetSource).
+    Processed,
+    /// The asset's output was found in the [`ProcessorCache`](super::ProcessorCache) and copied to the processed
+    /// [`AssetSource`](crate::io::AssetSource), so processing was skipped.
+    Cached,
+    /// The asset and its process dependencies were unchanged since the last run, so processing was skipped.
+    SkippedUnchanged,
+    /// The asset's meta file set the [`AssetAction`](crate::meta::AssetAction) to `Ignore`.
+    Ignored,
+    /// No [`AssetLoader`](crate::AssetLoader) exists for the asset's extension, so it was not processed.
+    MissingLoader,
+    /// The asset was not found in its [`AssetSource`](crate::io::AssetSource), for example because it was removed
+    /// while it was being processed.
+    NotFound,
+    /// Processing failed with the given [`ProcessError`](super::ProcessError) message.
+    Failed(String),
+}
+
+/// A single asset in a [`ProcessReport`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProcessReportEntry {
+    /// The path of the asset, including its source.
+    pub path: String,
+    /// The outcome of the last attempt to process the asset.
+    pub outcome: ProcessOutcome,
+    /// The [`ProcessedInfo`] of the asset, which contains its hashes. This is `None` if the asset has never
+    /// been processed.
+    pub processed_info: Option<ProcessedInfo>,
+}
+
+/// A machine-readable report of every asset seen by the [`AssetProcessor`](super::AssetProcessor), returned by
+/// [`AssetProcessor::report`](super::AssetProcessor::report).
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProcessReport {
+    /// The assets in the report, sorted by path.
+    pub assets: Vec<ProcessReportEntry>,
+}
+
+impl ProcessReport {
+    /// Returns an iterator over the assets with the given `outcome`.
+    pub fn with_outcome<'a>(
+        &'a self,
+        outcome: &'a ProcessOutcome,
+    ) -> impl Iterator<Item = &'a ProcessReportEntry> {
+        self.assets
+            .iter()
+            .filter(move |entry| &entry.outcome == outcome)
+    }
+
+    /// Returns an iterator over the assets that failed to process.
+    pub fn failed(&self) -> impl Iterator<Item = &ProcessReportEntry> {
+        self.assets
+            .iter()
+            .filter(|entry| matches!(entry.outcome, ProcessOutcome::Failed(_)))
+    }
+
+    /// Returns `true` if any asset failed to process.
+    pub fn has_failures(&self) -> bool {
+        self.failed().next().is_some()
+    }
+
+    /// Serializes the report to a pretty-printed RON string.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+pub use batch::*;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+mod batch {
+    use super::super::AssetProcessor;
+    use bevy_app::{App, AppExit, Plugin, PluginsState};
+    use bevy_utils::tracing::{error, info};
+    use std::{io::Write, path::PathBuf};
+
+    /// Runs the [`AssetProcessor`] once over every processed [`AssetSource`](crate::io::AssetSource) and exits,
+    /// instead of running the app's schedules.
+    ///
+    /// This is meant for CI and build scripts. A [`ProcessReport`](super::ProcessReport) is written as RON to
+    /// [`report_path`](Self::report_path), or to stdout if it is `None`, and the app exits with an error status
+    /// if any asset failed to process. Nothing else is written to stdout by this plugin; the `LogPlugin` logs
+    /// to stderr.
+    ///
+    /// This requires [`AssetMode::Processed`](crate::AssetMode::Processed) and the `asset_processor` feature.
+    ///
+    /// ```no_run
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_asset::{prelude::*, processor::BatchProcessPlugin};
+    /// App::new()
+    ///     .add_plugins((
+    ///         AssetPlugin {
+    ///             mode: AssetMode::Processed,
+    ///             ..Default::default()
+    ///         },
+    ///         BatchProcessPlugin {
+    ///             report_path: Some("process_report.ron".into()),
+    ///         },
+    ///     ))
+    ///     .run();
+    /// ```
+    #[derive(Default)]
+    pub struct BatchProcessPlugin {
+        /// If set, the report is written to this file instead of stdout.
+        pub report_path: Option<PathBuf>,
+    }
+
+    impl Plugin for BatchProcessPlugin {
+        fn build(&self, app: &mut App) {
+            let report_path = self.report_path.clone();
+            app.set_runner(move |app| run_batch(app, report_path));
+        }
+    }
+
+    fn run_batch(mut app: App, report_path: Option<PathBuf>) -> AppExit {
+        while app.plugins_state() == PluginsState::Adding {
+            bevy_tasks::tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+
+        let Some(processor) = app.world().get_resource::<AssetProcessor>().cloned() else {
+            error!("BatchProcessPlugin requires AssetMode::Processed and the `asset_processor` feature");
+            return AppExit::error();
+        };
+        processor.process_assets();
+        let report = bevy_tasks::block_on(processor.report());
+
+        let ron = match report.to_ron() {
+            Ok(ron) => ron,
+            Err(err) => {
+                error!("Failed to serialize the process report: {err}");
+                return AppExit::error();
+            }
+        };
+        let written = match &report_path {
+            Some(path) => std::fs::write(path, &ron),
+            None => {
+                let mut stdout = std::io::stdout().lock();
+                writeln!(stdout, "{ron}").and_then(|()| stdout.flush())
+            }
+        };
+        if let Err(err) = written {
+            match report_path {
+                Some(path) => error!("Failed to write the process report to {path:?}: {err}"),
+                None => error!("Failed to write the process report to stdout: {err}"),
+            }
+            return AppExit::error();
+        }
+
+        let failed = report.failed().count();
+        if failed > 0 {
+            error!("{failed} asset(s) failed to process");
+            return AppExit::error();
+        }
+        info!("Processed {} asset(s)", report.assets.len());
+        AppExit::Success
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::run_batch;
+        use crate::{
+            io::memory::Dir,
+            meta::{get_asset_hash, get_full_asset_hash},
+            processor::{
+                tests::{create_app, lock_processor},
+                AssetProcessor, ProcessOutcome,
+            },
+        };
+        use bevy_app::AppExit;
+        use std::path::Path;
+
+        #[test]
+        fn batch_reports_processed_and_failed_assets() {
+            let _lock = lock_processor();
+            let source = Dir::default();
+            source.insert_asset_text(Path::new("good.txt"), "good");
+            source.insert_asset_text(Path::new("bad.txt"), "fail");
+            let processed = Dir::default();
+            let app = create_app(source.clone(), processed.clone());
+            let processor = app.world().resource::<AssetProcessor>().clone();
+
+            let report_path = std::env::temp_dir().join("bevy_asset_batch_report_test.ron");
+            assert_eq!(run_batch(app, Some(report_path.clone())), AppExit::error());
+            assert!(std::fs::read_to_string(&report_path)
+                .unwrap()
+                .contains("good.txt"));
+            let _ = std::fs::remove_file(report_path);
+
+            let report = bevy_tasks::block_on(processor.report());
+            assert_eq!(report.assets.len(), 2);
+            let entry = |path: &str| {
+                report
+                    .assets
+                    .iter()
+                    .find(|entry| entry.path == path)
+                    .unwrap()
+            };
+
+            let good = entry("good.txt");
+            assert_eq!(good.outcome, ProcessOutcome::Processed);
+            let meta = source.get_metadata(Path::new("good.txt")).unwrap();
+            let hash = get_asset_hash(meta.value(), b"good");
+            let info = good.processed_info.as_ref().unwrap();
+            assert_eq!(info.hash, hash);
+            assert_eq!(
+                info.full_hash,
+                get_full_asset_hash(hash, core::iter::empty())
+            );
+            assert_eq!(
+                processed.get_asset(Path::new("good.txt")).unwrap().value(),
+                b"GOOD"
+            );
+
+            let bad = entry("bad.txt");
+            assert!(matches!(bad.outcome, ProcessOutcome::Failed(_)));
+            assert!(bad.processed_info.is_none());
+            assert_eq!(report.failed().count(), 1);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{AssetPath, LoadState, UntypedAssetId};
+use alloc::string::String;
+use bevy_utils::HashSet;
+use core::fmt::Write;
+use serde::{ser::SerializeStructVariant, Serialize, Serializer};
+
+/// An asset in an [`AssetDependencyGraph`].
+#[derive(Serialize, Debug, Clone)]
+pub struct AssetGraphNode {
+    /// The id of the asset. It is serialized like an [`AssetId`](crate::AssetId), without its type.
+    #[serde(serialize_with = "serialize_asset_id")]
+    pub id: UntypedAssetId,
+    /// The path of the asset, if it has one.
+    pub path: Option<AssetPath<'static>>,
+    /// The [`LoadState`] of the asset.
+    #[serde(serialize_with = "serialize_load_state")]
+    pub load_state: LoadState,
+    /// The number of bytes read by the [`AssetLoader`](crate::AssetLoader) that loaded the asset.
+    /// This is `None` for assets that were not loaded from a path, and for labeled assets, whose bytes
+    /// are counted in the asset they were loaded from.
+    pub byte_size: Option<u64>,
+    /// The indices of the direct dependencies of the asset in [`AssetDependencyGraph::nodes`].
+    pub dependencies: Vec<usize>,
+    /// The indices of the assets in [`AssetDependencyGraph::nodes`] that directly depend on the asset.
+    pub dependants: Vec<usize>,
+}
+
+/// A snapshot of the dependency graph around an asset, returned by [`AssetServer::get_dependency_graph`].
+///
+/// The graph contains the root asset, everything it recursively depends on, and everything that recursively
+/// depends on it. Dependencies are the handles an asset holds when it finishes loading (see
+/// [`VisitAssetDependencies`](crate::VisitAssetDependencies)).
+///
+/// The graph can be exported to [DOT](https://graphviz.org/doc/info/lang.html) with [`to_dot`](Self::to_dot),
+/// or to JSON (or any other format) through its [`Serialize`] implementation.
+///
+/// [`AssetServer::get_dependency_graph`]: crate::AssetServer::get_dependency_graph
+#[derive(Serialize, Debug, Clone)]
+pub struct AssetDependencyGraph {
+    /// The assets in the graph. The root asset is always the first node.
+    pub nodes: Vec<AssetGraphNode>,
+}
+
+impl AssetDependencyGraph {
+    /// Returns the asset this graph was created for.
+    pub fn root(&self) -> &AssetGraphNode {
+        &self.nodes[0]
+    }
+
+    /// Returns the node of the asset with the given `id`, if it is in the graph.
+    pub fn get(&self, id: impl Into<UntypedAssetId>) -> Option<&AssetGraphNode> {
+        let id = id.into();
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Returns the direct dependencies of `node`.
+    pub fn dependencies<'a>(
+        &'a self,
+        node: &'a AssetGraphNode,
+    ) -> impl Iterator<Item = &'a AssetGraphNode> {
+        node.dependencies.iter().map(|&index| &self.nodes[index])
+    }
+
+    /// Returns the assets that directly depend on `node`.
+    pub fn dependants<'a>(
+        &'a self,
+        node: &'a AssetGraphNode,
+    ) -> impl Iterator<Item = &'a AssetGraphNode> {
+        node.dependants.iter().map(|&index| &self.nodes[index])
+    }
+
+    /// Returns every asset `node` depends on, directly or indirectly.
+    pub fn recursive_dependencies<'a>(
+        &'a self,
+        node: &'a AssetGraphNode,
+    ) -> Vec<&'a AssetGraphNode> {
+        self.walk(node, |node| &node.dependencies)
+    }
+
+    /// Returns every asset that depends on `node`, directly or indirectly.
+    pub fn recursive_dependants<'a>(&'a self, node: &'a AssetGraphNode) -> Vec<&'a AssetGraphNode> {
+        self.walk(node, |node| &node.dependants)
+    }
+
+    /// Returns the total [`byte_size`](AssetGraphNode::byte_size) of `node` and every asset it recursively depends on.
+    pub fn recursive_byte_size(&self, node: &AssetGraphNode) -> u64 {
+        self.recursive_dependencies(node)
+            .into_iter()
+            .chain([node])
+            .filter_map(|node| node.byte_size)
+            .sum()
+    }
+
+    fn walk<'a>(
+        &'a self,
+        node: &'a AssetGraphNode,
+        edges: fn(&AssetGraphNode) -> &[usize],
+    ) -> Vec<&'a AssetGraphNode> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![node];
+        let mut result = Vec::new();
+        while let Some(node) = stack.pop() {
+            for &index in edges(node) {
+                if visited.insert(index) {
+                    let next = &self.nodes[index];
+                    result.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+        result
+    }
+
+    /// Writes the graph in the [DOT](https://graphviz.org/doc/info/lang.html) format, which can be rendered with
+    /// tools like Graphviz. Edges point from an asset to its dependencies.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph assets {\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let path = match &node.path {
+                Some(path) => path.to_string(),
+                None => String::from("<no path>"),
+            };
+            let mut label = path.replace('\\', "\\\\").replace('"', "\\\"");
+            if let Some(byte_size) = node.byte_size {
+                let _ = write!(label, "\\n{byte_size} bytes");
+            }
+            let _ = write!(label, "\\n{}", load_state_name(&node.load_state));
+            let _ = write!(dot, "    {index} [label=\"{label}\"");
+            if index == 0 {
+                dot.push_str(", shape=box");
+            }
+            if node.load_state.is_failed() {
+                dot.push_str(", color=red");
+            }
+            dot.push_str("];\n");
+        }
+        for (index, node) in self.nodes.iter().enumerate() {
+            for dependency in &node.dependencies {
+                let _ = writeln!(dot, "    {index} -> {dependency};");
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn load_state_name(load_state: &LoadState) -> &'static str {
+    match load_state {
+        LoadState::NotLoaded => "NotLoaded",
+        LoadState::Loading => "Loading",
+        LoadState::Loaded => "Loaded",
+        LoadState::Failed(_) => "Failed",
+    }
+}
+
+fn serialize_asset_id<S: Serializer>(
+    id: &UntypedAssetId,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match id {
+        UntypedAssetId::Index { index, .. } => {
+            let mut variant = serializer.serialize_struct_variant("AssetId", 0, "Index", 1)?;
+            variant.serialize_field("index", index)?;
+            variant.end()
+        }
+        UntypedAssetId::Uuid { uuid, .. } => {
+            let mut variant = serializer.serialize_struct_variant("AssetId", 1, "Uuid", 1)?;
+            variant.serialize_field("uuid", uuid)?;
+            variant.end()
+        }
+    }
+}
+
+fn serialize_load_state<S: Serializer>(
+    load_state: &LoadState,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match load_state {
+        LoadState::Failed(error) => {
+            serializer.serialize_newtype_variant("LoadState", 3, "Failed", &error.to_string())
+        }
+        LoadState::NotLoaded => serializer.serialize_unit_variant("LoadState", 0, "NotLoaded"),
+        LoadState::Loading => serializer.serialize_unit_variant("LoadState", 1, "Loading"),
+        LoadState::Loaded => serializer.serialize_unit_variant("LoadState", 2, "Loaded"),
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
-    Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
-    Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
-    UntypedAssetId, UntypedHandle,
+    Asset, AssetDependencyGraph, AssetGraphNode, AssetHandleProvider, AssetLoadError, AssetPath,
+    DependencyLoadState, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
+    RecursiveDependencyLoadState, StrongHandle, UntypedAssetId, UntypedHandle,
 };
 use alloc::sync::{Arc, Weak};
 use bevy_ecs::world::World;
@@ -26,6 +26,10 @@ pub(crate) struct AssetInfo {
     failed_rec_dependencies: HashSet<UntypedAssetId>,
     dependents_waiting_on_load: HashSet<UntypedAssetId>,
     dependents_waiting_on_recursive_dep_load: HashSet<UntypedAssetId>,
+    /// The direct dependencies of this asset, as of its last load.
+    dependencies: HashSet<UntypedAssetId>,
+    /// The number of bytes read by the loader of this asset, as of its last load.
+    pub(crate) byte_size: Option<u64>,
     /// The asset paths required to load this asset. Hashes will only be set for processed assets.
     /// This is set using the value from [`LoadedAsset`].
     /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
@@ -55,6 +59,8 @@ impl AssetInfo {
             loader_dependencies: HashMap::default(),
             dependents_waiting_on_load: HashSet::default(),
             dependents_waiting_on_recursive_dep_load: HashSet::default(),
+            dependencies: HashSet::default(),
+            byte_size: None,
             handle_drops_to_skip: 0,
             waiting_tasks: Vec::new(),
         }
@@ -394,7 +400,7 @@ impl AssetInfos {
         }
 
         loaded_asset.value.insert(loaded_asset_id, world);
-        let mut loading_deps = loaded_asset.dependencies;
+        let mut loading_deps = loaded_asset.dependencies.clone();
         let mut failed_deps = HashSet::new();
         let mut dep_error = None;
         let mut loading_rec_deps = loading_deps.clone();
@@ -490,6 +496,7 @@ impl AssetInfos {
             let info = self
                 .get_mut(loaded_asset_id)
                 .expect("Asset info should always exist at this point");
+            info.dependencies = loaded_asset.dependencies;
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
@@ -546,6 +553,74 @@ impl AssetInfos {
         }
     }
 
+    /// Builds the [`AssetDependencyGraph`] of the asset `root`, containing every asset it recursively depends on,
+    /// and every asset that recursively depends on it.
+    pub(crate) fn dependency_graph(&self, root: UntypedAssetId) -> Option<AssetDependencyGraph> {
+        if !self.infos.contains_key(&root) {
+            return None;
+        }
+        let mut dependants: HashMap<UntypedAssetId, Vec<UntypedAssetId>> = HashMap::default();
+        for (id, info) in &self.infos {
+            for dependency in &info.dependencies {
+                dependants.entry(*dependency).or_default().push(*id);
+            }
+        }
+
+        let mut ids = vec![root];
+        let mut indices = HashMap::default();
+        indices.insert(root, 0);
+        let forward = |id: &UntypedAssetId| -> Vec<UntypedAssetId> {
+            self.infos
+                .get(id)
+                .map(|info| info.dependencies.iter().copied().collect())
+                .unwrap_or_default()
+        };
+        let backward = |id: &UntypedAssetId| dependants.get(id).cloned().unwrap_or_default();
+        for edges in [
+            &forward as &dyn Fn(&UntypedAssetId) -> Vec<UntypedAssetId>,
+            &backward,
+        ] {
+            let mut visited = HashSet::new();
+            let mut stack = vec![root];
+            while let Some(id) = stack.pop() {
+                for next in edges(&id) {
+                    if visited.insert(next) {
+                        indices.entry(next).or_insert_with(|| {
+                            ids.push(next);
+                            ids.len() - 1
+                        });
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+
+        let to_indices = |edges: &mut dyn Iterator<Item = &UntypedAssetId>| {
+            let mut edges: Vec<usize> = edges.filter_map(|id| indices.get(id).copied()).collect();
+            edges.sort_unstable();
+            edges
+        };
+        let nodes = ids
+            .iter()
+            .map(|id| {
+                let info = self.infos.get(id);
+                AssetGraphNode {
+                    id: *id,
+                    path: info.and_then(|info| info.path.clone()),
+                    load_state: info
+                        .map(|info| info.load_state.clone())
+                        .unwrap_or(LoadState::NotLoaded),
+                    byte_size: info.and_then(|info| info.byte_size),
+                    dependencies: to_indices(
+                        &mut info.into_iter().flat_map(|info| &info.dependencies),
+                    ),
+                    dependants: to_indices(&mut dependants.get(id).into_iter().flatten()),
+                }
+            })
+            .collect();
+        Some(AssetDependencyGraph { nodes })
+    }
+
     /// Recursively propagates loaded state up the dependency tree.
     fn propagate_loaded_state(
         infos: &mut AssetInfos,
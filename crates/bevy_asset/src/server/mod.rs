@@ -1,3 +1,4 @@
+mod graph;
 mod info;
 mod loaders;
 
@@ -5,7 +6,8 @@ use crate::{
     folder::LoadedFolder,
     io::{
         AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId, AssetSources,
-        ErasedAssetReader, MissingAssetSourceError, MissingProcessedAssetReaderError, Reader,
+        AsyncSeekForward, ErasedAssetReader, MissingAssetSourceError,
+        MissingProcessedAssetReaderError, Reader, STACK_FUTURE_SIZE,
     },
     loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
     meta::{
@@ -25,14 +27,18 @@ use bevy_utils::{
     tracing::{error, info},
     HashSet,
 };
-use core::{any::TypeId, future::Future, panic::AssertUnwindSafe, task::Poll};
+use core::{any::TypeId, future::Future, panic::AssertUnwindSafe, pin::Pin, task::Poll};
 use crossbeam_channel::{Receiver, Sender};
 use derive_more::derive::{Display, Error, From};
 use either::Either;
+use futures_io::AsyncRead;
 use futures_lite::{FutureExt, StreamExt};
 use info::*;
 use loaders::*;
+
+pub use graph::*;
 use parking_lot::{RwLock, RwLockWriteGuard};
+use stackfuture::StackFuture;
 use std::path::{Path, PathBuf};
 
 /// Loads and tracks the state of [`Asset`] values from a configured [`AssetReader`](crate::io::AssetReader). This can be used to kick off new asset loads and
@@ -646,11 +652,18 @@ impl AssetServer {
             (handle.clone().unwrap(), path.clone())
         };
 
+        let mut reader = CountingReader {
+            reader: &mut *reader,
+            bytes: 0,
+        };
         match self
-            .load_with_meta_loader_and_reader(&base_path, meta, &*loader, &mut *reader, true, false)
+            .load_with_meta_loader_and_reader(&base_path, meta, &*loader, &mut reader, true, false)
             .await
         {
             Ok(loaded_asset) => {
+                if let Some(info) = self.data.infos.write().get_mut(base_handle.id()) {
+                    info.byte_size = Some(reader.bytes);
+                }
                 let final_handle = if let Some(label) = path.label_cow() {
                     match loaded_asset.labeled_assets.get(&label) {
                         Some(labeled_asset) => labeled_asset.handle.clone(),
@@ -1112,6 +1125,26 @@ impl AssetServer {
         infos.get_path_ids(&path).collect()
     }
 
+    /// Returns the [`AssetDependencyGraph`] around the asset with the given `id`, which contains its recursive
+    /// dependencies and dependants along with their load states and sizes.
+    /// Returns `None` if the asset is not tracked by this server.
+    pub fn get_dependency_graph(
+        &self,
+        id: impl Into<UntypedAssetId>,
+    ) -> Option<AssetDependencyGraph> {
+        self.data.infos.read().dependency_graph(id.into())
+    }
+
+    /// Returns the [`AssetDependencyGraph`] around the asset at the given path. See
+    /// [`get_dependency_graph`](Self::get_dependency_graph) for details.
+    pub fn get_path_dependency_graph<'a>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+    ) -> Option<AssetDependencyGraph> {
+        let id = self.get_path_id(path)?;
+        self.get_dependency_graph(id)
+    }
+
     /// Returns an active untyped handle for the given path, if the asset at the given path has already started loading,
     /// or is still "alive".
     /// Returns the first handle in the event of multiple assets being registered against a single path.
@@ -1599,6 +1632,49 @@ pub fn handle_internal_asset_events(world: &mut World) {
     });
 }
 
+/// A [`Reader`] that counts the bytes read from the wrapped reader.
+struct CountingReader<'a> {
+    reader: &'a mut dyn Reader,
+    bytes: u64,
+}
+
+impl AsyncRead for CountingReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        let result = Pin::new(&mut *self.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(read)) = result {
+            self.bytes += read as u64;
+        }
+        result
+    }
+}
+
+impl AsyncSeekForward for CountingReader<'_> {
+    fn poll_seek_forward(
+        mut self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+        offset: u64,
+    ) -> Poll<futures_io::Result<u64>> {
+        Pin::new(&mut *self.reader).poll_seek_forward(cx, offset)
+    }
+}
+
+impl Reader for CountingReader<'_> {
+    fn read_to_end<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<u8>,
+    ) -> StackFuture<'a, std::io::Result<usize>, STACK_FUTURE_SIZE> {
+        StackFuture::from_or_box(async move {
+            let read = self.reader.read_to_end(buf).await?;
+            self.bytes += read as u64;
+            Ok(read)
+        })
+    }
+}
+
 /// Internal events for asset load results
 pub(crate) enum InternalAssetEvent {
     Loaded {
@@ -0,0 +1,81 @@
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time, Timer, TimerMode};
+use bevy_utils::Duration;
+
+use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+
+/// Adds diagnostics about archetypes and the memory used by components to an App, from
+/// [`World::memory_stats`].
+///
+/// Collecting these iterates over every archetype and table of the world, so they are only
+/// sampled once every [`wait_duration`](Self::wait_duration).
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct ArchetypeDiagnosticsPlugin {
+    /// The time between two samples of the diagnostics.
+    pub wait_duration: Duration,
+}
+
+impl Default for ArchetypeDiagnosticsPlugin {
+    fn default() -> Self {
+        ArchetypeDiagnosticsPlugin {
+            wait_duration: Duration::from_secs(1),
+        }
+    }
+}
+
+/// State used by the [`ArchetypeDiagnosticsPlugin`]
+#[derive(Resource)]
+struct ArchetypeDiagnosticsState {
+    timer: Timer,
+}
+
+impl Plugin for ArchetypeDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ArchetypeDiagnosticsState {
+            timer: Timer::new(self.wait_duration, TimerMode::Repeating),
+        })
+        .register_diagnostic(Diagnostic::new(Self::ARCHETYPE_COUNT))
+        .register_diagnostic(Diagnostic::new(Self::EMPTY_ARCHETYPE_COUNT))
+        .register_diagnostic(Diagnostic::new(Self::TABLE_COUNT))
+        .register_diagnostic(Diagnostic::new(Self::COMPONENT_BYTES).with_suffix("B"))
+        .register_diagnostic(Diagnostic::new(Self::SPARE_COMPONENT_BYTES).with_suffix("B"))
+        .add_systems(
+            Update,
+            (
+                Self::tick_timer,
+                Self::diagnostic_system
+                    .run_if(|state: Res<ArchetypeDiagnosticsState>| state.timer.just_finished()),
+            )
+                .chain(),
+        );
+    }
+}
+
+impl ArchetypeDiagnosticsPlugin {
+    pub const ARCHETYPE_COUNT: DiagnosticPath = DiagnosticPath::const_new("archetype_count");
+    pub const EMPTY_ARCHETYPE_COUNT: DiagnosticPath =
+        DiagnosticPath::const_new("empty_archetype_count");
+    pub const TABLE_COUNT: DiagnosticPath = DiagnosticPath::const_new("table_count");
+    pub const COMPONENT_BYTES: DiagnosticPath = DiagnosticPath::const_new("component_bytes");
+    pub const SPARE_COMPONENT_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("spare_component_bytes");
+
+    fn tick_timer(mut state: ResMut<ArchetypeDiagnosticsState>, time: Res<Time<Real>>) {
+        state.timer.tick(time.delta());
+    }
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, world: &World) {
+        let stats = world.memory_stats();
+        diagnostics.add_measurement(&Self::ARCHETYPE_COUNT, || stats.archetypes.len() as f64);
+        diagnostics.add_measurement(&Self::EMPTY_ARCHETYPE_COUNT, || {
+            stats.empty_archetype_count() as f64
+        });
+        diagnostics.add_measurement(&Self::TABLE_COUNT, || stats.tables.len() as f64);
+        diagnostics.add_measurement(&Self::COMPONENT_BYTES, || stats.component_bytes() as f64);
+        diagnostics.add_measurement(&Self::SPARE_COMPONENT_BYTES, || stats.spare_bytes() as f64);
+    }
+}
@@ -13,18 +13,22 @@
 
 extern crate alloc;
 
+mod archetype_diagnostics_plugin;
 mod diagnostic;
 mod entity_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod system_execution_diagnostics_plugin;
 #[cfg(feature = "sysinfo_plugin")]
 mod system_information_diagnostics_plugin;
 
 pub use diagnostic::*;
 
+pub use archetype_diagnostics_plugin::ArchetypeDiagnosticsPlugin;
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use system_execution_diagnostics_plugin::SystemExecutionDiagnosticsPlugin;
 #[cfg(feature = "sysinfo_plugin")]
 pub use system_information_diagnostics_plugin::{SystemInfo, SystemInformationDiagnosticsPlugin};
 
@@ -0,0 +1,120 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, schedule::SystemExecutionRecorder};
+use bevy_utils::{Duration, HashMap, Instant};
+
+use crate::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+
+/// Adds per-system timing diagnostics to an App, based on a [`SystemExecutionRecorder`].
+///
+/// Inserts a [`SystemExecutionRecorder`] if there is none, and every frame adds up the time each system
+/// spent running and waiting for conflicting systems in the multi-threaded executor. The totals are added
+/// to the [`DiagnosticsStore`] under `system_execution/<system name>/run_time` and
+/// `system_execution/<system name>/conflict_wait`, as well as the frame total under [`Self::CONFLICT_WAIT`].
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct SystemExecutionDiagnosticsPlugin;
+
+impl Plugin for SystemExecutionDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<SystemExecutionRecorder>()
+            .add_systems(Last, Self::diagnostic_system);
+
+        let mut store = app.world_mut().resource_mut::<DiagnosticsStore>();
+        store.add(Diagnostic::new(Self::CONFLICT_WAIT).with_suffix("ms"));
+    }
+}
+
+impl SystemExecutionDiagnosticsPlugin {
+    /// The total time systems spent waiting for conflicting systems during a frame.
+    pub const CONFLICT_WAIT: DiagnosticPath =
+        DiagnosticPath::const_new("system_execution/conflict_wait");
+
+    /// Returns the path of the diagnostic measuring how long the given system ran for each frame.
+    pub fn run_time_path(system: &str) -> DiagnosticPath {
+        DiagnosticPath::from_components(["system_execution", system, "run_time"])
+    }
+
+    /// Returns the path of the diagnostic measuring how long the given system waited for conflicting systems each frame.
+    pub fn conflict_wait_path(system: &str) -> DiagnosticPath {
+        DiagnosticPath::from_components(["system_execution", system, "conflict_wait"])
+    }
+
+    pub fn diagnostic_system(
+        recorder: Option<Res<SystemExecutionRecorder>>,
+        mut store: ResMut<DiagnosticsStore>,
+        mut cursor: Local<u64>,
+    ) {
+        let Some(recorder) = recorder else {
+            return;
+        };
+
+        let mut totals: HashMap<_, (Duration, Duration)> = HashMap::default();
+        let mut conflict_wait = Duration::ZERO;
+        for record in recorder.read_since(&mut cursor) {
+            let (run_time, wait) = totals.entry(record.system.clone()).or_default();
+            *run_time += record.duration();
+            *wait += record.conflict_wait();
+            conflict_wait += record.conflict_wait();
+        }
+
+        let time = Instant::now();
+        let mut add_measurement = |path: DiagnosticPath, duration: Duration| {
+            if store.get(&path).is_none() {
+                store.add(Diagnostic::new(path.clone()).with_suffix("ms"));
+            }
+            let diagnostic = store.get_mut(&path).unwrap();
+            if diagnostic.is_enabled {
+                diagnostic.add_measurement(DiagnosticMeasurement {
+                    time,
+                    value: duration.as_secs_f64() * 1000.0,
+                });
+            }
+        };
+        for (system, (run_time, wait)) in totals {
+            add_measurement(Self::run_time_path(&system), run_time);
+            add_measurement(Self::conflict_wait_path(&system), wait);
+        }
+        add_measurement(Self::CONFLICT_WAIT, conflict_wait);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::schedule::{ExecutorKind, ScheduleLabel};
+
+    fn busy_system() {}
+
+    #[test]
+    fn adds_system_diagnostics() {
+        let mut app = App::new();
+        for label in [Update.intern(), Last.intern()] {
+            app.edit_schedule(label, |schedule| {
+                schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+            });
+        }
+        app.add_plugins(SystemExecutionDiagnosticsPlugin)
+            .add_systems(Update, busy_system);
+        app.update();
+        app.update();
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        assert!(store
+            .get(&SystemExecutionDiagnosticsPlugin::CONFLICT_WAIT)
+            .unwrap()
+            .measurement()
+            .is_some());
+        let busy = store
+            .iter()
+            .find(|diagnostic| {
+                let path = diagnostic.path().as_str();
+                path.contains("busy_system") && path.ends_with("run_time")
+            })
+            .unwrap();
+        assert_eq!(busy.history_len(), 2);
+    }
+}
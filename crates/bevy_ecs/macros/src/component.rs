@@ -9,7 +9,7 @@ use syn::{
     punctuated::Punctuated,
     spanned::Spanned,
     token::{Comma, Paren},
-    DeriveInput, ExprClosure, ExprPath, Ident, LitStr, Path, Result,
+    Data, DeriveInput, ExprClosure, ExprPath, Field, Ident, LitStr, Path, Result, Type,
 };
 
 pub fn derive_event(input: TokenStream) -> TokenStream {
@@ -63,12 +63,30 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         Err(e) => return e.into_compile_error().into(),
     };
 
+    let relationship = match derive_relationship(&ast, &attrs, &bevy_ecs_path) {
+        Ok(value) => value,
+        Err(err) => return err.into_compile_error().into(),
+    };
+    let relationship_target = match derive_relationship_target(&ast, &attrs, &bevy_ecs_path) {
+        Ok(value) => value,
+        Err(err) => return err.into_compile_error().into(),
+    };
+
+    let mut attrs = attrs;
+    if let Err(err) = add_relationship_hooks(&mut attrs, &bevy_ecs_path) {
+        return err.into_compile_error().into();
+    }
+    if let Err(err) = add_index_hooks(&mut attrs, &bevy_ecs_path) {
+        return err.into_compile_error().into();
+    }
+
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
 
     let on_add = hook_register_function_call(quote! {on_add}, attrs.on_add);
     let on_insert = hook_register_function_call(quote! {on_insert}, attrs.on_insert);
     let on_replace = hook_register_function_call(quote! {on_replace}, attrs.on_replace);
     let on_remove = hook_register_function_call(quote! {on_remove}, attrs.on_remove);
+    let on_despawn = hook_register_function_call(quote! {on_despawn}, attrs.on_despawn);
 
     ast.generics
         .make_where_clause()
@@ -127,6 +145,26 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
 
+    let indexed_component = attrs.index.then(|| {
+        quote! {
+            impl #impl_generics #bevy_ecs_path::index::IndexedComponent for #struct_name #type_generics #where_clause {}
+        }
+    });
+
+    // Relationship targets are kept in sync by their relationships, so cloning them directly would
+    // produce a collection that doesn't match the relationships in the world.
+    let clone_handler = if attrs.relationship_target.is_some() {
+        quote! {
+            #bevy_ecs_path::component::ComponentCloneHandler::Ignore
+        }
+    } else {
+        quote! {
+            use #bevy_ecs_path::component::{ComponentCloneViaClone, ComponentCloneBase};
+            (&&&#bevy_ecs_path::component::ComponentCloneSpecializationWrapper::<Self>::default())
+                .get_component_clone_handler()
+        }
+    };
+
     // This puts `register_required` before `register_recursive_requires` to ensure that the constructors of _all_ top
     // level components are initialized first, giving them precedence over recursively defined constructors for the same component type
     TokenStream::from(quote! {
@@ -149,14 +187,19 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
                 #on_insert
                 #on_replace
                 #on_remove
+                #on_despawn
             }
 
             fn get_component_clone_handler() -> #bevy_ecs_path::component::ComponentCloneHandler {
-                use #bevy_ecs_path::component::{ComponentCloneViaClone, ComponentCloneBase};
-                (&&&#bevy_ecs_path::component::ComponentCloneSpecializationWrapper::<Self>::default())
-                    .get_component_clone_handler()
+                #clone_handler
             }
         }
+
+        #relationship
+
+        #relationship_target
+
+        #indexed_component
     })
 }
 
@@ -185,11 +228,15 @@ pub fn document_required_components(attr: TokenStream, item: TokenStream) -> Tok
 pub const COMPONENT: &str = "component";
 pub const STORAGE: &str = "storage";
 pub const REQUIRE: &str = "require";
+pub const RELATIONSHIP: &str = "relationship";
+pub const RELATIONSHIP_TARGET: &str = "relationship_target";
 
 pub const ON_ADD: &str = "on_add";
 pub const ON_INSERT: &str = "on_insert";
 pub const ON_REPLACE: &str = "on_replace";
 pub const ON_REMOVE: &str = "on_remove";
+pub const ON_DESPAWN: &str = "on_despawn";
+pub const INDEX: &str = "index";
 
 struct Attrs {
     storage: StorageTy,
@@ -198,6 +245,19 @@ struct Attrs {
     on_insert: Option<ExprPath>,
     on_replace: Option<ExprPath>,
     on_remove: Option<ExprPath>,
+    on_despawn: Option<ExprPath>,
+    relationship: Option<Relationship>,
+    relationship_target: Option<RelationshipTarget>,
+    index: bool,
+}
+
+struct Relationship {
+    relationship_target: Type,
+}
+
+struct RelationshipTarget {
+    relationship: Type,
+    linked_spawn: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -216,6 +276,9 @@ enum RequireFunc {
     Closure(ExprClosure),
 }
 
+// values for `relationship_target` attribute
+const LINKED_SPAWN: &str = "linked_spawn";
+
 // values for `storage` attribute
 const TABLE: &str = "Table";
 const SPARSE_SET: &str = "SparseSet";
@@ -227,7 +290,11 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
         on_insert: None,
         on_replace: None,
         on_remove: None,
+        on_despawn: None,
         requires: None,
+        relationship: None,
+        relationship_target: None,
+        index: false,
     };
 
     let mut require_paths = HashSet::new();
@@ -257,6 +324,12 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                 } else if nested.path.is_ident(ON_REMOVE) {
                     attrs.on_remove = Some(nested.value()?.parse::<ExprPath>()?);
                     Ok(())
+                } else if nested.path.is_ident(ON_DESPAWN) {
+                    attrs.on_despawn = Some(nested.value()?.parse::<ExprPath>()?);
+                    Ok(())
+                } else if nested.path.is_ident(INDEX) {
+                    attrs.index = true;
+                    Ok(())
                 } else {
                     Err(nested.error("Unsupported attribute"))
                 }
@@ -277,9 +350,59 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
             } else {
                 attrs.requires = Some(punctuated);
             }
+        } else if attr.path().is_ident(RELATIONSHIP) {
+            let mut relationship_target = None;
+            attr.parse_nested_meta(|nested| {
+                if nested.path.is_ident(RELATIONSHIP_TARGET) {
+                    relationship_target = Some(nested.value()?.parse::<Type>()?);
+                    Ok(())
+                } else {
+                    Err(nested.error("Unsupported attribute"))
+                }
+            })?;
+            let Some(relationship_target) = relationship_target else {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "Missing `relationship_target = X` in `relationship` attribute.",
+                ));
+            };
+            attrs.relationship = Some(Relationship {
+                relationship_target,
+            });
+        } else if attr.path().is_ident(RELATIONSHIP_TARGET) {
+            let mut relationship = None;
+            let mut linked_spawn = false;
+            attr.parse_nested_meta(|nested| {
+                if nested.path.is_ident(RELATIONSHIP) {
+                    relationship = Some(nested.value()?.parse::<Type>()?);
+                    Ok(())
+                } else if nested.path.is_ident(LINKED_SPAWN) {
+                    linked_spawn = true;
+                    Ok(())
+                } else {
+                    Err(nested.error("Unsupported attribute"))
+                }
+            })?;
+            let Some(relationship) = relationship else {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "Missing `relationship = X` in `relationship_target` attribute.",
+                ));
+            };
+            attrs.relationship_target = Some(RelationshipTarget {
+                relationship,
+                linked_spawn,
+            });
         }
     }
 
+    if attrs.relationship.is_some() && attrs.relationship_target.is_some() {
+        return Err(syn::Error::new(
+            ast.span(),
+            "A component cannot be both a `relationship` and a `relationship_target`.",
+        ));
+    }
+
     Ok(attrs)
 }
 
@@ -317,3 +440,175 @@ fn hook_register_function_call(
 ) -> Option<TokenStream2> {
     function.map(|meta| quote! { hooks. #hook (#meta); })
 }
+
+/// Registers the component hooks that keep a relationship and its target in sync,
+/// rejecting user-defined hooks that would otherwise be overwritten.
+fn add_relationship_hooks(attrs: &mut Attrs, bevy_ecs_path: &Path) -> Result<()> {
+    fn set_hook(hook: &mut Option<ExprPath>, name: &str, value: ExprPath) -> Result<()> {
+        if hook.is_some() {
+            return Err(syn::Error::new(
+                value.span(),
+                format!("Custom `{name}` hooks are not supported on relationship components, as relationships already define an `{name}` hook."),
+            ));
+        }
+        *hook = Some(value);
+        Ok(())
+    }
+
+    if attrs.relationship.is_some() {
+        set_hook(
+            &mut attrs.on_insert,
+            ON_INSERT,
+            parse_quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_insert),
+        )?;
+        set_hook(
+            &mut attrs.on_replace,
+            ON_REPLACE,
+            parse_quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_replace),
+        )?;
+    }
+
+    if let Some(relationship_target) = &attrs.relationship_target {
+        set_hook(
+            &mut attrs.on_replace,
+            ON_REPLACE,
+            parse_quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_replace),
+        )?;
+        if relationship_target.linked_spawn {
+            set_hook(
+                &mut attrs.on_despawn,
+                ON_DESPAWN,
+                parse_quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_despawn),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Registers the component hooks that keep the index of an indexed component up to date,
+/// rejecting user-defined hooks that would otherwise be overwritten.
+fn add_index_hooks(attrs: &mut Attrs, bevy_ecs_path: &Path) -> Result<()> {
+    if !attrs.index {
+        return Ok(());
+    }
+    for (hook, name, value) in [
+        (
+            &mut attrs.on_insert,
+            ON_INSERT,
+            parse_quote!(<Self as #bevy_ecs_path::index::IndexedComponent>::on_insert),
+        ),
+        (
+            &mut attrs.on_replace,
+            ON_REPLACE,
+            parse_quote!(<Self as #bevy_ecs_path::index::IndexedComponent>::on_replace),
+        ),
+    ] {
+        if let Some(existing) = hook {
+            return Err(syn::Error::new(
+                existing.span(),
+                format!("Custom `{name}` hooks are not supported on indexed components, as indexes already define an `{name}` hook."),
+            ));
+        }
+        *hook = Some(value);
+    }
+    Ok(())
+}
+
+/// Returns the single field of a relationship struct, which stores either the target [`Entity`]
+/// or the source collection.
+fn relationship_field<'a>(ast: &'a DeriveInput, attribute: &str) -> Result<&'a Field> {
+    let error = || {
+        syn::Error::new(
+            ast.span(),
+            format!("`{attribute}` can only be derived for structs with a single field."),
+        )
+    };
+    let Data::Struct(data) = &ast.data else {
+        return Err(error());
+    };
+    let mut fields = data.fields.iter();
+    match (fields.next(), fields.next()) {
+        (Some(field), None) => Ok(field),
+        _ => Err(error()),
+    }
+}
+
+fn derive_relationship(
+    ast: &DeriveInput,
+    attrs: &Attrs,
+    bevy_ecs_path: &Path,
+) -> Result<Option<TokenStream2>> {
+    let Some(relationship) = &attrs.relationship else {
+        return Ok(None);
+    };
+    let field = relationship_field(ast, RELATIONSHIP)?;
+    let (get, from) = match &field.ident {
+        Some(ident) => (quote! { self.#ident }, quote! { Self { #ident: entity } }),
+        None => (quote! { self.0 }, quote! { Self(entity) }),
+    };
+
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+    let relationship_target = &relationship.relationship_target;
+
+    Ok(Some(quote! {
+        impl #impl_generics #bevy_ecs_path::relationship::Relationship for #struct_name #type_generics #where_clause {
+            type RelationshipTarget = #relationship_target;
+
+            #[inline(always)]
+            fn get(&self) -> #bevy_ecs_path::entity::Entity {
+                #get
+            }
+
+            #[inline]
+            fn from(entity: #bevy_ecs_path::entity::Entity) -> Self {
+                #from
+            }
+        }
+    }))
+}
+
+fn derive_relationship_target(
+    ast: &DeriveInput,
+    attrs: &Attrs,
+    bevy_ecs_path: &Path,
+) -> Result<Option<TokenStream2>> {
+    let Some(relationship_target) = &attrs.relationship_target else {
+        return Ok(None);
+    };
+    let field = relationship_field(ast, RELATIONSHIP_TARGET)?;
+    let collection = &field.ty;
+    let (member, from) = match &field.ident {
+        Some(ident) => (quote! { #ident }, quote! { Self { #ident: collection } }),
+        None => (quote! { 0 }, quote! { Self(collection) }),
+    };
+
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+    let relationship = &relationship_target.relationship;
+    let linked_spawn = relationship_target.linked_spawn;
+
+    Ok(Some(quote! {
+        impl #impl_generics #bevy_ecs_path::relationship::RelationshipTarget for #struct_name #type_generics #where_clause {
+            const LINKED_SPAWN: bool = #linked_spawn;
+            type Relationship = #relationship;
+            type Collection = #collection;
+
+            #[inline]
+            fn collection(&self) -> &Self::Collection {
+                &self.#member
+            }
+
+            #[inline]
+            fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+                &mut self.#member
+            }
+
+            #[inline]
+            fn from_collection_risky(collection: Self::Collection) -> Self {
+                #from
+            }
+        }
+    }))
+}
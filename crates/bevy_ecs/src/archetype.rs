@@ -24,7 +24,10 @@ use crate::{
     component::{ComponentId, Components, RequiredComponentConstructor, StorageType},
     entity::{Entity, EntityLocation},
     observer::Observers,
-    storage::{ImmutableSparseSet, SparseArray, SparseSet, SparseSetIndex, TableId, TableRow},
+    storage::{
+        CompactionPolicy, ImmutableSparseSet, SparseArray, SparseSet, SparseSetIndex, TableId,
+        TableRow,
+    },
 };
 use bevy_utils::HashMap;
 use core::{
@@ -352,6 +355,7 @@ bitflags::bitflags! {
         const ON_INSERT_OBSERVER = (1 << 5);
         const ON_REPLACE_OBSERVER = (1 << 6);
         const ON_REMOVE_OBSERVER = (1 << 7);
+        const ON_DESPAWN_HOOK = (1 << 8);
     }
 }
 
@@ -642,6 +646,11 @@ impl Archetype {
         self.entities.clear();
     }
 
+    /// Shrinks the capacity of the entity list of this archetype to its length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.entities.shrink_to_fit();
+    }
+
     /// Returns true if any of the components in this archetype have `on_add` hooks
     #[inline]
     pub fn has_add_hook(&self) -> bool {
@@ -666,6 +675,12 @@ impl Archetype {
         self.flags().contains(ArchetypeFlags::ON_REMOVE_HOOK)
     }
 
+    /// Returns true if any of the components in this archetype have `on_despawn` hooks
+    #[inline]
+    pub fn has_despawn_hook(&self) -> bool {
+        self.flags().contains(ArchetypeFlags::ON_DESPAWN_HOOK)
+    }
+
     /// Returns true if any of the components in this archetype have at least one [`OnAdd`] observer
     ///
     /// [`OnAdd`]: crate::world::OnAdd
@@ -965,6 +980,15 @@ impl Archetypes {
         }
     }
 
+    /// Shrinks the entity lists of the [`Archetype`]s whose unused capacity matches `policy`.
+    pub(crate) fn compact(&mut self, policy: &CompactionPolicy) {
+        for archetype in &mut self.archetypes {
+            if policy.should_shrink(archetype.len(), archetype.entities.capacity()) {
+                archetype.shrink_to_fit();
+            }
+        }
+    }
+
     /// Get the component index
     pub(crate) fn component_index(&self) -> &ComponentIndex {
         &self.by_component
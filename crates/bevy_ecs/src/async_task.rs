@@ -0,0 +1,390 @@
+//! Async tasks that can `await` access to the [`World`].
+//!
+//! A task is a [`Future`] stored in the [`AsyncTask`] component of an entity. It is spawned with
+//! [`Commands::spawn_task`] or [`World::spawn_task`], and receives an [`AsyncWorld`] which it can use to run
+//! closures and systems on the world:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::async_task::run_async_tasks;
+//!
+//! #[derive(Resource, Default)]
+//! struct Progress(u32);
+//!
+//! fn start_loading(mut commands: Commands) {
+//!     commands.spawn_task(|world| async move {
+//!         for step in 1..=3 {
+//!             // Each access resumes the task during the next run of `run_async_tasks`.
+//!             world.run(move |world| world.resource_mut::<Progress>().0 = step).await;
+//!         }
+//!     });
+//! }
+//!
+//! let mut world = World::new();
+//! world.init_resource::<Progress>();
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems((start_loading, run_async_tasks).chain());
+//! schedule.run(&mut world);
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(run_async_tasks);
+//! for _ in 0..3 {
+//!     schedule.run(&mut world);
+//! }
+//! assert_eq!(world.resource::<Progress>().0, 3);
+//! ```
+//!
+//! Tasks are driven by the [`run_async_tasks`] system, which should be added to the schedule where the world
+//! access of tasks should happen. Each run of [`run_async_tasks`] first applies the world accesses requested
+//! since its last run, then polls the tasks that can make progress. A task therefore gets at most one world
+//! access per run, and always resumes right after its access is applied, before any other system runs.
+//!
+//! Tasks can `await` any other future, like a [`Task`](bevy_tasks::Task) spawned on a
+//! [`TaskPool`](bevy_tasks::TaskPool) for expensive work. They are cancelled by removing their [`AsyncTask`]
+//! component with [`EntityCommands::cancel_task`], or by despawning their entity. When a task completes,
+//! [`AsyncTaskFinished`] is triggered for its entity and the [`AsyncTask`] component is removed.
+//! World accesses requested by a task that has been cancelled, despawned or has finished are never applied.
+//! Tasks on [disabled](crate::entity_disabling) entities are paused.
+
+use alloc::{sync::Arc, task::Wake};
+use core::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+use std::sync::Mutex;
+
+use bevy_utils::synccell::SyncCell;
+
+use crate::{
+    self as bevy_ecs,
+    change_detection::DetectChangesMut,
+    component::{Component, ComponentId},
+    entity::Entity,
+    event::Event,
+    query::QueryState,
+    system::{Commands, EntityCommands, IntoSystem, Resource, RunSystemError, RunSystemOnce},
+    world::{DeferredWorld, EntityWorldMut, World},
+};
+
+type WorldRequest = Box<dyn FnOnce(&mut World) + Send>;
+
+/// A world access requested through an [`AsyncWorld`].
+struct AsyncWorldRequest {
+    /// Cleared once the [`AsyncTask`] that requested the access is removed, if it was requested by a task.
+    alive: Option<Arc<AtomicBool>>,
+    request: WorldRequest,
+}
+
+/// The world accesses requested by tasks, applied by [`run_async_tasks`].
+#[derive(Resource, Default)]
+struct AsyncWorldRequests(Arc<Mutex<Vec<AsyncWorldRequest>>>);
+
+/// A handle that lets a [`Future`] `await` access to the [`World`].
+///
+/// The accesses are applied by the [`run_async_tasks`] system. This handle can be used from any future,
+/// including ones spawned on a [`TaskPool`](bevy_tasks::TaskPool), but the returned futures only complete
+/// once [`run_async_tasks`] runs.
+///
+/// The handle passed to an [`AsyncTask`] is tied to it: once the task is cancelled, despawned or has finished,
+/// its pending and future accesses are dropped without being applied, and the futures awaiting them never complete.
+#[derive(Clone)]
+pub struct AsyncWorld {
+    requests: Arc<Mutex<Vec<AsyncWorldRequest>>>,
+    alive: Option<Arc<AtomicBool>>,
+}
+
+impl AsyncWorld {
+    /// Runs `f` with exclusive access to the world during the next run of [`run_async_tasks`], and returns its output.
+    pub fn run<R, F>(&self, f: F) -> impl Future<Output = R> + Send + 'static
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut World) -> R + Send + 'static,
+    {
+        let requests = self.requests.clone();
+        let alive = self.alive.clone();
+        async move {
+            let response = Arc::new(Mutex::new(Response {
+                output: None,
+                waker: None,
+            }));
+            let request_response = response.clone();
+            requests.lock().unwrap().push(AsyncWorldRequest {
+                alive,
+                request: Box::new(move |world: &mut World| {
+                    let output = f(world);
+                    let mut response = request_response.lock().unwrap();
+                    response.output = Some(output);
+                    if let Some(waker) = response.waker.take() {
+                        waker.wake();
+                    }
+                }),
+            });
+            poll_fn(|cx| {
+                let mut response = response.lock().unwrap();
+                match response.output.take() {
+                    Some(output) => Poll::Ready(output),
+                    None => {
+                        response.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            })
+            .await
+        }
+    }
+
+    /// Runs `system` once during the next run of [`run_async_tasks`], and returns its output.
+    ///
+    /// See [`RunSystemOnce::run_system_once`].
+    pub fn run_system<S, Out, Marker>(
+        &self,
+        system: S,
+    ) -> impl Future<Output = Result<Out, RunSystemError>> + Send + 'static
+    where
+        S: IntoSystem<(), Out, Marker> + Send + 'static,
+        Out: Send + 'static,
+    {
+        self.run(move |world| world.run_system_once(system))
+    }
+
+    /// Waits until the next run of [`run_async_tasks`].
+    pub fn yield_now(&self) -> impl Future<Output = ()> + Send + 'static {
+        self.run(|_| {})
+    }
+}
+
+struct Response<R> {
+    output: Option<R>,
+    waker: Option<Waker>,
+}
+
+/// A component storing a [`Future`] that is driven by [`run_async_tasks`].
+///
+/// See the [module docs](crate::async_task) for more information.
+#[derive(Component)]
+#[component(on_replace = AsyncTask::on_replace)]
+pub struct AsyncTask {
+    future: SyncCell<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    waker: Arc<TaskWaker>,
+    alive: Arc<AtomicBool>,
+}
+
+impl AsyncTask {
+    /// Creates a task from the future returned by `task`.
+    ///
+    /// The task isn't driven until it is inserted into an entity of `world`.
+    pub fn new<F, Fut>(world: &mut World, task: F) -> Self
+    where
+        F: FnOnce(AsyncWorld) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let alive = Arc::new(AtomicBool::new(true));
+        let mut async_world = world.async_world();
+        async_world.alive = Some(alive.clone());
+        let future = task(async_world);
+        Self {
+            future: SyncCell::new(Box::pin(future)),
+            // Newly spawned tasks are polled during the next run of `run_async_tasks`.
+            waker: Arc::new(TaskWaker(AtomicBool::new(true))),
+            alive,
+        }
+    }
+
+    /// Drops the pending world accesses of the task once it is cancelled, despawned, replaced or finished.
+    fn on_replace(world: DeferredWorld, entity: Entity, _: ComponentId) {
+        if let Some(task) = world.get::<AsyncTask>(entity) {
+            task.alive.store(false, Ordering::Release);
+        }
+    }
+}
+
+/// Marks a task as ready to be polled.
+struct TaskWaker(AtomicBool);
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// Triggered for the entity of an [`AsyncTask`] when its future completes.
+#[derive(Event, Debug)]
+pub struct AsyncTaskFinished;
+
+/// Applies the world accesses requested by tasks, then polls the [`AsyncTask`]s that can make progress.
+///
+/// See the [module docs](crate::async_task) for more information.
+pub fn run_async_tasks(world: &mut World, tasks: &mut QueryState<(Entity, &mut AsyncTask)>) {
+    if let Some(requests) = world.get_resource::<AsyncWorldRequests>() {
+        let requests = core::mem::take(&mut *requests.0.lock().unwrap());
+        for AsyncWorldRequest { alive, request } in requests {
+            if alive.is_some_and(|alive| !alive.load(Ordering::Acquire)) {
+                continue;
+            }
+            request(world);
+            world.flush();
+        }
+    }
+
+    let mut finished = Vec::new();
+    for (entity, mut task) in tasks.iter_mut(world) {
+        let task = task.bypass_change_detection();
+        if !task.waker.0.swap(false, Ordering::Acquire) {
+            continue;
+        }
+        let waker = Waker::from(task.waker.clone());
+        let mut cx = Context::from_waker(&waker);
+        if task.future.get().as_mut().poll(&mut cx).is_ready() {
+            finished.push(entity);
+        }
+    }
+
+    for entity in finished {
+        world.entity_mut(entity).remove::<AsyncTask>();
+        world.trigger_targets(AsyncTaskFinished, entity);
+    }
+}
+
+impl World {
+    /// Returns an [`AsyncWorld`] to access this world from a [`Future`].
+    pub fn async_world(&mut self) -> AsyncWorld {
+        AsyncWorld {
+            requests: self.get_resource_or_init::<AsyncWorldRequests>().0.clone(),
+            alive: None,
+        }
+    }
+
+    /// Spawns an entity with an [`AsyncTask`] running the future returned by `task`.
+    ///
+    /// See the [module docs](crate::async_task) for more information.
+    pub fn spawn_task<F, Fut>(&mut self, task: F) -> EntityWorldMut
+    where
+        F: FnOnce(AsyncWorld) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let task = AsyncTask::new(self, task);
+        self.spawn(task)
+    }
+}
+
+impl<'w, 's> Commands<'w, 's> {
+    /// Spawns an entity with an [`AsyncTask`] running the future returned by `task`.
+    ///
+    /// See the [module docs](crate::async_task) for more information.
+    pub fn spawn_task<F, Fut>(&mut self, task: F) -> EntityCommands
+    where
+        F: FnOnce(AsyncWorld) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut entity_commands = self.spawn_empty();
+        entity_commands.queue(move |mut entity: EntityWorldMut| {
+            let task = entity.world_scope(|world| AsyncTask::new(world, task));
+            entity.insert(task);
+        });
+        entity_commands
+    }
+}
+
+impl EntityCommands<'_> {
+    /// Cancels the [`AsyncTask`] of this entity by removing it, without despawning the entity.
+    pub fn cancel_task(&mut self) -> &mut Self {
+        self.remove::<AsyncTask>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        async_task::{run_async_tasks, AsyncTask, AsyncTaskFinished},
+        prelude::*,
+    };
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn log(world: &mut World, message: &'static str) {
+        world.resource_mut::<Log>().0.push(message);
+    }
+
+    fn drive(world: &mut World, runs: usize) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(run_async_tasks);
+        for _ in 0..runs {
+            schedule.run(world);
+        }
+    }
+
+    #[test]
+    fn async_task_world_access() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.add_observer(|_: Trigger<AsyncTaskFinished>, mut log: ResMut<Log>| {
+            log.0.push("finished");
+        });
+        let task = world
+            .spawn_task(|world| async move {
+                world.run(|world| log(world, "first")).await;
+                world.yield_now().await;
+                let value = world.run_system(|log: Res<Log>| log.0.len()).await.unwrap();
+                assert_eq!(value, 1);
+                world.run(|world| log(world, "second")).await;
+            })
+            .id();
+
+        // The first run only polls the new task.
+        drive(&mut world, 1);
+        assert!(world.resource::<Log>().0.is_empty());
+        drive(&mut world, 1);
+        assert_eq!(world.resource::<Log>().0, ["first"]);
+        drive(&mut world, 3);
+        assert_eq!(world.resource::<Log>().0, ["first", "second", "finished"]);
+        assert!(!world.entity(task).contains::<AsyncTask>());
+    }
+
+    #[test]
+    fn async_task_commands() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+
+        let mut commands = world.commands();
+        let cancelled = commands
+            .spawn_task(|world| async move {
+                world.run(|world| log(world, "cancelled")).await;
+            })
+            .id();
+        commands.spawn_task(|world| async move {
+            world.run(|world| log(world, "completed")).await;
+        });
+        world.flush();
+
+        drive(&mut world, 1);
+        world.commands().entity(cancelled).cancel_task();
+        world.flush();
+        drive(&mut world, 2);
+        // The access was requested before the task was cancelled, but is never applied.
+        assert_eq!(world.resource::<Log>().0, ["completed"]);
+        assert_eq!(world.query::<&AsyncTask>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn async_task_despawned() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let task = world
+            .spawn_task(|world| async move {
+                world.run(|world| log(world, "despawned")).await;
+            })
+            .id();
+        drive(&mut world, 1);
+        world.despawn(task);
+        drive(&mut world, 1);
+        assert!(world.resource::<Log>().0.is_empty());
+    }
+}
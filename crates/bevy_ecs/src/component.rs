@@ -287,6 +287,7 @@ pub use bevy_ecs_macros::require;
 /// - `#[component(on_insert = on_insert_function)]`
 /// - `#[component(on_replace = on_replace_function)]`
 /// - `#[component(on_remove = on_remove_function)]`
+/// - `#[component(on_despawn = on_despawn_function)]`
 ///
 /// ```
 /// # use bevy_ecs::component::Component;
@@ -314,6 +315,13 @@ pub use bevy_ecs_macros::require;
 /// }
 /// ```
 ///
+/// # Indexing components
+///
+/// Components that implement [`Hash`], [`Eq`] and [`Clone`] can be indexed with `#[component(index)]`, to look up
+/// entities by component value with the [`Index`](crate::index::Index) system parameter.
+/// Indexed components use the `on_insert` and `on_replace` hooks, so they can't define their own.
+/// See the [`index`](crate::index) module for more information.
+///
 /// # Implementing the trait for foreign types
 ///
 /// As a consequence of the [orphan rule], it is not possible to separate into two different crates the implementation of `Component` from the definition of a type.
@@ -484,6 +492,7 @@ pub struct ComponentHooks {
     pub(crate) on_insert: Option<ComponentHook>,
     pub(crate) on_replace: Option<ComponentHook>,
     pub(crate) on_remove: Option<ComponentHook>,
+    pub(crate) on_despawn: Option<ComponentHook>,
 }
 
 impl ComponentHooks {
@@ -550,6 +559,20 @@ impl ComponentHooks {
             .expect("Component already has an on_remove hook")
     }
 
+    /// Register a [`ComponentHook`] that will be run when an entity with this component is despawned.
+    ///
+    /// An `on_despawn` hook always runs before any `on_replace` and `on_remove` hooks triggered by the despawn,
+    /// so the component and the rest of the entity are still accessible.
+    /// Removing the component without despawning the entity does *not* run this hook.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_despawn` hook
+    pub fn on_despawn(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_despawn(hook)
+            .expect("Component already has an on_despawn hook")
+    }
+
     /// Attempt to register a [`ComponentHook`] that will be run when this component is added to an entity.
     ///
     /// This is a fallible version of [`Self::on_add`].
@@ -601,6 +624,19 @@ impl ComponentHooks {
         self.on_remove = Some(hook);
         Some(self)
     }
+
+    /// Attempt to register a [`ComponentHook`] that will be run when an entity with this component is despawned.
+    ///
+    /// This is a fallible version of [`Self::on_despawn`].
+    ///
+    /// Returns `None` if the component already has an `on_despawn` hook.
+    pub fn try_on_despawn(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_despawn.is_some() {
+            return None;
+        }
+        self.on_despawn = Some(hook);
+        Some(self)
+    }
 }
 
 /// Stores metadata for a type of component or resource stored in a specific [`World`].
@@ -690,6 +726,9 @@ impl ComponentInfo {
         if self.hooks().on_remove.is_some() {
             flags.insert(ArchetypeFlags::ON_REMOVE_HOOK);
         }
+        if self.hooks().on_despawn.is_some() {
+            flags.insert(ArchetypeFlags::ON_DESPAWN_HOOK);
+        }
     }
 
     /// Provides a reference to the collection of hooks associated with this [`Component`]
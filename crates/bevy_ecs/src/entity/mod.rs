@@ -640,6 +640,65 @@ impl Entities {
         );
     }
 
+    /// Captures the allocation state of these [`Entities`]: the generation of every index and the freelist.
+    ///
+    /// Must not be called while reserved entities are awaiting `flush()`.
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) fn allocator_state(&mut self) -> EntityAllocatorState {
+        self.verify_flushed();
+        EntityAllocatorState {
+            generations: self.meta.iter().map(|meta| meta.generation).collect(),
+            pending: self.pending.clone(),
+            len: self.len,
+        }
+    }
+
+    /// Restores an allocation state captured by [`Entities::allocator_state`], so that future allocations
+    /// hand out the same IDs as they would have at the time of the capture.
+    ///
+    /// This only overwrites the generations of free indices and the freelist, and keeps every live entity
+    /// along with its location. Indices that are free now but were not free in the captured state, or that
+    /// were not allocated yet, are handed out after the captured freelist is exhausted.
+    ///
+    /// Must not be called while reserved entities are awaiting `flush()`.
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) fn restore_allocator_state(&mut self, state: &EntityAllocatorState) {
+        self.verify_flushed();
+        let is_live = |meta: &EntityMeta| meta.location.archetype_id != ArchetypeId::INVALID;
+        let len = self
+            .meta
+            .iter()
+            .rposition(is_live)
+            .map_or(0, |index| index + 1)
+            .max(state.generations.len());
+        self.meta.resize(len, EntityMeta::EMPTY);
+        for (meta, generation) in self.meta.iter_mut().zip(&state.generations) {
+            if !is_live(meta) {
+                meta.generation = *generation;
+            }
+        }
+
+        let mut captured_free = vec![false; len];
+        for index in &state.pending {
+            captured_free[*index as usize] = true;
+        }
+        // `alloc` pops from the end of the freelist, so the captured freelist goes last.
+        let mut pending: Vec<u32> = (0..len)
+            .rev()
+            .filter(|index| !captured_free[*index] && !is_live(&self.meta[*index]))
+            .map(|index| index as u32)
+            .collect();
+        pending.extend(
+            state
+                .pending
+                .iter()
+                .filter(|index| !is_live(&self.meta[**index as usize])),
+        );
+        self.pending = pending;
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+        self.len = self.meta.iter().filter(|meta| is_live(meta)).count() as u32;
+    }
+
     /// Allocate an entity ID directly.
     pub fn alloc(&mut self) -> Entity {
         self.verify_flushed();
@@ -940,6 +999,44 @@ impl Entities {
     }
 }
 
+/// The allocation state of [`Entities`], as captured by [`Entities::allocator_state`].
+#[cfg(feature = "bevy_reflect")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub(crate) struct EntityAllocatorState {
+    generations: Vec<NonZero<u32>>,
+    pending: Vec<u32>,
+    len: u32,
+}
+
+#[cfg(all(feature = "bevy_reflect", feature = "serialize"))]
+impl EntityAllocatorState {
+    /// Returns `true` if this state could have been captured from [`Entities`] where exactly
+    /// the given `entities` are alive, as is the case for states that weren't deserialized.
+    pub(crate) fn is_consistent_with(&self, entities: &[Entity]) -> bool {
+        let mut free = vec![false; self.generations.len()];
+        for index in &self.pending {
+            match free.get_mut(*index as usize) {
+                Some(free) if !*free => *free = true,
+                _ => return false,
+            }
+        }
+        let mut alive = free;
+        entities.len() == self.len as usize
+            && self.len as usize + self.pending.len() == self.generations.len()
+            && entities.iter().all(|entity| {
+                let index = entity.index() as usize;
+                match alive.get_mut(index) {
+                    Some(taken) if !*taken && self.generations[index] == entity.generation => {
+                        *taken = true;
+                        true
+                    }
+                    _ => false,
+                }
+            })
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 struct EntityMeta {
     /// The current generation of the [`Entity`].
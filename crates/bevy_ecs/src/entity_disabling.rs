@@ -0,0 +1,239 @@
+//! Disabled entities do not show up in queries unless the query explicitly mentions them.
+//!
+//! If for example we have `Disabled` as an entity disabling component, when you add `Disabled`
+//! to an entity, the entity will only be visible to queries with a filter like
+//! [`With`]`<Disabled>` or query data like [`Has`]`<Disabled>`.
+//!
+//! This is useful for pooled or pre-spawned entities that should not be touched by regular
+//! gameplay systems until they are activated, without adding `Without<Inactive>` to every query.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::entity_disabling::Disabled;
+//!
+//! #[derive(Component)]
+//! struct Enemy;
+//!
+//! let mut world = World::new();
+//! world.spawn(Enemy);
+//! world.spawn((Enemy, Disabled));
+//!
+//! // Disabled entities are skipped by default...
+//! assert_eq!(world.query::<&Enemy>().iter(&world).count(), 1);
+//! // ...unless the query explicitly mentions the disabling component.
+//! assert_eq!(world.query_filtered::<&Enemy, With<Disabled>>().iter(&world).count(), 1);
+//! assert_eq!(world.query::<(&Enemy, Has<Disabled>)>().iter(&world).count(), 2);
+//! ```
+//!
+//! ### Disabling components
+//!
+//! Each [`World`] stores its [`DefaultQueryFilters`], which contain the list of "disabling components".
+//! Any component registered there is treated like [`Disabled`]: entities that have it are hidden from queries
+//! that do not mention it. [`Disabled`] is registered by default, and additional components can be registered
+//! with [`DefaultQueryFilters::register_disabling_component`] through [`World::default_query_filters_mut`].
+//!
+//! ### Warnings
+//!
+//! Currently, only queries for which the cache is built after enabling a default query filter will have entities
+//! with those components filtered. As a result, they should generally only be modified before the app starts.
+//!
+//! Because filters are applied to all queries they can have performance implication for
+//! the entire [`World`], especially when they cause queries to mix sparse and table components.
+//! See [`Query` performance] for more info.
+//!
+//! [`With`]: crate::prelude::With
+//! [`Has`]: crate::prelude::Has
+//! [`World`]: crate::prelude::World
+//! [`World::default_query_filters_mut`]: crate::prelude::World::default_query_filters_mut
+//! [`Query` performance]: crate::prelude::Query#performance
+
+use crate as bevy_ecs;
+use crate::{
+    component::{ComponentId, Components, StorageType},
+    prelude::Component,
+    query::FilteredAccess,
+};
+use smallvec::SmallVec;
+
+#[cfg(feature = "bevy_reflect")]
+use {crate::reflect::ReflectComponent, bevy_reflect::Reflect};
+
+/// A marker component for disabled entities.
+///
+/// Semantically, this component is used to mark entities that are temporarily disabled (typically for gameplay reasons),
+/// but will likely be re-enabled at some point.
+///
+/// Like all disabling components, this only disables the entity itself,
+/// not its children or other entities that reference it.
+///
+/// See [the module docs](crate::entity_disabling) for more info.
+#[derive(Component, Clone, Debug, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Component, Debug))]
+pub struct Disabled;
+
+/// The default filters for all queries made in a [`World`](crate::world::World).
+///
+/// Every component registered as a "disabling component" is added as a `Without<C>` filter to each query,
+/// unless the query explicitly mentions that component (through data like `&C`, `Option<&C>`, `Has<C>`,
+/// or filters like `With<C>` and `Without<C>`).
+///
+/// See [the module docs](crate::entity_disabling) for more info.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultQueryFilters {
+    disabling: SmallVec<[ComponentId; 4]>,
+}
+
+impl DefaultQueryFilters {
+    /// Creates a new, completely empty [`DefaultQueryFilters`].
+    ///
+    /// This is provided as an escape hatch; in most cases you should keep the filters
+    /// of the world and register additional disabling components instead.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Adds this [`ComponentId`] to the set of "disabling components".
+    ///
+    /// Entities with any disabling component are hidden from queries that do not mention it.
+    pub fn register_disabling_component(&mut self, component_id: ComponentId) {
+        if !self.disabling.contains(&component_id) {
+            self.disabling.push(component_id);
+        }
+    }
+
+    /// Removes this [`ComponentId`] from the set of "disabling components".
+    pub fn unregister_disabling_component(&mut self, component_id: ComponentId) {
+        self.disabling.retain(|id| *id != component_id);
+    }
+
+    /// Gets an iterator over all currently registered disabling components.
+    pub fn disabling_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.disabling.iter().copied()
+    }
+
+    /// Modifies the provided [`FilteredAccess`] to include the filters from this [`DefaultQueryFilters`].
+    pub(crate) fn modify_access(&self, component_access: &mut FilteredAccess<ComponentId>) {
+        for component_id in self.disabling_ids() {
+            if !component_access.contains(component_id) {
+                component_access.and_without(component_id);
+            }
+        }
+    }
+
+    /// Returns `false` if any disabling component is stored in sparse sets, as filtering those out
+    /// requires checking each entity instead of whole tables.
+    pub(crate) fn is_dense(&self, components: &Components) -> bool {
+        self.disabling_ids().all(|component_id| {
+            components
+                .get_info(component_id)
+                .is_some_and(|info| info.storage_type() == StorageType::Table)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entity::Entity,
+        prelude::{EntityRef, Has, Query, With, Without, World},
+        system::SystemState,
+    };
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct CustomDisabled;
+
+    #[test]
+    fn filters_modify_access() {
+        let mut filters = DefaultQueryFilters::empty();
+        filters.register_disabling_component(ComponentId::new(1));
+
+        // Adds the filter when the query does not mention the component.
+        let mut access = FilteredAccess::<ComponentId>::default();
+        filters.modify_access(&mut access);
+        assert_eq!(
+            access.without_filters().collect::<Vec<_>>(),
+            [ComponentId::new(1)]
+        );
+
+        // Keeps the query as-is when the component is explicitly mentioned.
+        let mut access = FilteredAccess::<ComponentId>::default();
+        access.and_with(ComponentId::new(1));
+        filters.modify_access(&mut access);
+        assert_eq!(access.without_filters().count(), 0);
+    }
+
+    #[test]
+    fn disabled_entities_are_hidden() {
+        let mut world = World::new();
+        let enabled = world.spawn(A).id();
+        let disabled = world.spawn((A, Disabled)).id();
+
+        let mut query = world.query::<Entity>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [enabled]);
+        assert!(world.query::<&A>().get(&world, disabled).is_err());
+
+        let mut query = world.query_filtered::<Entity, With<Disabled>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [disabled]);
+
+        let mut query = world.query::<(Entity, Has<Disabled>)>();
+        assert_eq!(query.iter(&world).count(), 2);
+
+        let mut query = world.query_filtered::<Entity, Without<Disabled>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [enabled]);
+
+        let mut query = world.query::<(Entity, Option<&Disabled>)>();
+        assert_eq!(query.iter(&world).count(), 2);
+
+        // Reading every component does not count as explicitly mentioning the disabling component.
+        let mut query = world.query::<EntityRef>();
+        assert_eq!(query.iter(&world).count(), 1);
+
+        world.entity_mut(disabled).remove::<Disabled>();
+        let mut query = world.query::<&A>();
+        assert_eq!(query.iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn systems_skip_disabled_entities() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn((A, Disabled));
+
+        let mut state = SystemState::<Query<&A>>::new(&mut world);
+        assert_eq!(state.get(&world).iter().count(), 1);
+    }
+
+    #[test]
+    fn custom_disabling_components() {
+        let mut world = World::new();
+        let custom = world.register_component::<CustomDisabled>();
+        world
+            .default_query_filters_mut()
+            .register_disabling_component(custom);
+        world.spawn(A);
+        world.spawn((A, CustomDisabled));
+        world.spawn((A, Disabled));
+
+        let mut query = world.query::<&A>();
+        assert_eq!(query.iter(&world).count(), 1);
+
+        let mut query = world.query_filtered::<&A, With<CustomDisabled>>();
+        assert_eq!(query.iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn empty_default_query_filters() {
+        let mut world = World::new();
+        *world.default_query_filters_mut() = DefaultQueryFilters::empty();
+        world.spawn(A);
+        world.spawn((A, Disabled));
+
+        let mut query = world.query::<&A>();
+        assert_eq!(query.iter(&world).count(), 2);
+    }
+}
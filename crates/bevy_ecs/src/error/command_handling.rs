@@ -0,0 +1,69 @@
+use core::any::type_name;
+
+use crate::{
+    error::{BevyError, ErrorContext, ErrorHandler, Never},
+    world::{Command, World},
+};
+
+/// Turns a [`Command`] that returns a [`Result`](crate::error::Result) into one that passes errors to an [`ErrorHandler`].
+///
+/// This is implemented for all commands. Commands that return `()` are left as they are, and so are
+/// closures that always panic.
+pub trait HandleError<Out = ()> {
+    /// Returns a [`Command`] that passes errors returned by this command to `error_handler`.
+    fn handle_error_with(self, error_handler: ErrorHandler) -> impl Command;
+
+    /// Returns a [`Command`] that passes errors returned by this command to the
+    /// [`DefaultErrorHandler`](crate::error::DefaultErrorHandler) of the world.
+    fn handle_error(self) -> impl Command;
+}
+
+impl<C, T, E> HandleError<Result<T, E>> for C
+where
+    C: Command<Result<T, E>>,
+    E: Into<BevyError>,
+{
+    fn handle_error_with(self, error_handler: ErrorHandler) -> impl Command {
+        move |world: &mut World| {
+            if let Err(error) = self.apply(world) {
+                error_handler(error.into(), command_context::<C>());
+            }
+        }
+    }
+
+    fn handle_error(self) -> impl Command {
+        move |world: &mut World| {
+            if let Err(error) = self.apply(world) {
+                (world.default_error_handler())(error.into(), command_context::<C>());
+            }
+        }
+    }
+}
+
+impl<C: Command> HandleError for C {
+    #[inline]
+    fn handle_error_with(self, _error_handler: ErrorHandler) -> impl Command {
+        self
+    }
+
+    #[inline]
+    fn handle_error(self) -> impl Command {
+        self
+    }
+}
+
+impl<C: Command<Never>> HandleError<Never> for C {
+    fn handle_error_with(self, _error_handler: ErrorHandler) -> impl Command {
+        move |world: &mut World| match self.apply(world) {}
+    }
+
+    fn handle_error(self) -> impl Command {
+        move |world: &mut World| match self.apply(world) {}
+    }
+}
+
+fn command_context<C>() -> ErrorContext {
+    ErrorContext::Command {
+        name: type_name::<C>().into(),
+    }
+}
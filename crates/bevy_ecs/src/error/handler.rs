@@ -0,0 +1,88 @@
+use alloc::borrow::Cow;
+use core::fmt::Display;
+
+use crate::{self as bevy_ecs, component::Tick, error::BevyError, system::Resource, world::World};
+
+/// Where an error handled by an [`ErrorHandler`] happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorContext {
+    /// The error was returned by a system.
+    System {
+        /// The name of the system.
+        name: Cow<'static, str>,
+        /// The tick of the system run that returned the error.
+        last_run: Tick,
+    },
+    /// The error was returned by a command.
+    Command {
+        /// The type name of the command.
+        name: Cow<'static, str>,
+    },
+}
+
+impl ErrorContext {
+    /// Returns the name of the system or command that returned the error.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::System { name, .. } | Self::Command { name } => name,
+        }
+    }
+
+    /// Returns a short description of where the error happened, like `"system"` or `"command"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::System { .. } => "system",
+            Self::Command { .. } => "command",
+        }
+    }
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} `{}`", self.kind(), self.name())
+    }
+}
+
+/// A function that handles an error returned by a system or command.
+///
+/// This module provides [`panic`], [`error`], [`warn`] and [`ignore`], but any function with this signature can be used.
+pub type ErrorHandler = fn(BevyError, ErrorContext);
+
+/// The [`ErrorHandler`] used for errors that are not handled by a more specific handler.
+///
+/// If this resource doesn't exist, errors [`panic`].
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct DefaultErrorHandler(pub ErrorHandler);
+
+impl Default for DefaultErrorHandler {
+    fn default() -> Self {
+        Self(panic)
+    }
+}
+
+impl World {
+    /// Returns the [`ErrorHandler`] of the [`DefaultErrorHandler`] resource, or [`panic`] if there is none.
+    pub fn default_error_handler(&self) -> ErrorHandler {
+        self.get_resource::<DefaultErrorHandler>()
+            .map_or(panic, |handler| handler.0)
+    }
+}
+
+/// Error handler that panics with the error.
+#[track_caller]
+pub fn panic(error: BevyError, context: ErrorContext) {
+    panic!("Encountered an error in {context}: {error}");
+}
+
+/// Error handler that logs the error at the error level.
+pub fn error(error: BevyError, context: ErrorContext) {
+    bevy_utils::tracing::error!("Encountered an error in {context}: {error}");
+}
+
+/// Error handler that logs the error at the warning level.
+pub fn warn(error: BevyError, context: ErrorContext) {
+    bevy_utils::tracing::warn!("Encountered an error in {context}: {error}");
+}
+
+/// Error handler that ignores the error.
+pub fn ignore(_: BevyError, _: ErrorContext) {}
@@ -0,0 +1,226 @@
+//! Error handling for fallible systems and commands.
+//!
+//! Systems added to a [`Schedule`] can return a [`Result`], and commands can return a [`Result`] from
+//! [`Command::apply`]. Errors are type-erased into a [`BevyError`], so the `?` operator works with any error type.
+//!
+//! Returned errors are passed to an [`ErrorHandler`], which decides what to do with them: [`panic`], log them with
+//! [`error`] or [`warn`], [`ignore`] them, or anything else a custom handler function does.
+//!
+//! For systems, the handler is picked in the following order:
+//! - the handler of the innermost system set containing the system, set with [`Schedule::set_error_handler_for_set`],
+//! - the handler of the schedule, set with [`Schedule::set_error_handler`],
+//! - the [`DefaultErrorHandler`] of the [`World`], which is [`panic`] if the resource doesn't exist.
+//!
+//! Commands use the [`DefaultErrorHandler`], unless they are queued with a specific handler using
+//! [`Commands::queue_handled`] or [`EntityCommands::queue_handled`].
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::error::{warn, DefaultErrorHandler, Result};
+//!
+//! #[derive(Resource)]
+//! struct Config(String);
+//!
+//! fn parse_config(config: Res<Config>) -> Result {
+//!     let value: u32 = config.0.parse()?;
+//!     assert!(value > 0);
+//!     Ok(())
+//! }
+//!
+//! let mut world = World::new();
+//! // Log errors instead of panicking.
+//! world.insert_resource(DefaultErrorHandler(warn));
+//! world.insert_resource(Config("not a number".into()));
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(parse_config);
+//! schedule.run(&mut world);
+//! ```
+//!
+//! [`Schedule`]: crate::schedule::Schedule
+//! [`Schedule::set_error_handler`]: crate::schedule::Schedule::set_error_handler
+//! [`Schedule::set_error_handler_for_set`]: crate::schedule::Schedule::set_error_handler_for_set
+//! [`Command::apply`]: crate::world::Command::apply
+//! [`World`]: crate::world::World
+//! [`Commands::queue_handled`]: crate::system::Commands::queue_handled
+//! [`EntityCommands::queue_handled`]: crate::system::EntityCommands::queue_handled
+
+mod command_handling;
+mod handler;
+
+pub use command_handling::*;
+pub use handler::*;
+
+/// A type-erased error, which any error type can be converted into with the `?` operator.
+pub type BevyError = Box<dyn core::error::Error + Send + Sync + 'static>;
+
+/// A result type for fallible systems and commands, defaulting to `Result<(), BevyError>`.
+pub type Result<T = (), E = BevyError> = core::result::Result<T, E>;
+
+/// The never type `!`, which can't be named in a type position on stable Rust.
+///
+/// A closure that always panics, like `|| panic!()`, returns `!`, which falls back to `()`. When the
+/// closure could be either a system or command returning `()` or a fallible one returning a [`Result`],
+/// relying on that fallback is an error since the 2024 edition, so `!` is accepted as well.
+pub(crate) type Never = <fn() -> ! as FnReturn>::Output;
+
+/// Names the return type of a function pointer, see [`Never`].
+pub(crate) trait FnReturn {
+    type Output;
+}
+
+impl<T> FnReturn for fn() -> T {
+    type Output = T;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{
+        self as bevy_ecs,
+        error::{ignore, BevyError, DefaultErrorHandler, ErrorContext, Result},
+        prelude::*,
+        schedule::ExecutorKind,
+        world::CommandQueue,
+    };
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Outer;
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Inner;
+
+    #[derive(Component)]
+    struct A;
+
+    // Error handlers are plain functions, so they record the errors in a static.
+    // Each test uses its own static, since tests run in parallel.
+    macro_rules! recording_handler {
+        ($log:ident, $handler:ident, $tag:literal) => {
+            fn $handler(error: BevyError, context: ErrorContext) {
+                $log.lock()
+                    .unwrap()
+                    .push(format!("{} {}: {error}", $tag, context.kind()));
+            }
+        };
+    }
+
+    fn failing_system() -> Result {
+        Err("failed".into())
+    }
+
+    #[test]
+    fn fallible_system_uses_default_error_handler() {
+        static LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        recording_handler!(LOG, handler, "default");
+
+        for executor in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::new();
+            world.insert_resource(DefaultErrorHandler(handler));
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            schedule.add_systems((failing_system, || {}, |_: &mut World| -> Result { Ok(()) }));
+            schedule.run(&mut world);
+        }
+
+        assert_eq!(*LOG.lock().unwrap(), vec!["default system: failed"; 3]);
+    }
+
+    #[test]
+    fn error_handler_precedence() {
+        static LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        recording_handler!(LOG, default_handler, "default");
+        recording_handler!(LOG, schedule_handler, "schedule");
+        recording_handler!(LOG, outer_handler, "outer");
+        recording_handler!(LOG, inner_handler, "inner");
+
+        let mut world = World::new();
+        world.insert_resource(DefaultErrorHandler(default_handler));
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        schedule.configure_sets(Inner.in_set(Outer));
+        schedule.add_systems(
+            (
+                failing_system,
+                failing_system.in_set(Outer),
+                failing_system.in_set(Inner),
+            )
+                .chain(),
+        );
+        schedule.run(&mut world);
+
+        schedule.set_error_handler(schedule_handler);
+        schedule.run(&mut world);
+
+        schedule.set_error_handler_for_set(Outer, outer_handler);
+        schedule.run(&mut world);
+
+        schedule.set_error_handler_for_set(Inner, inner_handler);
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *LOG.lock().unwrap(),
+            [
+                "default", "default", "default", "schedule", "schedule", "schedule", "schedule",
+                "outer", "outer", "schedule", "outer", "inner",
+            ]
+            .map(|tag| format!("{tag} system: failed"))
+        );
+    }
+
+    #[test]
+    fn command_error_handling() {
+        static LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        recording_handler!(LOG, handler, "default");
+
+        let mut world = World::new();
+        world.insert_resource(DefaultErrorHandler(handler));
+        let entity = world.spawn_empty().id();
+
+        let mut commands = world.commands();
+        // The entity is despawned before the other commands are applied.
+        commands.entity(entity).despawn();
+        commands.queue(|_: &mut World| -> Result { Err("failed".into()) });
+        commands.queue_handled(|_: &mut World| -> Result { Err("ignored".into()) }, ignore);
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(A);
+        entity_commands.queue_handled(
+            |mut entity: EntityWorldMut| {
+                entity.insert(A);
+            },
+            ignore,
+        );
+        // `try_despawn` ignores missing entities.
+        entity_commands.try_despawn();
+        world.flush();
+
+        let log = LOG.lock().unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0], "default command: failed");
+        assert!(log[1].starts_with("default command: error[B0003]"));
+    }
+
+    #[test]
+    fn diverging_closures() {
+        // Closures that always panic are inferred as returning `()`, without annotations.
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((|| panic!("not run")).run_if(|| false));
+        schedule.run(&mut world);
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.queue(|_: &mut World| panic!("not run"));
+        commands
+            .entity(entity)
+            .queue(|_: EntityWorldMut| panic!("not run"))
+            .queue(|_: Entity, _: &mut World| panic!("not run"));
+        assert!(!queue.is_empty());
+    }
+}
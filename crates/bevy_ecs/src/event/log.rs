@@ -0,0 +1,457 @@
+//! Recording of events into an [`EventLog`], and replaying them with an [`EventReplay`].
+//!
+//! [`Events`](super::Events) are dropped after two updates, so they can't be used to reproduce what happened
+//! during a session. An [`EventLog`] instead keeps every event of the types recorded by [`record_events`],
+//! along with the frame they were sent on. The log can then be replayed into another world, for example to
+//! reproduce a bug from the input events captured by a tester:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_reflect::Reflect;
+//! use bevy_ecs::event::{
+//!     advance_event_log, advance_event_replay, record_events, replay_events, EventLog, EventReplay,
+//! };
+//!
+//! #[derive(Event, Reflect, Clone, Debug, PartialEq)]
+//! struct Jump {
+//!     height: f32,
+//! }
+//!
+//! // Record the events of a session.
+//! let mut world = World::new();
+//! world.init_resource::<Events<Jump>>();
+//! world.init_resource::<EventLog>();
+//! let mut frame = Schedule::default();
+//! frame.add_systems((record_events::<Jump>, advance_event_log).chain());
+//!
+//! frame.run(&mut world);
+//! world.send_event(Jump { height: 2. });
+//! frame.run(&mut world);
+//!
+//! let log = world.remove_resource::<EventLog>().unwrap();
+//! assert_eq!(log.events()[0].frame, 1);
+//!
+//! // Replay them into a new world, on the same frames.
+//! let mut world = World::new();
+//! world.init_resource::<Events<Jump>>();
+//! world.insert_resource(EventReplay::new(log));
+//! let mut frame = Schedule::default();
+//! frame.add_systems((replay_events::<Jump>, advance_event_replay).chain());
+//!
+//! frame.run(&mut world);
+//! assert!(world.resource::<Events<Jump>>().is_empty());
+//! frame.run(&mut world);
+//! let jumps: Vec<_> = world.resource_mut::<Events<Jump>>().drain().collect();
+//! assert_eq!(jumps, [Jump { height: 2. }]);
+//! ```
+//!
+//! Apps should use `App::record_events` and `App::replay_events` instead, which schedule these systems so
+//! that events are replayed at the start of the frame they were recorded on.
+//!
+//! With the `serialize` feature, [`EventLogSerializer`] and [`EventLogDeserializer`] save and load a log
+//! with the reflection serializer, so the recorded event types must be registered in the
+//! [`TypeRegistry`](bevy_reflect::TypeRegistry).
+
+use alloc::{boxed::Box, vec::Vec};
+use core::any::TypeId;
+
+use bevy_reflect::{FromReflect, PartialReflect};
+
+use crate::{
+    self as bevy_ecs,
+    component::Tick,
+    event::{Event, EventReader, EventWriter},
+    system::{Res, ResMut, Resource, SystemChangeTick},
+};
+
+/// An event stored in an [`EventLog`].
+#[derive(Debug)]
+pub struct LoggedEvent {
+    /// The frame the event was recorded on, counted by [`advance_event_log`].
+    pub frame: u32,
+    /// The change tick of the world when the event was recorded.
+    pub tick: Tick,
+    /// The value of the event.
+    pub event: Box<dyn PartialReflect>,
+}
+
+impl LoggedEvent {
+    /// Returns `true` if the event is of type `E`.
+    pub fn is<E: 'static>(&self) -> bool {
+        self.event
+            .get_represented_type_info()
+            .is_some_and(|info| info.type_id() == TypeId::of::<E>())
+    }
+}
+
+/// A [`Resource`] storing the events recorded by [`record_events`], in the order they were sent.
+///
+/// See the [module docs](crate::event::log) for more information.
+#[derive(Resource, Debug, Default)]
+pub struct EventLog {
+    frame: u32,
+    events: Vec<LoggedEvent>,
+}
+
+impl EventLog {
+    /// Returns the current frame of the log, which is the number of times [`advance_event_log`] ran.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Returns the recorded events, in the order they were sent.
+    pub fn events(&self) -> &[LoggedEvent] {
+        &self.events
+    }
+
+    /// Records `event` on the current frame.
+    pub fn push(&mut self, event: &dyn PartialReflect, tick: Tick) {
+        self.events.push(LoggedEvent {
+            frame: self.frame,
+            tick,
+            event: event.clone_value(),
+        });
+    }
+
+    /// Removes all recorded events, without resetting the current frame.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Advances the log to the next frame.
+    pub fn advance_frame(&mut self) {
+        self.frame += 1;
+    }
+}
+
+/// Records every event of type `E` sent since the last run of this system into the [`EventLog`].
+///
+/// This system should run once per frame, before [`advance_event_log`].
+pub fn record_events<E: Event + PartialReflect>(
+    mut events: EventReader<E>,
+    mut log: ResMut<EventLog>,
+    tick: SystemChangeTick,
+) {
+    for event in events.read() {
+        log.push(event, tick.this_run());
+    }
+}
+
+/// Advances the [`EventLog`] to the next frame.
+pub fn advance_event_log(mut log: ResMut<EventLog>) {
+    log.advance_frame();
+}
+
+/// A [`Resource`] that sends the events of an [`EventLog`] again, on the frames they were recorded on.
+///
+/// See the [module docs](crate::event::log) for more information.
+#[derive(Resource, Debug)]
+pub struct EventReplay {
+    frame: u32,
+    events: Vec<LoggedEvent>,
+}
+
+impl EventReplay {
+    /// Creates a replay of the events of `log`, starting on frame 0.
+    pub fn new(log: EventLog) -> Self {
+        let mut events = log.events;
+        // Keeps the order of the events of each frame.
+        events.sort_by_key(|event| event.frame);
+        Self { frame: 0, events }
+    }
+
+    /// Returns the current frame of the replay, which is the number of times [`advance_event_replay`] ran.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Returns the events of the current frame.
+    pub fn current_events(&self) -> &[LoggedEvent] {
+        let start = self
+            .events
+            .partition_point(|event| event.frame < self.frame);
+        let end = self
+            .events
+            .partition_point(|event| event.frame <= self.frame);
+        &self.events[start..end]
+    }
+
+    /// Returns `true` if all the events have been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.events
+            .last()
+            .map_or(true, |event| event.frame < self.frame)
+    }
+
+    /// Advances the replay to the next frame.
+    pub fn advance_frame(&mut self) {
+        self.frame += 1;
+    }
+}
+
+/// Sends the events of type `E` of the current frame of the [`EventReplay`].
+///
+/// Does nothing if there is no [`EventReplay`]. Events that can't be converted to `E` with
+/// [`FromReflect`] are skipped.
+pub fn replay_events<E: Event + FromReflect>(
+    replay: Option<Res<EventReplay>>,
+    mut events: EventWriter<E>,
+) {
+    let Some(replay) = replay else {
+        return;
+    };
+    events.send_batch(
+        replay
+            .current_events()
+            .iter()
+            .filter(|event| event.is::<E>())
+            .filter_map(|event| E::from_reflect(event.event.as_ref())),
+    );
+}
+
+/// Advances the [`EventReplay`] to the next frame.
+pub fn advance_event_replay(mut replay: ResMut<EventReplay>) {
+    replay.advance_frame();
+}
+
+#[cfg(feature = "serialize")]
+pub use serialize::*;
+
+#[cfg(feature = "serialize")]
+mod serialize {
+    use core::fmt;
+
+    use bevy_reflect::{
+        serde::{ReflectDeserializer, ReflectSerializer},
+        TypeRegistry,
+    };
+    use serde::{
+        de::{DeserializeSeed, Error, SeqAccess, Visitor},
+        ser::{SerializeSeq, SerializeTuple},
+        Deserializer, Serialize, Serializer,
+    };
+
+    use super::{EventLog, LoggedEvent};
+    use crate::component::Tick;
+
+    /// Serializes an [`EventLog`] as a sequence of `(frame, tick, event)` tuples, using the
+    /// [`ReflectSerializer`] for the events.
+    pub struct EventLogSerializer<'a> {
+        log: &'a EventLog,
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'a> EventLogSerializer<'a> {
+        /// Creates a serializer of `log`, whose event types are registered in `registry`.
+        pub fn new(log: &'a EventLog, registry: &'a TypeRegistry) -> Self {
+            Self { log, registry }
+        }
+    }
+
+    impl Serialize for EventLogSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.log.events.len()))?;
+            for event in &self.log.events {
+                seq.serialize_element(&LoggedEventSerializer {
+                    event,
+                    registry: self.registry,
+                })?;
+            }
+            seq.end()
+        }
+    }
+
+    struct LoggedEventSerializer<'a> {
+        event: &'a LoggedEvent,
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for LoggedEventSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut tuple = serializer.serialize_tuple(3)?;
+            tuple.serialize_element(&self.event.frame)?;
+            tuple.serialize_element(&self.event.tick.get())?;
+            tuple.serialize_element(&ReflectSerializer::new(
+                self.event.event.as_ref(),
+                self.registry,
+            ))?;
+            tuple.end()
+        }
+    }
+
+    /// Deserializes an [`EventLog`] serialized with [`EventLogSerializer`].
+    ///
+    /// The frame of the deserialized log is the one after the frame of its last event, so that events
+    /// recorded into it afterwards don't share a frame with the deserialized ones.
+    pub struct EventLogDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'a> EventLogDeserializer<'a> {
+        /// Creates a deserializer of logs whose event types are registered in `registry`.
+        pub fn new(registry: &'a TypeRegistry) -> Self {
+            Self { registry }
+        }
+    }
+
+    impl<'de> DeserializeSeed<'de> for EventLogDeserializer<'_> {
+        type Value = EventLog;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<EventLog, D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for EventLogDeserializer<'_> {
+        type Value = EventLog;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a sequence of logged events")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<EventLog, A::Error> {
+            let mut log = EventLog::default();
+            while let Some(event) = seq.next_element_seed(LoggedEventDeserializer {
+                registry: self.registry,
+            })? {
+                log.frame = log.frame.max(event.frame.saturating_add(1));
+                log.events.push(event);
+            }
+            Ok(log)
+        }
+    }
+
+    struct LoggedEventDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'de> DeserializeSeed<'de> for LoggedEventDeserializer<'_> {
+        type Value = LoggedEvent;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<LoggedEvent, D::Error> {
+            deserializer.deserialize_tuple(3, self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for LoggedEventDeserializer<'_> {
+        type Value = LoggedEvent;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a (frame, tick, event) tuple")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<LoggedEvent, A::Error> {
+            let frame = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(0, &self))?;
+            let tick: u32 = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(1, &self))?;
+            let event = seq
+                .next_element_seed(ReflectDeserializer::new(self.registry))?
+                .ok_or_else(|| Error::invalid_length(2, &self))?;
+            Ok(LoggedEvent {
+                frame,
+                tick: Tick::new(tick),
+                event,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        advance_event_log, advance_event_replay, record_events, replay_events, EventLog,
+        EventReplay,
+    };
+    use crate::{self as bevy_ecs, prelude::*};
+    use alloc::vec::Vec;
+    use bevy_reflect::Reflect;
+
+    #[derive(Event, Reflect, Clone, Debug, PartialEq)]
+    enum Input {
+        Press(u32),
+        Release(u32),
+    }
+
+    #[derive(Event, Reflect, Clone, Debug, PartialEq)]
+    struct Other;
+
+    fn record(frames: &[&[Input]]) -> EventLog {
+        let mut world = World::new();
+        world.init_resource::<Events<Input>>();
+        world.init_resource::<Events<Other>>();
+        world.init_resource::<EventLog>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                record_events::<Input>,
+                record_events::<Other>,
+                advance_event_log,
+            )
+                .chain(),
+        );
+        for &events in frames {
+            world.send_event_batch(events.iter().cloned());
+            world.send_event(Other);
+            schedule.run(&mut world);
+            world.resource_mut::<Events<Input>>().update();
+        }
+        world.remove_resource::<EventLog>().unwrap()
+    }
+
+    fn replay(log: EventLog, frames: usize) -> Vec<Vec<Input>> {
+        let mut world = World::new();
+        world.init_resource::<Events<Input>>();
+        world.insert_resource(EventReplay::new(log));
+        let mut schedule = Schedule::default();
+        schedule.add_systems((replay_events::<Input>, advance_event_replay).chain());
+        let replayed = (0..frames)
+            .map(|_| {
+                schedule.run(&mut world);
+                world.resource_mut::<Events<Input>>().drain().collect()
+            })
+            .collect();
+        assert!(world.resource::<EventReplay>().is_finished());
+        replayed
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let frames: &[&[Input]] = &[
+            &[Input::Press(1)],
+            &[],
+            &[Input::Release(1), Input::Press(2)],
+        ];
+        let log = record(frames);
+        assert_eq!(log.frame(), 3);
+        assert_eq!(log.events().len(), 6);
+        assert_eq!(replay(log, 3), frames);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn serialize_event_log() {
+        use super::{EventLogDeserializer, EventLogSerializer};
+        use bevy_reflect::TypeRegistry;
+        use serde::de::DeserializeSeed;
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Input>();
+        registry.register::<Other>();
+        let frames: &[&[Input]] = &[&[Input::Press(1)], &[Input::Release(1)]];
+        let log = record(frames);
+
+        let serialized = ron::to_string(&EventLogSerializer::new(&log, &registry)).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let log = EventLogDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(log.frame(), 2);
+        assert_eq!(replay(log, 2), frames);
+    }
+}
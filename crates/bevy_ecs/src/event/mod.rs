@@ -3,6 +3,8 @@ mod base;
 mod collections;
 mod event_cursor;
 mod iterators;
+#[cfg(feature = "bevy_reflect")]
+pub mod log;
 mod mut_iterators;
 mod mutator;
 mod reader;
@@ -19,6 +21,11 @@ pub use event_cursor::EventCursor;
 #[cfg(feature = "multi_threaded")]
 pub use iterators::EventParIter;
 pub use iterators::{EventIterator, EventIteratorWithId};
+#[cfg(feature = "bevy_reflect")]
+pub use log::{
+    advance_event_log, advance_event_replay, record_events, replay_events, EventLog, EventReplay,
+    LoggedEvent,
+};
 #[cfg(feature = "multi_threaded")]
 pub use mut_iterators::EventMutParIter;
 pub use mut_iterators::{EventMutIterator, EventMutIteratorWithId};
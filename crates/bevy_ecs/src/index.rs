@@ -0,0 +1,308 @@
+//! Secondary indexes, which look up entities by the value of one of their components.
+//!
+//! Add `#[component(index)]` to a component that implements [`Hash`], [`Eq`] and [`Clone`] to maintain a
+//! [`ComponentIndex`] for it, and use the [`Index`] system parameter to look up entities by value:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::index::Index;
+//!
+//! #[derive(Component, Hash, PartialEq, Eq, Clone, Debug)]
+//! #[component(index)]
+//! struct NetworkId(u64);
+//!
+//! #[derive(Resource)]
+//! struct Received(NetworkId);
+//!
+//! fn apply_update(index: Index<NetworkId>, received: Res<Received>) {
+//!     if let Some(entity) = index.get_single(&received.0) {
+//!         println!("Received an update for {entity:?}");
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(apply_update);
+//! ```
+//!
+//! The index is kept up to date with component hooks when the component is inserted, replaced or removed,
+//! and when the entity is despawned. Changes made in place, through a [`Mut`](crate::change_detection::Mut),
+//! are picked up by the [`Index`] system parameter before the system runs.
+
+use core::hash::Hash;
+
+use bevy_utils::HashMap;
+
+use crate::{
+    self as bevy_ecs,
+    archetype::Archetype,
+    change_detection::DetectChangesMut,
+    component::{Component, ComponentId, Tick},
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    query::Changed,
+    system::{Query, ResMut, Resource, SystemMeta, SystemParam},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, FromWorld, World},
+};
+
+/// A [`Component`] whose values are indexed in a [`ComponentIndex`].
+///
+/// This trait is implemented by adding `#[component(index)]` to the [`Component`] derive, which also
+/// registers the hooks that keep the index up to date.
+pub trait IndexedComponent: Component + Hash + Eq + Clone {
+    /// The `on_insert` component hook that adds the new value to the index.
+    fn on_insert(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let value = world.entity(entity).get::<Self>().unwrap().clone();
+        match world.get_resource_mut::<ComponentIndex<Self>>() {
+            Some(mut index) => index.insert(entity, value),
+            // The index is built from all existing entities when it is created.
+            None => world.commands().init_resource::<ComponentIndex<Self>>(),
+        }
+    }
+
+    /// The `on_replace` component hook that removes the old value from the index.
+    fn on_replace(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        if let Some(mut index) = world.get_resource_mut::<ComponentIndex<Self>>() {
+            index.remove(entity);
+        }
+    }
+}
+
+/// A [`Resource`] mapping the values of the indexed component `C` to the entities that have them.
+///
+/// Changes made in place through a [`Mut`](crate::change_detection::Mut) are only reflected once a system
+/// with an [`Index<C>`] parameter runs. Prefer [`Index`] in systems.
+#[derive(Resource)]
+pub struct ComponentIndex<C: IndexedComponent> {
+    entities: HashMap<C, EntityHashSet>,
+    values: EntityHashMap<C>,
+}
+
+impl<C: IndexedComponent> ComponentIndex<C> {
+    /// Returns the entities whose component is equal to `value`.
+    pub fn get(&self, value: &C) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.get(value).into_iter().flatten().copied()
+    }
+
+    /// Returns the entity whose component is equal to `value`, or `None` if there are none or several of them.
+    pub fn get_single(&self, value: &C) -> Option<Entity> {
+        let entities = self.entities.get(value)?;
+        let mut iter = entities.iter();
+        match (iter.next(), iter.next()) {
+            (Some(&entity), None) => Some(entity),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if any entity has a component equal to `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns the indexed value of `entity`, if it has the component.
+    pub fn value(&self, entity: Entity) -> Option<&C> {
+        self.values.get(&entity)
+    }
+
+    /// Returns an iterator over the distinct values in the index.
+    pub fn values(&self) -> impl Iterator<Item = &C> + '_ {
+        self.entities.keys()
+    }
+
+    /// Returns the number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no entity is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn insert(&mut self, entity: Entity, value: C) {
+        self.remove(entity);
+        self.entities
+            .entry(value.clone())
+            .or_default()
+            .insert(entity);
+        self.values.insert(entity, value);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let Some(value) = self.values.remove(&entity) else {
+            return;
+        };
+        if let Some(entities) = self.entities.get_mut(&value) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                self.entities.remove(&value);
+            }
+        }
+    }
+
+    /// Updates the index of `entity` if its value changed. Returns `true` if it did.
+    fn update(&mut self, entity: Entity, value: &C) -> bool {
+        if self.values.get(&entity) == Some(value) {
+            return false;
+        }
+        self.insert(entity, value.clone());
+        true
+    }
+}
+
+impl<C: IndexedComponent> FromWorld for ComponentIndex<C> {
+    fn from_world(world: &mut World) -> Self {
+        let mut index = Self {
+            entities: HashMap::default(),
+            values: EntityHashMap::default(),
+        };
+        let Some(id) = world.component_id::<C>() else {
+            return index;
+        };
+        // Disabled entities are indexed too, so the archetypes are scanned instead of using a query.
+        for archetype in world.archetypes().iter().filter(|a| a.contains(id)) {
+            for entity in archetype.entities() {
+                let entity = entity.id();
+                index.insert(entity, world.get::<C>(entity).unwrap().clone());
+            }
+        }
+        index
+    }
+}
+
+type IndexParam<C> = (
+    ResMut<'static, ComponentIndex<C>>,
+    Query<'static, 'static, (Entity, &'static C), Changed<C>>,
+);
+
+/// A [`SystemParam`] to look up entities by the value of the indexed component `C`.
+///
+/// `C` must be indexed with `#[component(index)]`. Before the system runs, values changed in place since
+/// its last run are updated in the [`ComponentIndex`]. This needs to check every entity with `C`, like
+/// a query with a [`Changed`] filter.
+///
+/// Changes made in place to entities hidden by [default query filters](crate::entity_disabling),
+/// like disabled entities, are only picked up if the component is changed again after the entity is re-enabled.
+pub struct Index<'w, C: IndexedComponent> {
+    index: ResMut<'w, ComponentIndex<C>>,
+}
+
+impl<'w, C: IndexedComponent> core::ops::Deref for Index<'w, C> {
+    type Target = ComponentIndex<C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+// SAFETY: The access of `ResMut<ComponentIndex<C>>` and `Query<(Entity, &C), Changed<C>>` is registered
+// in `init_state`, and `Index` only uses that access.
+unsafe impl<C: IndexedComponent> SystemParam for Index<'_, C> {
+    type State = <IndexParam<C> as SystemParam>::State;
+    type Item<'w, 's> = Index<'w, C>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        world.init_resource::<ComponentIndex<C>>();
+        <IndexParam<C>>::init_state(world, system_meta)
+    }
+
+    unsafe fn new_archetype(
+        state: &mut Self::State,
+        archetype: &Archetype,
+        system_meta: &mut SystemMeta,
+    ) {
+        // SAFETY: The caller ensures that `archetype` is from the world the state was initialized from.
+        unsafe { <IndexParam<C>>::new_archetype(state, archetype, system_meta) };
+    }
+
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Delegated to the inner parameters, with the same invariants.
+        unsafe { <IndexParam<C>>::validate_param(state, system_meta, world) }
+    }
+
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: Delegated to the inner parameters, with the same invariants.
+        let (mut index, changed) =
+            unsafe { <IndexParam<C>>::get_param(state, system_meta, world, change_tick) };
+        let mut updated = false;
+        for (entity, value) in &changed {
+            updated |= index.bypass_change_detection().update(entity, value);
+        }
+        if updated {
+            index.set_changed();
+        }
+        Index { index }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        entity_disabling::Disabled,
+        index::{ComponentIndex, Index},
+        prelude::*,
+        system::RunSystemOnce,
+    };
+
+    #[derive(Component, Hash, PartialEq, Eq, Clone, Copy, Debug)]
+    #[component(index)]
+    struct NetworkId(u64);
+
+    fn lookup(world: &mut World, id: u64) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = world
+            .run_system_once(move |index: Index<NetworkId>| index.get(&NetworkId(id)).collect())
+            .unwrap();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn index_structural_changes() {
+        let mut world = World::new();
+        let a = world.spawn(NetworkId(1)).id();
+        let b = world.spawn((NetworkId(1), Disabled)).id();
+        let c = world.spawn(NetworkId(2)).id();
+        assert_eq!(lookup(&mut world, 1), [a, b]);
+
+        world.entity_mut(a).insert(NetworkId(2));
+        world.entity_mut(b).remove::<NetworkId>();
+        assert_eq!(lookup(&mut world, 1), []);
+        assert_eq!(lookup(&mut world, 2), [a, c]);
+
+        world.despawn(c);
+        assert_eq!(lookup(&mut world, 2), [a]);
+        assert_eq!(world.resource::<ComponentIndex<NetworkId>>().len(), 1);
+    }
+
+    #[test]
+    fn index_in_place_changes() {
+        let mut world = World::new();
+        let a = world.spawn(NetworkId(1)).id();
+        let b = world.spawn(NetworkId(2)).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                |mut ids: Query<&mut NetworkId>| {
+                    for mut id in &mut ids {
+                        id.0 += 10;
+                    }
+                },
+                move |index: Index<NetworkId>| {
+                    assert_eq!(index.get_single(&NetworkId(11)), Some(a));
+                    assert_eq!(index.get_single(&NetworkId(12)), Some(b));
+                    assert!(!index.contains(&NetworkId(1)));
+                    assert_eq!(index.value(a), Some(&NetworkId(11)));
+                },
+            )
+                .chain(),
+        );
+        schedule.run(&mut world);
+    }
+}
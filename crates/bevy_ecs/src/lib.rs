@@ -16,19 +16,25 @@ compile_error!("bevy_ecs cannot safely compile for a 16-bit platform.");
 extern crate alloc;
 
 pub mod archetype;
+pub mod async_task;
 pub mod batching;
 pub mod bundle;
 pub mod change_detection;
 pub mod component;
 pub mod entity;
+pub mod entity_disabling;
+pub mod error;
 pub mod event;
 pub mod identifier;
+pub mod index;
 pub mod intern;
 pub mod label;
+pub mod memory_stats;
 pub mod observer;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
+pub mod relationship;
 pub mod removal_detection;
 pub mod schedule;
 pub mod storage;
@@ -51,6 +57,7 @@ pub mod prelude {
         event::{Event, EventMutator, EventReader, EventWriter, Events},
         observer::{CloneEntityWithObserversExt, Observer, Trigger},
         query::{Added, AnyOf, Changed, Has, Or, QueryBuilder, QueryState, With, Without},
+        relationship::RelationshipTarget,
         removal_detection::RemovedComponents,
         schedule::{
             apply_deferred, common_conditions::*, Condition, IntoSystemConfigs, IntoSystemSet,
@@ -87,6 +94,7 @@ mod tests {
         change_detection::Ref,
         component::{require, Component, ComponentId, RequiredComponents, RequiredComponentsError},
         entity::Entity,
+        entity_disabling::Disabled,
         prelude::Or,
         query::{Added, Changed, FilteredAccess, QueryFilter, With, Without},
         system::Resource,
@@ -1450,6 +1458,9 @@ mod tests {
         let b_id = world.components.get_id(TypeId::of::<B>()).unwrap();
         expected.add_component_write(a_id);
         expected.add_component_read(b_id);
+        // Disabled entities are filtered out by default.
+        let disabled_id = world.components.get_id(TypeId::of::<Disabled>()).unwrap();
+        expected.and_without(disabled_id);
         assert!(
             query.component_access.eq(&expected),
             "ComponentId access from query fetch and query filter should be combined"
@@ -0,0 +1,324 @@
+//! Statistics about the memory used to store components, to diagnose archetype fragmentation.
+//!
+//! [`World::memory_stats`] collects a [`MemoryStats`] snapshot of every [`Archetype`](crate::archetype::Archetype),
+//! [`Table`](crate::storage::Table) and [`ComponentSparseSet`](crate::storage::ComponentSparseSet) of a world:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! #[derive(Component)]
+//! struct Position(f32, f32);
+//!
+//! #[derive(Component)]
+//! struct Stunned;
+//!
+//! let mut world = World::new();
+//! world.spawn(Position(0., 0.));
+//! world.spawn((Position(1., 0.), Stunned));
+//!
+//! let stats = world.memory_stats();
+//! for archetype in &stats.archetypes {
+//!     println!(
+//!         "{:?}: {} entities, {} archetypes differing by a single component",
+//!         archetype.id, archetype.entity_count, archetype.single_component_neighbors,
+//!     );
+//! }
+//! println!("{} bytes of components, {} spare", stats.component_bytes(), stats.spare_bytes());
+//! ```
+//!
+//! Only the component values are counted. The change detection ticks stored next to each value, and the
+//! metadata of archetypes and tables, are not.
+//!
+//! Collecting the statistics iterates over every archetype, table and sparse set, so it should not be done
+//! every frame for large worlds.
+
+use alloc::vec::Vec;
+
+use bevy_utils::HashMap;
+
+use crate::{archetype::ArchetypeId, component::ComponentId, storage::TableId, world::World};
+
+/// A snapshot of the memory used by the components of a [`World`].
+///
+/// See the [module docs](crate::memory_stats) for more information.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStats {
+    /// The statistics of each archetype, in [`ArchetypeId`] order.
+    pub archetypes: Vec<ArchetypeStats>,
+    /// The statistics of each table, in [`TableId`] order.
+    pub tables: Vec<TableStats>,
+    /// The statistics of the sparse set of each [`StorageType::SparseSet`](crate::component::StorageType::SparseSet) component.
+    pub sparse_sets: Vec<SparseSetStats>,
+}
+
+impl MemoryStats {
+    /// Returns the number of bytes used by component values, in tables and sparse sets.
+    pub fn component_bytes(&self) -> usize {
+        self.tables.iter().map(TableStats::bytes).sum::<usize>()
+            + self.sparse_sets.iter().map(|s| s.bytes).sum::<usize>()
+    }
+
+    /// Returns the number of bytes allocated for component values but not used, in tables and sparse sets.
+    pub fn spare_bytes(&self) -> usize {
+        self.tables
+            .iter()
+            .map(TableStats::spare_bytes)
+            .sum::<usize>()
+            + self
+                .sparse_sets
+                .iter()
+                .map(|s| s.spare_bytes)
+                .sum::<usize>()
+    }
+
+    /// Returns the number of archetypes without any entity.
+    ///
+    /// Archetypes are never removed, so these are often left behind by components that are added and removed
+    /// frequently, like markers.
+    pub fn empty_archetype_count(&self) -> usize {
+        self.archetypes
+            .iter()
+            .filter(|archetype| archetype.entity_count == 0)
+            .count()
+    }
+}
+
+/// The statistics of an [`Archetype`](crate::archetype::Archetype).
+#[derive(Debug, Clone)]
+pub struct ArchetypeStats {
+    /// The id of the archetype.
+    pub id: ArchetypeId,
+    /// The table storing the table components of the archetype.
+    pub table_id: TableId,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The number of components in the archetype.
+    pub component_count: usize,
+    /// The number of other archetypes that have exactly one more or one less component than this archetype.
+    ///
+    /// A high count means that entities move between archetypes often, by adding and removing single components.
+    pub single_component_neighbors: usize,
+}
+
+/// The statistics of a [`Table`](crate::storage::Table).
+#[derive(Debug, Clone)]
+pub struct TableStats {
+    /// The id of the table.
+    pub id: TableId,
+    /// The number of entities in the table.
+    pub entity_count: usize,
+    /// The number of entities the table can store without reallocating.
+    pub entity_capacity: usize,
+    /// The statistics of each column of the table.
+    pub columns: Vec<ColumnStats>,
+}
+
+impl TableStats {
+    /// Returns the number of bytes used by the component values of the table.
+    pub fn bytes(&self) -> usize {
+        self.columns.iter().map(|column| column.bytes).sum()
+    }
+
+    /// Returns the number of bytes allocated for component values but not used by the table.
+    pub fn spare_bytes(&self) -> usize {
+        self.columns.iter().map(|column| column.spare_bytes).sum()
+    }
+}
+
+/// The statistics of a column of a [`Table`](crate::storage::Table).
+#[derive(Debug, Clone)]
+pub struct ColumnStats {
+    /// The component stored in the column.
+    pub component_id: ComponentId,
+    /// The size of a component value, in bytes.
+    pub item_size: usize,
+    /// The number of bytes used by the component values.
+    pub bytes: usize,
+    /// The number of bytes allocated for component values but not used.
+    pub spare_bytes: usize,
+}
+
+/// The statistics of a [`ComponentSparseSet`](crate::storage::ComponentSparseSet).
+#[derive(Debug, Clone)]
+pub struct SparseSetStats {
+    /// The component stored in the sparse set.
+    pub component_id: ComponentId,
+    /// The number of component values in the sparse set.
+    pub len: usize,
+    /// The size of a component value, in bytes.
+    pub item_size: usize,
+    /// The number of bytes used by the component values.
+    pub bytes: usize,
+    /// The number of bytes allocated for component values but not used.
+    pub spare_bytes: usize,
+}
+
+impl World {
+    /// Collects the [`MemoryStats`] of this world.
+    ///
+    /// See the [module docs](crate::memory_stats) for more information.
+    pub fn memory_stats(&self) -> MemoryStats {
+        let item_size = |id: ComponentId| {
+            self.components()
+                .get_info(id)
+                .map_or(0, |info| info.layout().size())
+        };
+
+        let archetypes = self.archetypes();
+        let sorted_components: Vec<Vec<ComponentId>> = archetypes
+            .iter()
+            .map(|archetype| {
+                let mut components: Vec<_> = archetype.components().collect();
+                components.sort_unstable();
+                components
+            })
+            .collect();
+        let by_components: HashMap<&[ComponentId], usize> = sorted_components
+            .iter()
+            .enumerate()
+            .map(|(index, components)| (components.as_slice(), index))
+            .collect();
+        // Each pair of neighbors is found once, from the archetype with one more component.
+        let mut neighbors = vec![0; sorted_components.len()];
+        let mut subset = Vec::new();
+        for (index, components) in sorted_components.iter().enumerate() {
+            for removed in 0..components.len() {
+                subset.clear();
+                subset.extend_from_slice(&components[..removed]);
+                subset.extend_from_slice(&components[removed + 1..]);
+                if let Some(&other) = by_components.get(subset.as_slice()) {
+                    neighbors[index] += 1;
+                    neighbors[other] += 1;
+                }
+            }
+        }
+
+        let archetypes = archetypes
+            .iter()
+            .zip(neighbors)
+            .map(|(archetype, single_component_neighbors)| ArchetypeStats {
+                id: archetype.id(),
+                table_id: archetype.table_id(),
+                entity_count: archetype.len(),
+                component_count: archetype.component_count(),
+                single_component_neighbors,
+            })
+            .collect();
+
+        let tables = self
+            .storages()
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(index, table)| {
+                let spare = table.capacity() - table.entity_count();
+                TableStats {
+                    id: TableId::from_usize(index),
+                    entity_count: table.entity_count(),
+                    entity_capacity: table.capacity(),
+                    columns: table
+                        .components()
+                        .map(|component_id| {
+                            let item_size = item_size(component_id);
+                            ColumnStats {
+                                component_id,
+                                item_size,
+                                bytes: item_size * table.entity_count(),
+                                spare_bytes: item_size * spare,
+                            }
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let sparse_sets = self
+            .storages()
+            .sparse_sets
+            .iter()
+            .map(|(component_id, sparse_set)| {
+                let item_size = item_size(component_id);
+                SparseSetStats {
+                    component_id,
+                    len: sparse_set.len(),
+                    item_size,
+                    bytes: item_size * sparse_set.len(),
+                    // Zero-sized components have a capacity of `usize::MAX`.
+                    spare_bytes: item_size * sparse_set.capacity().saturating_sub(sparse_set.len()),
+                }
+            })
+            .collect();
+
+        MemoryStats {
+            archetypes,
+            tables,
+            sparse_sets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as bevy_ecs, component::ComponentId, prelude::*};
+
+    #[derive(Component)]
+    #[allow(dead_code)]
+    struct A(u64);
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    struct C;
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    #[allow(dead_code)]
+    struct Sparse(u32);
+
+    #[test]
+    fn memory_stats() {
+        let mut world = World::new();
+        let a = world.spawn(A(0)).id();
+        world.spawn((A(1), B));
+        world.spawn((A(2), C, Sparse(0)));
+        world.entity_mut(a).insert(B).remove::<B>();
+
+        let stats = world.memory_stats();
+        let neighbors = |components: &[ComponentId]| {
+            let archetype = world
+                .archetypes()
+                .iter()
+                .find(|archetype| {
+                    archetype.component_count() == components.len()
+                        && components.iter().all(|&id| archetype.contains(id))
+                })
+                .unwrap();
+            stats.archetypes[archetype.id().index()].single_component_neighbors
+        };
+        let [a_id, b_id, c_id, sparse_id] = [
+            world.component_id::<A>().unwrap(),
+            world.component_id::<B>().unwrap(),
+            world.component_id::<C>().unwrap(),
+            world.component_id::<Sparse>().unwrap(),
+        ];
+        // The empty archetype and (A, B)
+        assert_eq!(neighbors(&[a_id]), 2);
+        assert_eq!(neighbors(&[a_id, b_id]), 1);
+        // Spawned directly, without going through (A, C)
+        assert_eq!(neighbors(&[a_id, c_id, sparse_id]), 0);
+
+        let table = world.entity(a).archetype().table_id();
+        let table = &stats.tables[table.as_usize()];
+        assert_eq!(table.entity_count, 1);
+        assert_eq!(table.bytes(), 8);
+        assert_eq!(table.spare_bytes(), 8 * (table.entity_capacity - 1));
+
+        let sparse = stats
+            .sparse_sets
+            .iter()
+            .find(|sparse_set| sparse_set.component_id == sparse_id)
+            .unwrap();
+        assert_eq!((sparse.len, sparse.bytes), (1, 4));
+        assert!(stats.empty_archetype_count() >= 1);
+    }
+}
@@ -1,10 +1,14 @@
 //! Types for creating and storing [`Observer`]s
 
 mod entity_observer;
+mod query_match;
+mod resource_observer;
 mod runner;
 mod trigger_event;
 
 pub use entity_observer::CloneEntityWithObserversExt;
+pub use query_match::*;
+pub use resource_observer::*;
 pub use runner::*;
 pub use trigger_event::*;
 
@@ -14,34 +18,41 @@ use crate::{
     entity::EntityHashMap,
     observer::entity_observer::ObservedBy,
     prelude::*,
+    schedule::InternedSystemSet,
     system::IntoObserverSystem,
     world::{DeferredWorld, *},
 };
+use alloc::sync::Arc;
 use bevy_ptr::Ptr;
-use bevy_utils::HashMap;
+use bevy_utils::{tracing::warn, HashMap};
 use core::{
     fmt::Debug,
     marker::PhantomData,
     ops::{Deref, DerefMut},
 };
 use smallvec::SmallVec;
+use std::sync::{Mutex, PoisonError};
 
 /// Type containing triggered [`Event`] information for a given run of an [`Observer`]. This contains the
 /// [`Event`] data itself. If it was triggered for a specific [`Entity`], it includes that as well. It also
 /// contains event propagation information. See [`Trigger::propagate`] for more information.
 pub struct Trigger<'w, E, B: Bundle = ()> {
     event: &'w mut E,
-    propagate: &'w mut bool,
+    propagation: &'w mut Propagation,
     trigger: ObserverTrigger,
     _marker: PhantomData<B>,
 }
 
 impl<'w, E, B: Bundle> Trigger<'w, E, B> {
     /// Creates a new trigger for the given event and observer information.
-    pub fn new(event: &'w mut E, propagate: &'w mut bool, trigger: ObserverTrigger) -> Self {
+    pub fn new(
+        event: &'w mut E,
+        propagation: &'w mut Propagation,
+        trigger: ObserverTrigger,
+    ) -> Self {
         Self {
             event,
-            propagate,
+            propagation,
             trigger,
             _marker: PhantomData,
         }
@@ -114,18 +125,80 @@ impl<'w, E, B: Bundle> Trigger<'w, E, B> {
     /// + Set [`Event::Traversal`] to the component you want to propagate along.
     /// + Either call `propagate(true)` in the first observer or set [`Event::AUTO_PROPAGATE`] to `true`.
     ///
-    /// You can prevent an event from propagating further using `propagate(false)`.
+    /// You can prevent an event from propagating further using `propagate(false)`, or send it somewhere else
+    /// with [`propagate_to`](Trigger::propagate_to).
     ///
     /// [`Traversal`]: crate::traversal::Traversal
     pub fn propagate(&mut self, should_propagate: bool) {
-        *self.propagate = should_propagate;
+        self.propagation.enabled = should_propagate;
+        if !should_propagate {
+            self.propagation.redirect = None;
+        }
     }
 
     /// Returns the value of the flag that controls event propagation. See [`propagate`] for more information.
     ///
     /// [`propagate`]: Trigger::propagate
     pub fn get_propagate(&self) -> bool {
-        *self.propagate
+        self.propagation.enabled
+    }
+
+    /// Enables propagation and makes `entity` the next hop of the event, instead of the entity given by
+    /// its [`Traversal`]. Propagation continues along the [`Traversal`] of `entity` afterwards.
+    ///
+    /// The remaining observers of the current hop still run. If several of them redirect the event, the last
+    /// one wins.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Parent(Entity);
+    /// # impl bevy_ecs::traversal::Traversal for &'_ Parent {
+    /// #     fn traverse(item: Self::Item<'_>) -> Option<Entity> { Some(item.0) }
+    /// # }
+    /// # #[derive(Component)]
+    /// # struct Click;
+    /// # impl Event for Click {
+    /// #     type Traversal = &'static Parent;
+    /// #     const AUTO_PROPAGATE: bool = true;
+    /// # }
+    /// /// Entities that forward the clicks they receive to another entity.
+    /// #[derive(Component)]
+    /// struct ForwardClicks(Entity);
+    ///
+    /// fn forward_clicks(mut trigger: Trigger<Click>, forwarders: Query<&ForwardClicks>) {
+    ///     if let Ok(forward) = forwarders.get(trigger.entity()) {
+    ///         trigger.propagate_to(forward.0);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`Traversal`]: crate::traversal::Traversal
+    pub fn propagate_to(&mut self, entity: Entity) {
+        self.propagation.enabled = true;
+        self.propagation.redirect = Some(entity);
+    }
+
+    /// Returns the entity the event will propagate to next if it was redirected with
+    /// [`propagate_to`](Trigger::propagate_to) during this hop.
+    pub fn propagation_target(&self) -> Option<Entity> {
+        self.propagation.redirect
+    }
+
+    /// Returns the entity the event was originally triggered for, before propagating.
+    ///
+    /// This is the same as [`entity`](Trigger::entity) for the first hop, and [`Entity::PLACEHOLDER`]
+    /// for events without targets.
+    pub fn original_entity(&self) -> Entity {
+        self.propagation.origin
+    }
+
+    /// Returns how many times the event has propagated before reaching the current entity.
+    ///
+    /// Together with [`event_mut`](Trigger::event_mut), this allows observers to collect results along the
+    /// propagation path, which the sender can read back when using [`World::trigger_targets_ref`].
+    pub fn hop(&self) -> usize {
+        self.propagation.hop
     }
 }
 
@@ -133,7 +206,7 @@ impl<'w, E: Debug, B: Bundle> Debug for Trigger<'w, E, B> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Trigger")
             .field("event", &self.event)
-            .field("propagate", &self.propagate)
+            .field("propagation", &self.propagation)
             .field("trigger", &self.trigger)
             .field("_marker", &self._marker)
             .finish()
@@ -154,6 +227,55 @@ impl<'w, E, B: Bundle> DerefMut for Trigger<'w, E, B> {
     }
 }
 
+/// The state of an event propagating along its [`Traversal`](crate::traversal::Traversal).
+///
+/// Observers control it through [`Trigger::propagate`] and [`Trigger::propagate_to`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Propagation {
+    enabled: bool,
+    redirect: Option<Entity>,
+    origin: Entity,
+    hop: usize,
+}
+
+impl Propagation {
+    /// Creates the propagation state of an event triggered for `origin`.
+    pub fn new(enabled: bool, origin: Entity) -> Self {
+        Self {
+            enabled,
+            redirect: None,
+            origin,
+            hop: 0,
+        }
+    }
+
+    /// Returns `true` if the event will propagate after the current hop.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the next hop, if an observer redirected the event.
+    pub fn redirect(&self) -> Option<Entity> {
+        self.redirect
+    }
+
+    /// Returns the entity the event was originally triggered for.
+    pub fn origin(&self) -> Entity {
+        self.origin
+    }
+
+    /// Returns the number of hops the event has propagated.
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// Moves on to the next hop, returning the redirected target if there is one.
+    pub(crate) fn advance(&mut self) -> Option<Entity> {
+        self.hop += 1;
+        self.redirect.take()
+    }
+}
+
 /// A description of what an [`Observer`] observes.
 #[derive(Default, Clone)]
 pub struct ObserverDescriptor {
@@ -197,6 +319,27 @@ impl ObserverDescriptor {
     }
 }
 
+/// The sets an [`Observer`] is part of, and the sets it is ordered against.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct ObserverOrdering {
+    pub(crate) sets: Vec<InternedSystemSet>,
+    pub(crate) before: Vec<InternedSystemSet>,
+    pub(crate) after: Vec<InternedSystemSet>,
+}
+
+impl ObserverOrdering {
+    /// Returns `true` if the observer has to be ordered against other observers.
+    pub(crate) fn is_constrained(&self) -> bool {
+        !self.before.is_empty() || !self.after.is_empty()
+    }
+
+    /// Returns `true` if an observer with this ordering has to run before one with the `other` ordering.
+    fn runs_before(&self, other: &ObserverOrdering) -> bool {
+        self.before.iter().any(|set| other.sets.contains(set))
+            || other.after.iter().any(|set| self.sets.contains(set))
+    }
+}
+
 /// Event trigger metadata for a given [`Observer`],
 #[derive(Debug)]
 pub struct ObserverTrigger {
@@ -220,6 +363,12 @@ impl ObserverTrigger {
 // Map between an observer entity and its runner
 type ObserverMap = EntityHashMap<ObserverRunner>;
 
+// The targeted entity, or `Entity::PLACEHOLDER` if no observer watches it, and the targeted components that are observed
+type SortedObserversKey = (Entity, SmallVec<[ComponentId; 4]>);
+
+// Matching observers in the order they run in
+type SortedObservers = Arc<[(Entity, ObserverRunner)]>;
+
 /// Collection of [`ObserverRunner`] for [`Observer`] registered to a particular trigger targeted at a specific component.
 #[derive(Default, Debug)]
 pub struct CachedComponentObservers {
@@ -238,6 +387,96 @@ pub struct CachedObservers {
     component_observers: HashMap<ComponentId, CachedComponentObservers>,
     // Observers listening for this trigger fired at a specific entity
     entity_observers: EntityHashMap<ObserverMap>,
+    // Number of observers of this trigger with ordering constraints
+    ordered: usize,
+    // Sorted matching observers for each combination of targets, only used when `ordered > 0`.
+    // Cleared whenever an observer of this trigger is added or removed.
+    sorted: Mutex<HashMap<SortedObserversKey, SortedObservers>>,
+}
+
+impl CachedObservers {
+    /// Calls `f` for each observer of this trigger that targets the given `entity` or `components`.
+    fn for_each_matching(
+        &self,
+        entity: Entity,
+        components: impl Iterator<Item = ComponentId>,
+        mut f: impl FnMut((&Entity, &ObserverRunner)),
+    ) {
+        // Trigger observers listening for any kind of this trigger
+        self.map.iter().for_each(&mut f);
+
+        // Trigger entity observers listening for this kind of trigger
+        if entity != Entity::PLACEHOLDER {
+            if let Some(map) = self.entity_observers.get(&entity) {
+                map.iter().for_each(&mut f);
+            }
+        }
+
+        // Trigger observers listening to this trigger targeting a specific component
+        components.for_each(|id| {
+            if let Some(component_observers) = self.component_observers.get(&id) {
+                component_observers.map.iter().for_each(&mut f);
+
+                if entity != Entity::PLACEHOLDER {
+                    if let Some(map) = component_observers.entity_map.get(&entity) {
+                        map.iter().for_each(&mut f);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Returns the observers of this trigger that target the given `entity` or `components`, sorted according to
+    /// their ordering constraints. The order is cached until an observer of this trigger is added or removed.
+    fn sorted_matching(
+        &self,
+        world: &World,
+        entity: Entity,
+        components: impl Iterator<Item = ComponentId>,
+    ) -> SortedObservers {
+        let components: SmallVec<[ComponentId; 4]> = components
+            .filter(|id| self.component_observers.contains_key(id))
+            .collect();
+        let entity_is_observed = entity != Entity::PLACEHOLDER
+            && (self.entity_observers.contains_key(&entity)
+                || components.iter().any(|id| {
+                    self.component_observers[id]
+                        .entity_map
+                        .contains_key(&entity)
+                }));
+        let entity = if entity_is_observed {
+            entity
+        } else {
+            Entity::PLACEHOLDER
+        };
+
+        let mut sorted = self.sorted.lock().unwrap_or_else(PoisonError::into_inner);
+        sorted
+            .entry((entity, components))
+            .or_insert_with_key(|(entity, components)| {
+                let mut matching = SmallVec::new();
+                self.for_each_matching(
+                    *entity,
+                    components.iter().copied(),
+                    |(&observer, &runner)| {
+                        if !matching.iter().any(|(other, _)| *other == observer) {
+                            matching.push((observer, runner));
+                        }
+                    },
+                );
+                Observers::sort_observers(world, &mut matching);
+                matching.into_iter().collect()
+            })
+            .clone()
+    }
+
+    /// Clears the cached order of the observers, after one was added or removed.
+    fn invalidate_sorted(&mut self) {
+        self.sorted
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
 }
 
 /// Metadata for observers. Stores a cache mapping trigger ids to the registered observers.
@@ -280,7 +519,7 @@ impl Observers {
         entity: Entity,
         components: impl Iterator<Item = ComponentId> + Clone,
         data: &mut T,
-        propagate: &mut bool,
+        propagation: &mut Propagation,
     ) {
         // SAFETY: You cannot get a mutable reference to `observers` from `DeferredWorld`
         let (mut world, observers) = unsafe {
@@ -297,6 +536,11 @@ impl Observers {
 
         let trigger_for_components = components.clone();
 
+        // Observers with ordering constraints are sorted before any of them run. Observers watching several of the
+        // targeted components are only listed once, matching the unsorted path where runners skip repeated calls.
+        let sorted = (observers.ordered > 0)
+            .then(|| observers.sorted_matching(&world, entity, components.clone()));
+
         let mut trigger_observer = |(&observer, runner): (&Entity, &ObserverRunner)| {
             (runner)(
                 world.reborrow(),
@@ -307,34 +551,63 @@ impl Observers {
                     entity,
                 },
                 data.into(),
-                propagate,
+                propagation,
             );
         };
-        // Trigger observers listening for any kind of this trigger
-        observers.map.iter().for_each(&mut trigger_observer);
 
-        // Trigger entity observers listening for this kind of trigger
-        if entity != Entity::PLACEHOLDER {
-            if let Some(map) = observers.entity_observers.get(&entity) {
-                map.iter().for_each(&mut trigger_observer);
+        match sorted {
+            Some(sorted) => {
+                for (observer, runner) in sorted.iter() {
+                    trigger_observer((observer, runner));
+                }
             }
+            None => observers.for_each_matching(entity, trigger_for_components, trigger_observer),
         }
+    }
 
-        // Trigger observers listening to this trigger targeting a specific component
-        trigger_for_components.for_each(|id| {
-            if let Some(component_observers) = observers.component_observers.get(&id) {
-                component_observers
-                    .map
-                    .iter()
-                    .for_each(&mut trigger_observer);
+    /// Sorts the `observers` of a trigger according to their ordering constraints, keeping unconstrained
+    /// observers in their current order.
+    fn sort_observers(world: &World, observers: &mut SmallVec<[(Entity, ObserverRunner); 8]>) {
+        let orderings: SmallVec<[Option<&ObserverOrdering>; 8]> = observers
+            .iter()
+            .map(|(observer, _)| {
+                world
+                    .get::<ObserverState>(*observer)
+                    .map(|state| &state.ordering)
+            })
+            .collect();
+        let runs_before = |a: usize, b: usize| match (orderings[a], orderings[b]) {
+            (Some(a), Some(b)) => a.runs_before(b),
+            _ => false,
+        };
 
-                if entity != Entity::PLACEHOLDER {
-                    if let Some(map) = component_observers.entity_map.get(&entity) {
-                        map.iter().for_each(&mut trigger_observer);
-                    }
+        let len = observers.len();
+        // The number of observers that have to run before each observer and haven't been sorted yet.
+        let mut blockers: SmallVec<[usize; 8]> = (0..len)
+            .map(|b| (0..len).filter(|&a| a != b && runs_before(a, b)).count())
+            .collect();
+        let mut done: SmallVec<[bool; 8]> = SmallVec::from_elem(false, len);
+        let mut sorted = SmallVec::with_capacity(len);
+        while sorted.len() < len {
+            let next = (0..len)
+                .find(|&i| !done[i] && blockers[i] == 0)
+                .unwrap_or_else(|| {
+                    let cycle: Vec<_> = (0..len)
+                        .filter(|&i| !done[i])
+                        .map(|i| observers[i].0)
+                        .collect();
+                    warn!("Observers {cycle:?} have cyclic ordering constraints, some of them will run out of order.");
+                    (0..len).find(|&i| !done[i]).unwrap()
+                });
+            done[next] = true;
+            sorted.push(observers[next]);
+            for b in 0..len {
+                if !done[b] && runs_before(next, b) {
+                    blockers[b] -= 1;
                 }
             }
-        });
+        }
+        *observers = sorted;
     }
 
     pub(crate) fn is_archetype_cached(event_type: ComponentId) -> Option<ArchetypeFlags> {
@@ -463,6 +736,14 @@ impl World {
         };
         let descriptor = &observer_state.descriptor;
 
+        if descriptor
+            .events
+            .iter()
+            .any(|&event_type| is_resource_lifecycle_event(&self.components, event_type))
+        {
+            self.resource_lifecycle_triggers = true;
+        }
+
         for &event_type in &descriptor.events {
             let cache = observers.get_observers(event_type);
 
@@ -499,16 +780,30 @@ impl World {
                     }
                 }
             }
+
+            if observer_state.ordering.is_constrained() {
+                cache.ordered += 1;
+            }
+            cache.invalidate_sorted();
         }
     }
 
     /// Remove the observer from the cache, called when an observer gets despawned
-    pub(crate) fn unregister_observer(&mut self, entity: Entity, descriptor: ObserverDescriptor) {
+    pub(crate) fn unregister_observer(
+        &mut self,
+        entity: Entity,
+        descriptor: ObserverDescriptor,
+        is_ordered: bool,
+    ) {
         let archetypes = &mut self.archetypes;
         let observers = &mut self.observers;
 
         for &event_type in &descriptor.events {
             let cache = observers.get_observers(event_type);
+            if is_ordered {
+                cache.ordered -= 1;
+            }
+            cache.invalidate_sorted();
             if descriptor.components.is_empty() && descriptor.entities.is_empty() {
                 cache.map.remove(&entity);
             } else if descriptor.components.is_empty() {
@@ -984,7 +1279,7 @@ mod tests {
         world.spawn(ObserverState {
             // SAFETY: we registered `event_a` above and it matches the type of TriggerA
             descriptor: unsafe { ObserverDescriptor::default().with_events(vec![event_a]) },
-            runner: |mut world, _trigger, _ptr, _propagate| {
+            runner: |mut world, _trigger, _ptr, _propagation| {
                 world.resource_mut::<Order>().observed("event_a");
             },
             ..Default::default()
@@ -1265,6 +1560,182 @@ mod tests {
         assert_eq!(vec!["event", "event"], world.resource::<Order>().0);
     }
 
+    #[test]
+    fn observer_propagating_redirect() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let grandparent = world
+            .spawn_empty()
+            .observe(|_: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                res.observed("grandparent");
+            })
+            .id();
+        let other = world
+            .spawn(Parent(grandparent))
+            .observe(|_: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                res.observed("other");
+            })
+            .id();
+        let parent = world
+            .spawn_empty()
+            .observe(|_: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                res.observed("parent");
+            })
+            .id();
+        let child = world
+            .spawn(Parent(parent))
+            .observe(
+                move |mut trigger: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                    res.observed("child");
+                    trigger.propagate_to(other);
+                },
+            )
+            .id();
+
+        world.flush();
+        world.trigger_targets(EventPropagating, child);
+        world.flush();
+        assert_eq!(
+            vec!["child", "other", "grandparent"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_propagating_collect() {
+        #[derive(Component)]
+        struct Collect(Vec<(Entity, usize)>);
+
+        impl Event for Collect {
+            type Traversal = &'static Parent;
+
+            const AUTO_PROPAGATE: bool = true;
+        }
+
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        let child = world.spawn(Parent(parent)).id();
+        world.add_observer(move |mut trigger: Trigger<Collect>| {
+            assert_eq!(trigger.original_entity(), child);
+            let (entity, hop) = (trigger.entity(), trigger.hop());
+            trigger.event_mut().0.push((entity, hop));
+        });
+        world.flush();
+
+        let mut event = Collect(Vec::new());
+        world.trigger_targets_ref(&mut event, child);
+        assert_eq!(event.0, vec![(child, 0), (parent, 1)]);
+    }
+
+    #[test]
+    fn observer_ordering() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Gameplay;
+
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Ui;
+
+        fn sound(_: Trigger<EventA>, mut res: ResMut<Order>) {
+            res.observed("sound");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.spawn(
+            Observer::new(|_: Trigger<EventA>, mut res: ResMut<Order>| res.observed("ui"))
+                .in_set(Ui)
+                .after(Gameplay),
+        );
+        world.spawn(Observer::new(sound).after(Ui));
+        let gameplay = world
+            .spawn(
+                Observer::new(|_: Trigger<EventA>, mut res: ResMut<Order>| {
+                    res.observed("gameplay");
+                })
+                .in_set(Gameplay),
+            )
+            .id();
+        world.spawn(
+            Observer::new(|_: Trigger<EventA>, mut res: ResMut<Order>| res.observed("first"))
+                .before(Gameplay)
+                .before(sound),
+        );
+        world.flush();
+
+        world.trigger(EventA);
+        assert_eq!(
+            vec!["first", "gameplay", "ui", "sound"],
+            world.resource::<Order>().0
+        );
+
+        world.resource_mut::<Order>().0.clear();
+        world.despawn(gameplay);
+        world.flush();
+        world.trigger(EventA);
+        let order = &world.resource::<Order>().0;
+        assert_eq!(order.len(), 3);
+        assert!(
+            order.iter().position(|name| *name == "ui")
+                < order.iter().position(|name| *name == "sound")
+        );
+    }
+
+    #[test]
+    fn observer_ordering_entity_and_component_observers() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Late;
+
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.spawn(
+            Observer::new(|_: Trigger<OnAdd, A>, mut res: ResMut<Order>| res.observed("late"))
+                .in_set(Late),
+        );
+        world.spawn(
+            Observer::new(|_: Trigger<OnAdd>, mut res: ResMut<Order>| res.observed("global"))
+                .after(Late),
+        );
+        let entity = world.spawn_empty().id();
+        world.spawn(
+            Observer::new(|_: Trigger<OnAdd>, mut res: ResMut<Order>| res.observed("entity"))
+                .with_entity(entity)
+                .before(Late),
+        );
+        world.flush();
+
+        world.entity_mut(entity).insert(A);
+        world.flush();
+        assert_eq!(
+            vec!["entity", "late", "global"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_ordering_multiple_components() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Unrelated;
+
+        let mut world = World::new();
+        world.init_resource::<Order>();
+        world.add_observer(|_: Trigger<OnAdd, (A, B)>, mut res: ResMut<Order>| {
+            res.observed("add_ab");
+        });
+        world.spawn((A, B));
+        assert_eq!(vec!["add_ab"], world.resource::<Order>().0);
+
+        // Ordering an unrelated observer of the same event doesn't change how often the others run.
+        world.resource_mut::<Order>().0.clear();
+        world.spawn(Observer::new(|_: Trigger<OnAdd, C>| {}).before(Unrelated));
+        world.flush();
+        world.spawn((A, B));
+        world.spawn((A, B));
+        assert_eq!(vec!["add_ab", "add_ab"], world.resource::<Order>().0);
+    }
+
     // Regression test for https://github.com/bevyengine/bevy/issues/14467
     // Fails prior to https://github.com/bevyengine/bevy/pull/15398
     #[test]
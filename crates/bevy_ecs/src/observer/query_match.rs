@@ -0,0 +1,330 @@
+use core::{fmt::Debug, marker::PhantomData};
+
+use bevy_ptr::PtrMut;
+
+use crate::{
+    self as bevy_ecs,
+    component::ComponentId,
+    entity::Entity,
+    observer::{ObserverDescriptor, ObserverState, ObserverTrigger, Propagation},
+    prelude::*,
+    query::{QueryData, QueryFilter, QueryState},
+    world::{DeferredWorld, ON_ADD, ON_REMOVE},
+};
+
+/// Trigger emitted when an entity starts matching the query `Query<D, F>`.
+///
+/// Only emitted for queries that are tracked with [`World::track_query_matches`].
+/// Whether an entity matches only depends on its components, so `D` and `F` are usually
+/// a combination of `()`, [`With`] and [`Without`]. See [`World::track_query_matches`] for more information.
+#[derive(Event)]
+pub struct OnMatch<D: QueryData + 'static = (), F: QueryFilter + 'static = ()>(
+    PhantomData<fn() -> (D, F)>,
+);
+
+/// Trigger emitted when an entity stops matching the query `Query<D, F>`, either because a component was
+/// added or removed, or because the entity was despawned.
+///
+/// Only emitted for queries that are tracked with [`World::track_query_matches`].
+#[derive(Event)]
+pub struct OnUnmatch<D: QueryData + 'static = (), F: QueryFilter + 'static = ()>(
+    PhantomData<fn() -> (D, F)>,
+);
+
+impl<D: QueryData + 'static, F: QueryFilter + 'static> Default for OnMatch<D, F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<D: QueryData + 'static, F: QueryFilter + 'static> Debug for OnMatch<D, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "OnMatch<{}, {}>",
+            core::any::type_name::<D>(),
+            core::any::type_name::<F>()
+        )
+    }
+}
+
+impl<D: QueryData + 'static, F: QueryFilter + 'static> Default for OnUnmatch<D, F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<D: QueryData + 'static, F: QueryFilter + 'static> Debug for OnUnmatch<D, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "OnUnmatch<{}, {}>",
+            core::any::type_name::<D>(),
+            core::any::type_name::<F>()
+        )
+    }
+}
+
+/// The state of a query tracked with [`World::track_query_matches`].
+#[derive(Resource)]
+struct QueryMatchTracker<D: QueryData + 'static, F: QueryFilter + 'static> {
+    state: QueryState<D, F>,
+    // The components that decide whether an entity matches
+    components: Vec<ComponentId>,
+    // The internal observers, one for each component
+    observers: Vec<Entity>,
+}
+
+impl World {
+    /// Starts triggering [`OnMatch`] and [`OnUnmatch`] for the query `Query<D, F>`, whenever an entity starts
+    /// or stops matching it.
+    ///
+    /// This is driven by the [`OnAdd`] and [`OnRemove`] triggers of the components the query filters on, so every
+    /// transition is caught, even if an entity stops matching in the same frame it started to. The events are
+    /// queued as commands while the change is applied, so the components of an entity that stopped matching
+    /// are already gone when observers of [`OnUnmatch`] run.
+    ///
+    /// Entities that already match the query when tracking starts do not trigger [`OnMatch`]. Calling this again
+    /// for the same query has no effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `F` contains filters that do not only depend on the components of an entity, like [`Added`] or [`Changed`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::observer::{OnMatch, OnUnmatch};
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// #[derive(Component)]
+    /// struct Visible;
+    ///
+    /// type VisibleEnemy = (With<Enemy>, With<Visible>);
+    ///
+    /// let mut world = World::new();
+    /// world.track_query_matches::<(), VisibleEnemy>();
+    /// world.add_observer(|trigger: Trigger<OnMatch<(), VisibleEnemy>>| {
+    ///     println!("{:?} is a visible enemy", trigger.entity());
+    /// });
+    /// world.add_observer(|trigger: Trigger<OnUnmatch<(), VisibleEnemy>>| {
+    ///     println!("{:?} is no longer a visible enemy", trigger.entity());
+    /// });
+    /// world.flush();
+    ///
+    /// let enemy = world.spawn(Enemy).id();
+    /// world.entity_mut(enemy).insert(Visible);
+    /// world.entity_mut(enemy).remove::<Visible>();
+    /// ```
+    pub fn track_query_matches<D: QueryData + 'static, F: QueryFilter + 'static>(&mut self) {
+        assert!(
+            F::IS_ARCHETYPAL,
+            "Cannot track the matches of a query with non-archetypal filters like `Added` or `Changed`: {}",
+            core::any::type_name::<F>()
+        );
+        if self.contains_resource::<QueryMatchTracker<D, F>>() {
+            return;
+        }
+
+        let state = QueryState::<D, F>::new(self);
+        let access = state.component_access();
+        let mut components: Vec<ComponentId> = access.with_filters().collect();
+        components.extend(access.without_filters());
+        components.sort();
+        components.dedup();
+
+        let observers = components
+            .iter()
+            .map(|&component| {
+                // SAFETY: `query_match_runner` ignores the event data.
+                let descriptor =
+                    unsafe { ObserverDescriptor::default().with_events(vec![ON_ADD, ON_REMOVE]) }
+                        .with_components(vec![component]);
+                self.spawn(ObserverState {
+                    descriptor,
+                    runner: query_match_runner::<D, F>,
+                    ..Default::default()
+                })
+                .id()
+            })
+            .collect();
+        self.insert_resource(QueryMatchTracker {
+            state,
+            components,
+            observers,
+        });
+        self.flush();
+    }
+
+    /// Stops triggering [`OnMatch`] and [`OnUnmatch`] for the query `Query<D, F>`.
+    ///
+    /// Returns `false` if the query wasn't tracked.
+    pub fn untrack_query_matches<D: QueryData + 'static, F: QueryFilter + 'static>(
+        &mut self,
+    ) -> bool {
+        let Some(tracker) = self.remove_resource::<QueryMatchTracker<D, F>>() else {
+            return false;
+        };
+        for observer in tracker.observers {
+            self.despawn(observer);
+        }
+        self.flush();
+        true
+    }
+}
+
+fn query_match_runner<D: QueryData + 'static, F: QueryFilter + 'static>(
+    mut world: DeferredWorld,
+    trigger: ObserverTrigger,
+    _: PtrMut,
+    _: &mut Propagation,
+) {
+    let Some(tracker) = world.get_resource::<QueryMatchTracker<D, F>>() else {
+        return;
+    };
+    // Each observer of a tracked query watches one component. When several of them are added
+    // or removed at once, only the observer of the first one reacts.
+    let first = trigger
+        .components()
+        .iter()
+        .find(|component| tracker.components.contains(component));
+    let observed = world
+        .get::<ObserverState>(trigger.observer)
+        .and_then(|state| state.descriptor.components.first());
+    if first.is_none() || first != observed {
+        return;
+    }
+    let Ok(entity) = world.get_entity(trigger.entity) else {
+        return;
+    };
+
+    let archetype = entity.archetype();
+    let changed = trigger.components();
+    let with_changes = tracker
+        .state
+        .matches_component_set(&|id| archetype.contains(id));
+    let without_changes = tracker
+        .state
+        .matches_component_set(&|id| archetype.contains(id) && !changed.contains(&id));
+    // Added components are already part of the archetype, removed components are still part of it.
+    let (matched, matches) = if trigger.event_type == ON_ADD {
+        (without_changes, with_changes)
+    } else {
+        (with_changes, without_changes)
+    };
+
+    match (matched, matches) {
+        (false, true) => {
+            world
+                .commands()
+                .trigger_targets(OnMatch::<D, F>::default(), trigger.entity);
+        }
+        (true, false) => {
+            world
+                .commands()
+                .trigger_targets(OnUnmatch::<D, F>::default(), trigger.entity);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        entity_disabling::Disabled,
+        observer::{OnMatch, OnUnmatch},
+        prelude::*,
+    };
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[derive(Component)]
+    struct Visible;
+
+    #[derive(Component)]
+    struct Dead;
+
+    #[derive(Component)]
+    struct Other;
+
+    type VisibleEnemy = (With<Visible>, Without<Dead>);
+
+    #[derive(Resource, Default)]
+    struct Transitions(Vec<(Entity, bool)>);
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<Transitions>();
+        world.track_query_matches::<&Enemy, VisibleEnemy>();
+        world.add_observer(
+            |trigger: Trigger<OnMatch<&'static Enemy, VisibleEnemy>>,
+             mut transitions: ResMut<Transitions>| {
+                transitions.0.push((trigger.entity(), true));
+            },
+        );
+        world.add_observer(
+            |trigger: Trigger<OnUnmatch<&'static Enemy, VisibleEnemy>>,
+             mut transitions: ResMut<Transitions>| {
+                transitions.0.push((trigger.entity(), false));
+            },
+        );
+        world.flush();
+        world
+    }
+
+    #[test]
+    fn query_match_transitions() {
+        let mut world = setup();
+
+        let enemy = world.spawn((Enemy, Other)).id();
+        world.entity_mut(enemy).insert(Visible);
+        world.entity_mut(enemy).remove::<Other>();
+        world.entity_mut(enemy).insert(Dead);
+        world.entity_mut(enemy).remove::<Dead>();
+        let spawned = world.spawn((Enemy, Visible)).id();
+        world.despawn(enemy);
+        world.entity_mut(spawned).remove::<(Enemy, Visible)>();
+        world.flush();
+
+        assert_eq!(
+            world.resource::<Transitions>().0,
+            vec![
+                (enemy, true),
+                (enemy, false),
+                (enemy, true),
+                (spawned, true),
+                (enemy, false),
+                (spawned, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn query_match_default_filters() {
+        let mut world = setup();
+
+        let enemy = world.spawn((Enemy, Visible)).id();
+        world.entity_mut(enemy).insert(Disabled);
+        world.entity_mut(enemy).remove::<Disabled>();
+        world.flush();
+
+        assert_eq!(
+            world.resource::<Transitions>().0,
+            vec![(enemy, true), (enemy, false), (enemy, true)]
+        );
+    }
+
+    #[test]
+    fn query_match_untrack() {
+        let mut world = setup();
+        assert!(world.untrack_query_matches::<&Enemy, VisibleEnemy>());
+        assert!(!world.untrack_query_matches::<&Enemy, VisibleEnemy>());
+
+        world.spawn((Enemy, Visible));
+        assert!(world.resource::<Transitions>().0.is_empty());
+    }
+}
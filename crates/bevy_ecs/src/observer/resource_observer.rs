@@ -0,0 +1,428 @@
+use alloc::vec::Vec;
+use core::{any::TypeId, iter};
+
+use crate::{
+    self as bevy_ecs,
+    component::{ComponentHooks, ComponentId, ComponentInfo, Components},
+    entity::Entity,
+    observer::Observer,
+    prelude::*,
+    storage::ResourceData,
+    system::IntoObserverSystem,
+    world::DeferredWorld,
+};
+
+/// Trigger emitted when a resource is inserted into a world that didn't contain it.
+///
+/// Observers of resources listen for the [`ComponentId`] of the resource, and the target of the trigger
+/// is [`Entity::PLACEHOLDER`]. See [`World::add_resource_observer`] for more information.
+#[derive(Event, Debug)]
+pub struct OnAddResource;
+
+/// Trigger emitted when a resource is inserted, whether or not it was already present.
+///
+/// See [`World::add_resource_observer`] for more information.
+#[derive(Event, Debug)]
+pub struct OnInsertResource;
+
+/// Trigger emitted when a resource is about to be overwritten or removed, while the previous value
+/// is still accessible.
+///
+/// See [`World::add_resource_observer`] for more information.
+#[derive(Event, Debug)]
+pub struct OnReplaceResource;
+
+/// Trigger emitted when a resource is about to be removed, while its value is still accessible.
+///
+/// See [`World::add_resource_observer`] for more information.
+#[derive(Event, Debug)]
+pub struct OnRemoveResource;
+
+/// Trigger emitted by [`trigger_resource_changes`] for each resource that changed since its last run.
+///
+/// See [`World::add_resource_observer`] for more information.
+#[derive(Event, Debug)]
+pub struct OnChangeResource;
+
+/// The lifecycle events of a resource, which run its hooks and observers.
+#[derive(Clone, Copy)]
+pub(crate) enum ResourceLifecycle {
+    Add,
+    Insert,
+    Replace,
+    Remove,
+}
+
+impl World {
+    /// Returns a mutable reference to the [`ComponentHooks`] of the resource `R`.
+    ///
+    /// Resource hooks run when the resource is inserted, overwritten or removed, in the same order as the
+    /// hooks of a component. Their `entity` argument is [`Entity::PLACEHOLDER`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource)]
+    /// struct Volume(f32);
+    ///
+    /// #[derive(Resource, Default)]
+    /// struct AppliedVolume(f32);
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<AppliedVolume>();
+    /// world
+    ///     .register_resource_hooks::<Volume>()
+    ///     .on_insert(|mut world, _, _| {
+    ///         let volume = world.resource::<Volume>().0;
+    ///         world.resource_mut::<AppliedVolume>().0 = volume;
+    ///     });
+    ///
+    /// world.insert_resource(Volume(0.5));
+    /// assert_eq!(world.resource::<AppliedVolume>().0, 0.5);
+    /// ```
+    ///
+    /// Will panic if `R` is already present in the world.
+    pub fn register_resource_hooks<R: Resource>(&mut self) -> &mut ComponentHooks {
+        let id = self.components.register_resource::<R>();
+        self.register_resource_hooks_by_id(id)
+            .expect("The resource was just registered")
+    }
+
+    /// Returns a mutable reference to the [`ComponentHooks`] of the non-send resource `R`.
+    ///
+    /// See [`World::register_resource_hooks`] for more information.
+    ///
+    /// Will panic if `R` is already present in the world.
+    pub fn register_non_send_hooks<R: 'static>(&mut self) -> &mut ComponentHooks {
+        let id = self.components.register_non_send::<R>();
+        self.register_resource_hooks_by_id(id)
+            .expect("The resource was just registered")
+    }
+
+    /// Returns a mutable reference to the [`ComponentHooks`] of the resource or non-send resource with
+    /// the given id if it exists.
+    ///
+    /// Will panic if the resource is already present in the world.
+    pub fn register_resource_hooks_by_id(
+        &mut self,
+        id: ComponentId,
+    ) -> Option<&mut ComponentHooks> {
+        assert!(!self.is_resource_present(id), "Resource hooks cannot be modified if the resource is already present, register them before inserting the resource with id {:?}", id);
+        self.resource_lifecycle_triggers = true;
+        self.components.get_hooks_mut(id)
+    }
+
+    /// Spawns an [`Observer`] of the event `E` for the resource `R`.
+    ///
+    /// `E` is one of [`OnAddResource`], [`OnInsertResource`], [`OnReplaceResource`], [`OnRemoveResource`]
+    /// or [`OnChangeResource`]. Observers added with [`World::add_observer`] instead run for these events
+    /// of every resource, and can use [`Trigger::components`] to know which resource it is.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// use bevy_ecs::observer::{trigger_resource_changes, OnChangeResource};
+    ///
+    /// #[derive(Resource, Default)]
+    /// struct Settings {
+    ///     vsync: bool,
+    /// }
+    ///
+    /// #[derive(Resource, Default)]
+    /// struct Applied(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Settings>();
+    /// world.init_resource::<Applied>();
+    /// world.add_resource_observer::<Settings, _, _>(
+    ///     |_: Trigger<OnChangeResource>, mut applied: ResMut<Applied>| applied.0 += 1,
+    /// );
+    /// world.flush();
+    ///
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems(trigger_resource_changes);
+    /// // The first run sees the insertion of `Settings` as a change.
+    /// schedule.run(&mut world);
+    /// schedule.run(&mut world);
+    /// assert_eq!(world.resource::<Applied>().0, 1);
+    ///
+    /// world.resource_mut::<Settings>().vsync = true;
+    /// schedule.run(&mut world);
+    /// assert_eq!(world.resource::<Applied>().0, 2);
+    /// ```
+    pub fn add_resource_observer<R: Resource, E: Event, M>(
+        &mut self,
+        system: impl IntoObserverSystem<E, (), M>,
+    ) -> EntityWorldMut {
+        let id = self.components.register_resource::<R>();
+        self.spawn(Observer::new(system).with_component(id))
+    }
+
+    fn is_resource_present(&self, id: ComponentId) -> bool {
+        self.storages
+            .resources
+            .get(id)
+            .is_some_and(ResourceData::is_present)
+            || self
+                .storages
+                .non_send_resources
+                .get(id)
+                .is_some_and(ResourceData::is_present)
+    }
+
+    /// Runs the `on_replace` and `on_remove` hooks and observers of the resource `id`, if it is present.
+    ///
+    /// Returns `true` if anything ran, see [`World::trigger_resource_lifecycle`].
+    pub(crate) fn trigger_resource_removal(&mut self, id: ComponentId, non_send: bool) -> bool {
+        let present = if non_send {
+            self.storages
+                .non_send_resources
+                .get(id)
+                .map(ResourceData::is_present)
+        } else {
+            self.storages
+                .resources
+                .get(id)
+                .map(ResourceData::is_present)
+        };
+        if present != Some(true) {
+            return false;
+        }
+        let replaced = self.trigger_resource_lifecycle(id, ResourceLifecycle::Replace);
+        self.trigger_resource_lifecycle(id, ResourceLifecycle::Remove) | replaced
+    }
+
+    /// Runs the hook and observers of the resource `id` for `lifecycle`.
+    ///
+    /// Returns `true` if anything ran, in which case the world should be flushed once the operation
+    /// that caused `lifecycle` is complete.
+    pub(crate) fn trigger_resource_lifecycle(
+        &mut self,
+        id: ComponentId,
+        lifecycle: ResourceLifecycle,
+    ) -> bool {
+        // Skip the lookups below in the common case where no resource is hooked or observed.
+        if !self.resource_lifecycle_triggers {
+            return false;
+        }
+        let Some(info) = self.components.get_info(id) else {
+            return false;
+        };
+        let hooks = info.hooks();
+        let (hook, event) = match lifecycle {
+            ResourceLifecycle::Add => (hooks.on_add, TypeId::of::<OnAddResource>()),
+            ResourceLifecycle::Insert => (hooks.on_insert, TypeId::of::<OnInsertResource>()),
+            ResourceLifecycle::Replace => (hooks.on_replace, TypeId::of::<OnReplaceResource>()),
+            ResourceLifecycle::Remove => (hooks.on_remove, TypeId::of::<OnRemoveResource>()),
+        };
+        let event = self
+            .components
+            .get_id(event)
+            .filter(|&event| self.observers.try_get_observers(event).is_some());
+        if hook.is_none() && event.is_none() {
+            return false;
+        }
+
+        let mut world = DeferredWorld::from(self);
+        if let Some(hook) = hook {
+            hook(world.reborrow(), Entity::PLACEHOLDER, id);
+        }
+        if let Some(event) = event {
+            // SAFETY: The resource lifecycle events are zero-sized.
+            unsafe { world.trigger_observers(event, Entity::PLACEHOLDER, iter::once(id)) };
+        }
+        true
+    }
+}
+
+/// Returns `true` if `event` is one of the resource lifecycle events, like [`OnAddResource`].
+pub(crate) fn is_resource_lifecycle_event(components: &Components, event: ComponentId) -> bool {
+    components
+        .get_info(event)
+        .and_then(ComponentInfo::type_id)
+        .is_some_and(|type_id| {
+            [
+                TypeId::of::<OnAddResource>(),
+                TypeId::of::<OnInsertResource>(),
+                TypeId::of::<OnReplaceResource>(),
+                TypeId::of::<OnRemoveResource>(),
+            ]
+            .contains(&type_id)
+        })
+}
+
+/// Triggers [`OnChangeResource`] for each observed resource that changed since the last run of this system.
+///
+/// Only the resources that have an observer of [`OnChangeResource`] are checked, or every resource if
+/// there is a global observer of it. Like [`Res::is_changed`], inserting a resource counts as a change.
+///
+/// This system isn't run by default. In an `App`, add the `ResourceChangePlugin` to run it in `Last`.
+pub fn trigger_resource_changes(world: &mut World) {
+    let Some(event) = world.component_id::<OnChangeResource>() else {
+        return;
+    };
+    let Some(observers) = world.observers.try_get_observers(event) else {
+        return;
+    };
+    let (last_run, this_run) = (world.last_change_tick(), world.read_change_tick());
+    let is_changed = |id: ComponentId| {
+        let ticks = match world.storages.resources.get(id) {
+            Some(data) => data.get_ticks(),
+            None => world
+                .storages
+                .non_send_resources
+                .get(id)
+                .and_then(ResourceData::get_ticks),
+        };
+        ticks.is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+    };
+    let changed: Vec<ComponentId> = if observers.map.is_empty() {
+        observers
+            .component_observers
+            .keys()
+            .copied()
+            .filter(|&id| is_changed(id))
+            .collect()
+    } else {
+        world
+            .storages
+            .resources
+            .iter()
+            .map(|(id, _)| id)
+            .chain(world.storages.non_send_resources.iter().map(|(id, _)| id))
+            .filter(|&id| is_changed(id))
+            .collect()
+    };
+    if changed.is_empty() {
+        return;
+    }
+
+    let mut deferred = DeferredWorld::from(&mut *world);
+    for id in changed {
+        // SAFETY: `OnChangeResource` is zero-sized.
+        unsafe { deferred.trigger_observers(event, Entity::PLACEHOLDER, iter::once(id)) };
+    }
+    world.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        observer::{
+            OnAddResource, OnChangeResource, OnInsertResource, OnRemoveResource, OnReplaceResource,
+        },
+        prelude::*,
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Resource)]
+    struct Settings(u32);
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn log(message: &'static str) -> impl FnMut(Trigger<OnAddResource>, ResMut<Log>) {
+        move |_, mut log| log.0.push(message)
+    }
+
+    #[test]
+    fn resource_lifecycle() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world
+            .register_resource_hooks::<Settings>()
+            .on_add(|mut world, _, _| world.resource_mut::<Log>().0.push("hook add"))
+            .on_replace(|mut world, _, _| {
+                let value = world.resource::<Settings>().0;
+                world.resource_mut::<Log>().0.push(match value {
+                    0 => "hook replace 0",
+                    _ => "hook replace 1",
+                });
+            })
+            .on_remove(|mut world, _, _| world.resource_mut::<Log>().0.push("hook remove"));
+        world.add_resource_observer::<Settings, _, _>(log("add"));
+        world.add_resource_observer::<Settings, _, _>(
+            |_: Trigger<OnInsertResource>, mut log: ResMut<Log>| log.0.push("insert"),
+        );
+        world.add_resource_observer::<Settings, _, _>(
+            |_: Trigger<OnReplaceResource>, mut log: ResMut<Log>| log.0.push("replace"),
+        );
+        world.add_resource_observer::<Settings, _, _>(
+            |_: Trigger<OnRemoveResource>, mut commands: Commands| {
+                // Commands of observers are applied once the resource is removed.
+                commands.queue(|world: &mut World| {
+                    let present = world.contains_resource::<Settings>();
+                    world.resource_mut::<Log>().0.push(match present {
+                        true => "remove present",
+                        false => "remove",
+                    });
+                });
+            },
+        );
+        // Global observers see the events of every resource.
+        world.add_observer(|trigger: Trigger<OnAddResource>, mut log: ResMut<Log>| {
+            assert_eq!(trigger.entity(), Entity::PLACEHOLDER);
+            log.0.push("global add");
+        });
+        world.flush();
+
+        world.insert_resource(Settings(0));
+        world.insert_resource(Settings(1));
+        world.remove_resource::<Settings>();
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec![
+                "hook add",
+                "global add",
+                "add",
+                "insert",
+                "hook replace 0",
+                "replace",
+                "insert",
+                "hook replace 1",
+                "replace",
+                "hook remove",
+                "remove",
+            ]
+        );
+    }
+
+    #[test]
+    fn resource_observer_without_hooks() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        assert!(!world.resource_lifecycle_triggers);
+
+        world.add_resource_observer::<Settings, _, _>(log("add"));
+        world.flush();
+        assert!(world.resource_lifecycle_triggers);
+        world.insert_resource(Settings(0));
+        assert_eq!(world.resource::<Log>().0, vec!["add"]);
+    }
+
+    #[test]
+    fn resource_changes() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.insert_resource(Settings(0));
+        let settings = world.resource_id::<Settings>().unwrap();
+        // The global observer also sees the changes of `Log` itself.
+        world.add_observer(
+            move |trigger: Trigger<OnChangeResource>, mut log: ResMut<Log>| {
+                if trigger.components() == [settings] {
+                    log.0.push("changed");
+                }
+            },
+        );
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(super::trigger_resource_changes);
+        schedule.run(&mut world);
+        world.resource_mut::<Log>().0.clear();
+        schedule.run(&mut world);
+        assert!(world.resource::<Log>().0.is_empty());
+
+        world.resource_mut::<Settings>().0 = 1;
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec!["changed"]);
+    }
+}
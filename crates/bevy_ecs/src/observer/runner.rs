@@ -2,7 +2,7 @@ use core::any::Any;
 
 use crate::{
     component::{ComponentHook, ComponentHooks, ComponentId, StorageType},
-    observer::{ObserverDescriptor, ObserverTrigger},
+    observer::{ObserverDescriptor, ObserverOrdering, ObserverTrigger, Propagation},
     prelude::*,
     query::DebugCheckedUnwrap,
     system::{IntoObserverSystem, ObserverSystem},
@@ -15,6 +15,7 @@ use bevy_ptr::PtrMut;
 pub struct ObserverState {
     pub(crate) descriptor: ObserverDescriptor,
     pub(crate) runner: ObserverRunner,
+    pub(crate) ordering: ObserverOrdering,
     pub(crate) last_trigger_id: u32,
     pub(crate) despawned_watched_entities: u32,
 }
@@ -23,6 +24,7 @@ impl Default for ObserverState {
     fn default() -> Self {
         Self {
             runner: |_, _, _, _| {},
+            ordering: Default::default(),
             last_trigger_id: 0,
             despawned_watched_entities: 0,
             descriptor: Default::default(),
@@ -70,16 +72,12 @@ impl Component for ObserverState {
             });
         });
         hooks.on_remove(|mut world, entity, _| {
-            let descriptor = core::mem::take(
-                &mut world
-                    .entity_mut(entity)
-                    .get_mut::<ObserverState>()
-                    .unwrap()
-                    .as_mut()
-                    .descriptor,
-            );
+            let mut entity_mut = world.entity_mut(entity);
+            let mut state = entity_mut.get_mut::<ObserverState>().unwrap();
+            let descriptor = core::mem::take(&mut state.as_mut().descriptor);
+            let is_ordered = state.ordering.is_constrained();
             world.commands().queue(move |world: &mut World| {
-                world.unregister_observer(entity, descriptor);
+                world.unregister_observer(entity, descriptor, is_ordered);
             });
         });
     }
@@ -89,7 +87,7 @@ impl Component for ObserverState {
 ///
 /// Typically refers to the default runner that runs the system stored in the associated [`Observer`] component,
 /// but can be overridden for custom behavior.
-pub type ObserverRunner = fn(DeferredWorld, ObserverTrigger, PtrMut, propagate: &mut bool);
+pub type ObserverRunner = fn(DeferredWorld, ObserverTrigger, PtrMut, propagation: &mut Propagation);
 
 /// An [`Observer`] system. Add this [`Component`] to an [`Entity`] to turn it into an "observer".
 ///
@@ -259,6 +257,26 @@ pub type ObserverRunner = fn(DeferredWorld, ObserverTrigger, PtrMut, propagate:
 ///
 /// You can call [`Observer::watch_entity`] more than once, which allows you to watch multiple entities with the same [`Observer`].
 ///
+/// Observers of the same trigger run in an unspecified order by default. Like systems, they can be ordered relative to each other
+/// using [system sets](crate::schedule::SystemSet), or the observer functions themselves:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # let mut world = World::default();
+/// # #[derive(Event)]
+/// # struct Damage;
+/// #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct Gameplay;
+///
+/// fn apply_damage(trigger: Trigger<Damage>) {}
+/// fn flash_health_bar(trigger: Trigger<Damage>) {}
+/// fn play_sound(trigger: Trigger<Damage>) {}
+///
+/// world.spawn(Observer::new(flash_health_bar).after(Gameplay));
+/// world.spawn(Observer::new(apply_damage).in_set(Gameplay));
+/// world.spawn(Observer::new(play_sound).after(flash_health_bar));
+/// ```
+///
 /// When first added, [`Observer`] will also create an [`ObserverState`] component, which registers the observer with the [`World`] and
 /// serves as the "source of truth" of the observer.
 ///
@@ -266,6 +284,7 @@ pub type ObserverRunner = fn(DeferredWorld, ObserverTrigger, PtrMut, propagate:
 pub struct Observer {
     system: Box<dyn Any + Send + Sync + 'static>,
     descriptor: ObserverDescriptor,
+    ordering: ObserverOrdering,
     hook_on_add: ComponentHook,
 }
 
@@ -273,13 +292,38 @@ impl Observer {
     /// Creates a new [`Observer`], which defaults to a "global" observer. This means it will run whenever the event `E` is triggered
     /// for _any_ entity (or no entity).
     pub fn new<E: Event, B: Bundle, M, I: IntoObserverSystem<E, B, M>>(system: I) -> Self {
+        let system = IntoObserverSystem::into_system(system);
         Self {
-            system: Box::new(IntoObserverSystem::into_system(system)),
+            ordering: ObserverOrdering {
+                sets: system.default_system_sets(),
+                ..Default::default()
+            },
+            system: Box::new(system),
             descriptor: Default::default(),
             hook_on_add: hook_on_add::<E, B, I::System>,
         }
     }
 
+    /// Adds the [`Observer`] to the given system set, so other observers can be ordered relative to it.
+    ///
+    /// Observers are always part of the set of their system type, like systems.
+    pub fn in_set(mut self, set: impl SystemSet) -> Self {
+        self.ordering.sets.push(set.intern());
+        self
+    }
+
+    /// Runs the [`Observer`] before the observers in `set` when they are triggered together.
+    pub fn before<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        self.ordering.before.push(set.into_system_set().intern());
+        self
+    }
+
+    /// Runs the [`Observer`] after the observers in `set` when they are triggered together.
+    pub fn after<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        self.ordering.after.push(set.into_system_set().intern());
+        self
+    }
+
     /// Observe the given `entity`. This will cause the [`Observer`] to run whenever the [`Event`] is triggered
     /// for the `entity`.
     pub fn with_entity(mut self, entity: Entity) -> Self {
@@ -329,7 +373,7 @@ fn observer_system_runner<E: Event, B: Bundle, S: ObserverSystem<E, B>>(
     mut world: DeferredWorld,
     observer_trigger: ObserverTrigger,
     ptr: PtrMut,
-    propagate: &mut bool,
+    propagation: &mut Propagation,
 ) {
     let world = world.as_unsafe_world_cell();
     // SAFETY: Observer was triggered so must still exist in world
@@ -355,7 +399,7 @@ fn observer_system_runner<E: Event, B: Bundle, S: ObserverSystem<E, B>>(
     let trigger: Trigger<E, B> = Trigger::new(
         // SAFETY: Caller ensures `ptr` is castable to `&mut T`
         unsafe { ptr.deref_mut() },
-        propagate,
+        propagation,
         observer_trigger,
     );
     // SAFETY:
@@ -407,11 +451,12 @@ fn hook_on_add<E: Event, B: Bundle, S: ObserverSystem<E, B>>(
         };
 
         // Initialize System
-        let system: *mut dyn ObserverSystem<E, B> =
+        let (system, ordering): (*mut dyn ObserverSystem<E, B>, _) =
             if let Some(mut observe) = world.get_mut::<Observer>(entity) {
                 descriptor.merge(&observe.descriptor);
+                let ordering = observe.ordering.clone();
                 let system = observe.system.downcast_mut::<S>().unwrap();
-                &mut *system
+                (&mut *system, ordering)
             } else {
                 return;
             };
@@ -426,6 +471,7 @@ fn hook_on_add<E: Event, B: Bundle, S: ObserverSystem<E, B>>(
                 entry.insert(ObserverState {
                     descriptor,
                     runner: observer_system_runner::<E, B, S>,
+                    ordering,
                     ..Default::default()
                 });
             }
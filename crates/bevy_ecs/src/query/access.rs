@@ -1103,6 +1103,21 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.required.is_subset(&other.required) && self.access().is_subset(other.access())
     }
 
+    /// Returns `true` if this access explicitly mentions the element given by `index`,
+    /// either by accessing it (as in `&T`, `Option<&T>` or `Has<T>`) or by filtering on it (as in `With<T>` or `Without<T>`).
+    ///
+    /// Access to all components (as in `EntityRef`) does not count as mentioning any particular component.
+    pub fn contains(&self, index: T) -> bool {
+        let index = index.sparse_set_index();
+        (!self.access.component_read_and_writes_inverted
+            && self.access.component_read_and_writes.contains(index))
+            || self.access.archetypal.contains(index)
+            || self
+                .filter_sets
+                .iter()
+                .any(|f| f.with.contains(index) || f.without.contains(index))
+    }
+
     /// Returns the indices of the elements that this access filters for.
     pub fn with_filters(&self) -> impl Iterator<Item = T> + '_ {
         self.filter_sets
@@ -75,6 +75,8 @@ pub struct QueryState<D: QueryData, F: QueryFilter = ()> {
     // Represents whether this query iteration is dense or not. When this is true
     // `matched_storage_ids` stores `TableId`s, otherwise it stores `ArchetypeId`s.
     pub(super) is_dense: bool,
+    // Whether `matched_storage_ids` is sorted for a world with a deterministic query order.
+    matched_storage_ids_sorted: bool,
     pub(crate) fetch_state: D::State,
     pub(crate) filter_state: F::State,
     #[cfg(feature = "trace")]
@@ -197,7 +199,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     fn new_uninitialized(world: &mut World) -> Self {
         let fetch_state = D::init_state(world);
         let filter_state = F::init_state(world);
-        Self::from_states_uninitialized(world.id(), fetch_state, filter_state)
+        Self::from_states_uninitialized(world, fetch_state, filter_state)
     }
 
     /// Creates a new [`QueryState`] but does not populate it with the matched results from the World yet
@@ -208,7 +210,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         let fetch_state = D::get_state(world.components())?;
         let filter_state = F::get_state(world.components())?;
         Some(Self::from_states_uninitialized(
-            world.id(),
+            world,
             fetch_state,
             filter_state,
         ))
@@ -219,7 +221,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     /// `new_archetype` and its variants must be called on all of the World's archetypes before the
     /// state can return valid query results.
     fn from_states_uninitialized(
-        world_id: WorldId,
+        world: &World,
         fetch_state: <D as WorldQuery>::State,
         filter_state: <F as WorldQuery>::State,
     ) -> Self {
@@ -236,15 +238,20 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
 
+        // Hide entities with disabling components, unless the query explicitly asks for them.
+        let default_filters = world.default_query_filters();
+        default_filters.modify_access(&mut component_access);
+
         // For queries without dynamic filters the dense-ness of the query is equal to the dense-ness
-        // of its static type parameters.
-        let is_dense = D::IS_DENSE && F::IS_DENSE;
+        // of its static type parameters and of the default filters.
+        let is_dense = D::IS_DENSE && F::IS_DENSE && default_filters.is_dense(world.components());
 
         Self {
-            world_id,
+            world_id: world.id(),
             archetype_generation: ArchetypeGeneration::initial(),
             matched_storage_ids: Vec::new(),
             is_dense,
+            matched_storage_ids_sorted: true,
             fetch_state,
             filter_state,
             component_access,
@@ -265,15 +272,20 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         let filter_state = F::init_state(builder.world_mut());
         D::set_access(&mut fetch_state, builder.access());
 
+        let mut component_access = builder.access().clone();
+        let default_filters = builder.world().default_query_filters();
+        default_filters.modify_access(&mut component_access);
+
         let mut state = Self {
             world_id: builder.world().id(),
             archetype_generation: ArchetypeGeneration::initial(),
             matched_storage_ids: Vec::new(),
-            // For dynamic queries the dense-ness is given by the query builder.
-            is_dense: builder.is_dense(),
+            // For dynamic queries the dense-ness is given by the query builder and the default filters.
+            is_dense: builder.is_dense() && default_filters.is_dense(builder.world().components()),
+            matched_storage_ids_sorted: true,
             fetch_state,
             filter_state,
-            component_access: builder.access().clone(),
+            component_access,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             #[cfg(feature = "trace")]
@@ -440,6 +452,36 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             }
             self.archetype_generation = world.archetypes().generation();
         }
+        self.sort_matched_storages(world);
+    }
+
+    /// Sorts the matched tables or archetypes by their [`ComponentId`]s if the `world` iterates
+    /// queries in a deterministic order. See [`World::set_deterministic_query_order`].
+    pub(crate) fn sort_matched_storages(&mut self, world: UnsafeWorldCell) {
+        if self.matched_storage_ids_sorted || !world.deterministic_query_order() {
+            return;
+        }
+        if self.is_dense {
+            // SAFETY: Only the metadata of the tables is accessed.
+            let tables = unsafe { &world.storages().tables };
+            self.matched_storage_ids.sort_by_cached_key(|id| {
+                // SAFETY: `matched_storage_ids` stores `TableId`s when the query is dense.
+                let mut components: Vec<_> = tables[unsafe { id.table_id }].components().collect();
+                components.sort_unstable();
+                components
+            });
+        } else {
+            let archetypes = world.archetypes();
+            self.matched_storage_ids.sort_by_cached_key(|id| {
+                // SAFETY: `matched_storage_ids` stores `ArchetypeId`s when the query is not dense.
+                let mut components: Vec<_> = archetypes[unsafe { id.archetype_id }]
+                    .components()
+                    .collect();
+                components.sort_unstable();
+                components
+            });
+        }
+        self.matched_storage_ids_sorted = true;
     }
 
     /// # Panics
@@ -503,6 +545,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
                     self.matched_storage_ids.push(StorageId {
                         archetype_id: archetype.id(),
                     });
+                    self.matched_storage_ids_sorted = false;
                 }
             }
             let table_index = archetype.table_id().as_usize();
@@ -512,6 +555,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
                     self.matched_storage_ids.push(StorageId {
                         table_id: archetype.table_id(),
                     });
+                    self.matched_storage_ids_sorted = false;
                 }
             }
             true
@@ -634,6 +678,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             archetype_generation: self.archetype_generation,
             matched_storage_ids: self.matched_storage_ids.clone(),
             is_dense: self.is_dense,
+            matched_storage_ids_sorted: self.matched_storage_ids_sorted,
             fetch_state,
             filter_state,
             component_access: self.component_access.clone(),
@@ -756,6 +801,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             archetype_generation: self.archetype_generation,
             matched_storage_ids,
             is_dense,
+            matched_storage_ids_sorted: false,
             fetch_state: new_fetch_state,
             filter_state: new_filter_state,
             component_access: joined_component_access,
@@ -2189,4 +2235,25 @@ mod tests {
         let query_2 = QueryState::<&B, Without<C>>::new(&mut world);
         let _: QueryState<Entity, Changed<C>> = query_1.join_filtered(&world, &query_2);
     }
+
+    #[test]
+    fn deterministic_query_order() {
+        fn values(first: bool) -> Vec<usize> {
+            let mut world = World::new();
+            world.register_component::<B>();
+            world.register_component::<C>();
+            let mut query = world.query::<&A>();
+            if first {
+                world.spawn((A(1), B(0)));
+                world.spawn((A(2), C(0)));
+            } else {
+                world.spawn((A(2), C(0)));
+                world.spawn((A(1), B(0)));
+            }
+            world.set_deterministic_query_order(true);
+            query.iter(&world).map(|a| a.0).collect()
+        }
+
+        assert_eq!(values(true), values(false));
+    }
 }
@@ -0,0 +1,455 @@
+//! This module provides functionality to link entities to each other using specialized components called "relationships".
+//! See the [`Relationship`] trait for more info.
+
+mod related_methods;
+mod relationship_query;
+mod relationship_source_collection;
+
+pub use related_methods::*;
+pub use relationship_query::*;
+pub use relationship_source_collection::*;
+
+use crate::{
+    component::{Component, ComponentId},
+    entity::Entity,
+    traversal::Traversal,
+    world::{DeferredWorld, World},
+};
+use bevy_utils::tracing::warn;
+
+/// A [`Component`] on a "source" [`Entity`] that references another target [`Entity`], creating a "relationship" between them.
+/// Every [`Relationship`] has a corresponding [`RelationshipTarget`] type (and vice-versa), which exists on the "target" entity
+/// of a relationship and contains the complete list of "source" entities that relate to the target.
+///
+/// The [`Relationship`] component is the "source of truth" and the [`RelationshipTarget`] component reflects that source of truth.
+/// When a [`Relationship`] component is inserted on an [`Entity`], the corresponding [`RelationshipTarget`] component is immediately
+/// inserted on the target component if it does not already exist, and the "source" entity is automatically added to the
+/// [`RelationshipTarget`] collection (this is done via "component hooks").
+///
+/// A common example of a [`Relationship`] is an "ownership" link, where the `OwnedBy` component on an item points
+/// to its owner, and the owner's `Inventory` component lists every item that points to it:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// #[relationship(relationship_target = Inventory)]
+/// pub struct OwnedBy(pub Entity);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = OwnedBy)]
+/// pub struct Inventory(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// let owner = world.spawn_empty().id();
+/// let sword = world.spawn(OwnedBy(owner)).id();
+/// let shield = world.spawn(OwnedBy(owner)).id();
+/// world.flush();
+///
+/// let inventory = world.get::<Inventory>(owner).unwrap();
+/// assert_eq!(inventory.iter().collect::<Vec<_>>(), [sword, shield]);
+/// ```
+///
+/// By default, despawning the target only removes the [`Relationship`] from the sources. When the target's lifetime should
+/// be propagated to its sources, add `linked_spawn` to the [`RelationshipTarget`] attribute
+/// (`#[relationship_target(relationship = OwnedBy, linked_spawn)]`), which despawns every source when the target is despawned.
+///
+/// [`Relationship`] and [`RelationshipTarget`] should only be modified by inserting and removing the [`Relationship`] component.
+/// Mutating a [`Relationship`] in place (for example through a [`Mut`](crate::change_detection::Mut) from a query) bypasses
+/// the hooks that keep both sides in sync.
+pub trait Relationship: Component + Sized {
+    /// The [`Component`] added to the "target" entities of this [`Relationship`], which contains the list of all "source"
+    /// entities that relate to the "target".
+    type RelationshipTarget: RelationshipTarget<Relationship = Self>;
+
+    /// Gets the [`Entity`] ID of the related entity.
+    fn get(&self) -> Entity;
+
+    /// Creates this [`Relationship`] from the given `entity`.
+    fn from(entity: Entity) -> Self;
+
+    /// The `on_insert` component hook that maintains the [`Relationship`] / [`RelationshipTarget`] connection.
+    fn on_insert(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let target_entity = world.entity(entity).get::<Self>().unwrap().get();
+        if target_entity == entity {
+            warn!(
+                "The {}({target_entity:?}) relationship on entity {entity:?} points to itself. The invalid {} relationship has been removed.",
+                core::any::type_name::<Self>(),
+                core::any::type_name::<Self>()
+            );
+            world.commands().entity(entity).remove::<Self>();
+            return;
+        }
+        if world.get_entity(target_entity).is_err() {
+            warn!(
+                "The {}({target_entity:?}) relationship on entity {entity:?} relates to an entity that does not exist. The invalid {} relationship has been removed.",
+                core::any::type_name::<Self>(),
+                core::any::type_name::<Self>()
+            );
+            world.commands().entity(entity).remove::<Self>();
+            return;
+        }
+        if let Some(mut relationship_target) =
+            world.get_mut::<Self::RelationshipTarget>(target_entity)
+        {
+            relationship_target.collection_mut_risky().add(entity);
+        } else {
+            // The target component is inserted through a command, as hooks cannot change an entity's archetype.
+            world.commands().queue(move |world: &mut World| {
+                let points_to_target = world
+                    .get::<Self>(entity)
+                    .is_some_and(|relationship| relationship.get() == target_entity);
+                if !points_to_target {
+                    return;
+                }
+                let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity) else {
+                    return;
+                };
+                if let Some(mut relationship_target) =
+                    target_entity_mut.get_mut::<Self::RelationshipTarget>()
+                {
+                    relationship_target.collection_mut_risky().add(entity);
+                } else {
+                    let mut collection =
+                        <<Self::RelationshipTarget as RelationshipTarget>::Collection as RelationshipSourceCollection>::with_capacity(1);
+                    collection.add(entity);
+                    target_entity_mut
+                        .insert(Self::RelationshipTarget::from_collection_risky(collection));
+                }
+            });
+        }
+    }
+
+    /// The `on_replace` component hook that maintains the [`Relationship`] / [`RelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_replace(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let target_entity = world.entity(entity).get::<Self>().unwrap().get();
+        let Some(mut relationship_target) =
+            world.get_mut::<Self::RelationshipTarget>(target_entity)
+        else {
+            return;
+        };
+        relationship_target.collection_mut_risky().remove(entity);
+        if relationship_target.len() == 0 {
+            world.commands().queue(move |world: &mut World| {
+                let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity) else {
+                    return;
+                };
+                // The collection may have been refilled by the time this command is applied.
+                if target_entity_mut
+                    .get::<Self::RelationshipTarget>()
+                    .is_some_and(RelationshipTarget::is_empty)
+                {
+                    target_entity_mut.remove::<Self::RelationshipTarget>();
+                }
+            });
+        }
+    }
+}
+
+/// A [`Component`] containing the collection of entities that relate to this [`Entity`] via the associated `Relationship` type.
+/// See the [`Relationship`] documentation for more information.
+pub trait RelationshipTarget: Component + Sized {
+    /// If this is true, despawning this entity will also despawn the related entities targeting it.
+    ///
+    /// For example, this is set to `true` for hierarchy-style relationships, where "child" entities should not outlive their "parent".
+    /// It should be `false` when the related entities have a meaningful existence of their own.
+    const LINKED_SPAWN: bool;
+
+    /// The [`Relationship`] that populates this [`RelationshipTarget`] collection.
+    type Relationship: Relationship<RelationshipTarget = Self>;
+
+    /// The collection type that stores the "source" entities for this [`RelationshipTarget`] component.
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`RelationshipTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`RelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called outside of the [`Relationship`] implementation because it can invalidate
+    /// assumptions about the relationship's internal consistency.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`RelationshipTarget`] from the given [`RelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called outside of the [`Relationship`] implementation because it can invalidate
+    /// assumptions about the relationship's internal consistency.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// The `on_replace` component hook that maintains the [`Relationship`] / [`RelationshipTarget`] connection.
+    ///
+    /// Removes the [`Relationship`] from every "source" entity that still points to this entity.
+    // note: think of this as "on_drop"
+    fn on_replace(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let sources: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        world.commands().queue(move |world: &mut World| {
+            for source_entity in sources {
+                let Ok(mut source_entity_mut) = world.get_entity_mut(source_entity) else {
+                    continue;
+                };
+                // The source may have been related to another entity in the meantime.
+                if source_entity_mut
+                    .get::<Self::Relationship>()
+                    .is_some_and(|relationship| relationship.get() == entity)
+                {
+                    source_entity_mut.remove::<Self::Relationship>();
+                }
+            }
+        });
+    }
+
+    /// The `on_despawn` component hook that despawns entities stored in an entity's [`RelationshipTarget`] when
+    /// that entity is despawned. This is only registered when [`RelationshipTarget::LINKED_SPAWN`] is `true`.
+    fn on_despawn(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        if !Self::LINKED_SPAWN {
+            return;
+        }
+        let sources: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        let mut commands = world.commands();
+        for source_entity in sources {
+            commands.entity(source_entity).try_despawn();
+        }
+    }
+
+    /// Returns an iterator over the "source" entities stored in this [`RelationshipTarget`].
+    #[inline]
+    fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.collection().iter()
+    }
+
+    /// Returns the number of entities in this [`RelationshipTarget`].
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns `true` if this [`RelationshipTarget`] is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+}
+
+/// Traverses a [`Relationship`] from the "source" entity to its "target" entity, which allows
+/// [observer events](crate::observer::Trigger::propagate) to bubble up along any relationship.
+impl<R: Relationship> Traversal for &R {
+    fn traverse(item: Self::Item<'_>) -> Option<Entity> {
+        Some(item.get())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{
+        entity::Entity,
+        event::Event,
+        observer::Trigger,
+        prelude::Component,
+        relationship::{Relationship, RelationshipTarget},
+        system::{Query, ResMut, Resource, SystemState},
+        world::World,
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component, Debug, PartialEq, Eq)]
+    #[relationship(relationship_target = Inventory)]
+    struct OwnedBy(Entity);
+
+    #[derive(Component, Debug)]
+    #[relationship_target(relationship = OwnedBy)]
+    struct Inventory(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Attachments)]
+    struct AttachedTo {
+        entity: Entity,
+    }
+
+    #[derive(Component)]
+    #[relationship_target(relationship = AttachedTo, linked_spawn)]
+    struct Attachments {
+        entities: Vec<Entity>,
+    }
+
+    #[test]
+    fn relationship_target_tracks_sources() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        let a = world.spawn(OwnedBy(owner)).id();
+        let b = world.spawn(OwnedBy(owner)).id();
+        world.flush();
+        assert_eq!(world.get::<Inventory>(owner).unwrap().0, vec![a, b]);
+
+        world.entity_mut(a).remove::<OwnedBy>();
+        world.flush();
+        assert_eq!(world.get::<Inventory>(owner).unwrap().0, vec![b]);
+
+        world.entity_mut(b).remove::<OwnedBy>();
+        world.flush();
+        assert!(world.get::<Inventory>(owner).is_none());
+    }
+
+    #[test]
+    fn replacing_relationship_moves_source() {
+        let mut world = World::new();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        let item = world.spawn(OwnedBy(first)).id();
+        world.flush();
+
+        world.entity_mut(item).insert(OwnedBy(second));
+        world.flush();
+        assert!(world.get::<Inventory>(first).is_none());
+        assert_eq!(world.get::<Inventory>(second).unwrap().0, vec![item]);
+    }
+
+    #[test]
+    fn invalid_relationships_are_removed() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        world.entity_mut(entity).insert(OwnedBy(entity));
+        world.flush();
+        assert!(world.get::<OwnedBy>(entity).is_none());
+        assert!(world.get::<Inventory>(entity).is_none());
+
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+        let item = world.spawn(OwnedBy(missing)).id();
+        world.flush();
+        assert!(world.get::<OwnedBy>(item).is_none());
+    }
+
+    #[test]
+    fn despawning_target_orphans_sources() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        let item = world.spawn(OwnedBy(owner)).id();
+        world.flush();
+
+        world.despawn(owner);
+        assert!(world.get_entity(item).is_ok());
+        assert!(world.get::<OwnedBy>(item).is_none());
+    }
+
+    #[test]
+    fn linked_spawn_despawns_recursively() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let child = world.spawn(AttachedTo { entity: root }).id();
+        let grandchild = world.spawn(AttachedTo { entity: child }).id();
+        let unrelated = world.spawn_empty().id();
+        world.flush();
+
+        world.despawn(root);
+        assert!(world.get_entity(child).is_err());
+        assert!(world.get_entity(grandchild).is_err());
+        assert!(world.get_entity(unrelated).is_ok());
+    }
+
+    #[test]
+    fn with_related_and_despawn_related() {
+        let mut world = World::new();
+        let mut owner = world.spawn_empty();
+        let mut items = Vec::new();
+        owner.with_related::<OwnedBy>(|spawner| {
+            items.push(spawner.spawn_empty().id());
+            items.push(spawner.spawn_empty().id());
+        });
+        let owner = owner.id();
+        world.flush();
+        assert_eq!(world.get::<Inventory>(owner).unwrap().0, items);
+
+        world.entity_mut(owner).despawn_related::<Inventory>();
+        assert!(world.get_entity(owner).is_ok());
+        assert!(items.iter().all(|item| world.get_entity(*item).is_err()));
+        assert!(world.get::<Inventory>(owner).is_none());
+    }
+
+    #[test]
+    fn add_related_with_commands() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        let item = world.spawn_empty().id();
+        world
+            .commands()
+            .entity(owner)
+            .add_related::<OwnedBy>(&[item]);
+        world.flush();
+        assert_eq!(world.get::<OwnedBy>(item), Some(&OwnedBy(owner)));
+        assert_eq!(world.get::<Inventory>(owner).unwrap().0, vec![item]);
+    }
+
+    #[test]
+    fn relationship_queries() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let child = world.spawn(AttachedTo { entity: root }).id();
+        let grandchild = world.spawn(AttachedTo { entity: child }).id();
+        world.flush();
+
+        let mut state = SystemState::<(Query<&AttachedTo>, Query<&Attachments>)>::new(&mut world);
+        let (attached_to, attachments) = state.get(&world);
+        assert_eq!(attached_to.related(grandchild), Some(child));
+        assert_eq!(attached_to.related_root(grandchild), root);
+        assert_eq!(
+            attached_to
+                .iter_related_ancestors(grandchild)
+                .collect::<Vec<_>>(),
+            [child, root]
+        );
+        assert_eq!(
+            attachments.relationship_sources(root).collect::<Vec<_>>(),
+            [child]
+        );
+        assert_eq!(
+            attachments
+                .iter_related_descendants(root)
+                .collect::<Vec<_>>(),
+            [child, grandchild]
+        );
+    }
+
+    #[test]
+    fn events_propagate_along_relationships() {
+        #[derive(Component)]
+        struct Ping;
+
+        impl Event for Ping {
+            type Traversal = &'static OwnedBy;
+
+            const AUTO_PROPAGATE: bool = true;
+        }
+
+        #[derive(Resource, Default)]
+        struct Visited(Vec<Entity>);
+
+        let mut world = World::new();
+        world.init_resource::<Visited>();
+        world.add_observer(|trigger: Trigger<Ping>, mut visited: ResMut<Visited>| {
+            visited.0.push(trigger.entity());
+        });
+        let owner = world.spawn_empty().id();
+        let item = world.spawn(OwnedBy(owner)).id();
+        world.flush();
+
+        world.trigger_targets(Ping, item);
+        world.flush();
+        assert_eq!(world.resource::<Visited>().0, vec![item, owner]);
+    }
+
+    #[test]
+    fn relationship_target_collection() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        let item = world.spawn(OwnedBy(owner)).id();
+        world.flush();
+
+        let inventory = world.get::<Inventory>(owner).unwrap();
+        assert_eq!(inventory.len(), 1);
+        assert_eq!(inventory.iter().collect::<Vec<_>>(), [item]);
+        assert_eq!(world.get::<OwnedBy>(item).unwrap().get(), owner);
+    }
+}
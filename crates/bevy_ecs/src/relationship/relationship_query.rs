@@ -0,0 +1,164 @@
+use crate::{
+    entity::Entity,
+    query::{QueryData, QueryFilter, WorldQuery},
+    relationship::{Relationship, RelationshipTarget},
+    system::Query,
+};
+use alloc::collections::VecDeque;
+
+impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
+    /// If the given `entity` contains the `R` [`Relationship`] component, returns the
+    /// target entity of that relationship.
+    pub fn related<R: Relationship>(&'w self, entity: Entity) -> Option<Entity>
+    where
+        <D as QueryData>::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+    {
+        self.get(entity).map(R::get).ok()
+    }
+
+    /// If the given `entity` contains the `S` [`RelationshipTarget`] component, returns the
+    /// source entities stored on that component.
+    pub fn relationship_sources<S: RelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: WorldQuery<Item<'w> = &'w S>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(RelationshipTarget::iter)
+    }
+
+    /// Recursively walks up the tree defined by the given `R` [`Relationship`] until
+    /// there are no more related entities, returning the "root entity" of the relationship hierarchy.
+    ///
+    /// # Warning
+    ///
+    /// For relationship graphs that contain loops, this could loop infinitely.
+    /// If your relationship is not a tree (like Bevy's hierarchy), be sure to stop if you encounter a duplicate entity.
+    pub fn related_root<R: Relationship>(&'w self, entity: Entity) -> Entity
+    where
+        <D as QueryData>::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+    {
+        // Recursively search up the tree until we're out of parents
+        match self.get(entity) {
+            Ok(relationship) => self.related_root(relationship.get()),
+            Err(_) => entity,
+        }
+    }
+
+    /// Iterates all "ancestors" of the given `entity` as defined by the `R` [`Relationship`].
+    ///
+    /// # Warning
+    ///
+    /// For relationship graphs that contain loops, this could loop infinitely.
+    /// If your relationship is not a tree (like Bevy's hierarchy), be sure to stop if you encounter a duplicate entity.
+    pub fn iter_related_ancestors<R: Relationship>(
+        &'w self,
+        entity: Entity,
+    ) -> AncestorIter<'w, 's, D, F, R>
+    where
+        <D as QueryData>::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+    {
+        AncestorIter::new(self, entity)
+    }
+
+    /// Iterates all "descendant" entities of the given `entity`, as defined by the `S` [`RelationshipTarget`],
+    /// in breadth-first order.
+    ///
+    /// # Warning
+    ///
+    /// For relationship graphs that contain loops, this could loop infinitely.
+    /// If your relationship is not a tree (like Bevy's hierarchy), be sure to stop if you encounter a duplicate entity.
+    pub fn iter_related_descendants<S: RelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> DescendantIter<'w, 's, D, F, S>
+    where
+        <D as QueryData>::ReadOnly: WorldQuery<Item<'w> = &'w S>,
+    {
+        DescendantIter::new(self, entity)
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`], as defined by a [`RelationshipTarget`].
+///
+/// Traverses the relationship graph breadth-first.
+pub struct DescendantIter<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w S>,
+{
+    children_query: &'w Query<'w, 's, D, F>,
+    vecdeque: VecDeque<Entity>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget> DescendantIter<'w, 's, D, F, S>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w S>,
+{
+    /// Returns a new [`DescendantIter`].
+    pub fn new(children_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        DescendantIter {
+            children_query,
+            vecdeque: children_query
+                .get(entity)
+                .into_iter()
+                .flat_map(RelationshipTarget::iter)
+                .collect(),
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget> Iterator
+    for DescendantIter<'w, 's, D, F, S>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w S>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+
+        if let Ok(children) = self.children_query.get(entity) {
+            self.vecdeque.extend(children.iter());
+        }
+
+        Some(entity)
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s over the ancestors of an [`Entity`], as defined by a [`Relationship`].
+pub struct AncestorIter<'w, 's, D: QueryData, F: QueryFilter, R: Relationship>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+{
+    parent_query: &'w Query<'w, 's, D, F>,
+    next: Option<Entity>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: Relationship> AncestorIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+{
+    /// Returns a new [`AncestorIter`].
+    pub fn new(parent_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        AncestorIter {
+            parent_query,
+            next: Some(entity),
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: Relationship> Iterator
+    for AncestorIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next = self.parent_query.get(self.next?).ok().map(R::get);
+        self.next
+    }
+}
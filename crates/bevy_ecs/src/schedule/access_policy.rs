@@ -0,0 +1,247 @@
+use alloc::{format, string::String, vec::Vec};
+use core::any::TypeId;
+
+use bevy_utils::HashMap;
+
+use crate::{
+    component::{Component, ComponentId, Components},
+    query::Access,
+    system::{Resource, System},
+};
+
+/// How much access an [`AccessPolicy`] grants to a component or resource.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AccessLevel {
+    /// The data can't be accessed.
+    None,
+    /// The data can only be read.
+    Read,
+    /// The data can be read and written.
+    Write,
+}
+
+/// Restricts which components and resources the systems of a [`SystemSet`](super::SystemSet) can access.
+///
+/// Policies are attached to a set with [`Schedules::set_access_policy`](super::Schedules::set_access_policy),
+/// and are checked against the access of each system and run condition in the set when a schedule is built.
+/// Violations are reported with a [`ScheduleBuildError::AccessPolicyViolation`](super::ScheduleBuildError::AccessPolicyViolation)
+/// error, so a system can't be added to a set that it would break the policy of.
+///
+/// This makes it possible to catch systems of a set that access more than they are meant to, like the systems of mods:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::schedule::{AccessLevel, AccessPolicy};
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct ModData(u32);
+///
+/// #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct ModSystems;
+///
+/// let mut world = World::new();
+/// world.get_resource_or_init::<Schedules>().set_access_policy(
+///     ModSystems,
+///     AccessPolicy::deny_all()
+///         .with_component::<Health>(AccessLevel::Read)
+///         .with_component::<ModData>(AccessLevel::Write),
+/// );
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(
+///     (|health: Query<&Health>, mut data: Query<&mut ModData>| {}).in_set(ModSystems),
+/// );
+/// assert!(schedule.initialize(&mut world).is_ok());
+///
+/// schedule.add_systems((|mut health: Query<&mut Health>| {}).in_set(ModSystems));
+/// assert!(schedule.initialize(&mut world).is_err());
+/// ```
+///
+/// [`Commands`](crate::system::Commands) and exclusive systems can access any data, so they are only allowed
+/// if the policy allows [deferred](AccessPolicy::allow_deferred) and [exclusive](AccessPolicy::allow_exclusive)
+/// systems. Observers, and systems added to the schedule outside of the set, are not restricted by the schedule,
+/// but [`system_violations`](Self::system_violations) can be used to check them.
+///
+/// Policies are not a security boundary: code with access to the [`World`](crate::world::World) can
+/// replace them or access data directly. Only run code that you trust.
+#[derive(Clone, Debug)]
+pub struct AccessPolicy {
+    default_level: AccessLevel,
+    components: Vec<(TypeId, AccessLevel)>,
+    resources: Vec<(TypeId, AccessLevel)>,
+    allow_deferred: bool,
+    allow_exclusive: bool,
+}
+
+impl AccessPolicy {
+    /// Creates a policy that grants [`AccessLevel::Write`] to every component and resource, and allows
+    /// deferred and exclusive systems.
+    ///
+    /// Use [`with_component`](Self::with_component) and [`with_resource`](Self::with_resource) to
+    /// protect specific data.
+    pub fn allow_all() -> Self {
+        Self {
+            default_level: AccessLevel::Write,
+            components: Vec::new(),
+            resources: Vec::new(),
+            allow_deferred: true,
+            allow_exclusive: true,
+        }
+    }
+
+    /// Creates a policy that grants [`AccessLevel::None`] to every component and resource, and denies
+    /// deferred and exclusive systems.
+    ///
+    /// Use [`with_component`](Self::with_component) and [`with_resource`](Self::with_resource) to
+    /// allow access to specific data.
+    pub fn deny_all() -> Self {
+        Self {
+            default_level: AccessLevel::None,
+            components: Vec::new(),
+            resources: Vec::new(),
+            allow_deferred: false,
+            allow_exclusive: false,
+        }
+    }
+
+    /// Sets the access granted to the component `T`.
+    pub fn with_component<T: Component>(mut self, level: AccessLevel) -> Self {
+        self.components.push((TypeId::of::<T>(), level));
+        self
+    }
+
+    /// Sets the access granted to the resource `T`.
+    pub fn with_resource<T: Resource>(mut self, level: AccessLevel) -> Self {
+        self.resources.push((TypeId::of::<T>(), level));
+        self
+    }
+
+    /// Sets whether systems with deferred parameters, like [`Commands`](crate::system::Commands), are allowed.
+    pub fn allow_deferred(mut self, allow: bool) -> Self {
+        self.allow_deferred = allow;
+        self
+    }
+
+    /// Sets whether exclusive systems are allowed.
+    pub fn allow_exclusive(mut self, allow: bool) -> Self {
+        self.allow_exclusive = allow;
+        self
+    }
+
+    /// Returns a description of each way `system` violates this policy, or an empty list if it doesn't.
+    ///
+    /// The access of `system` is only known once it is [initialized](System::initialize).
+    pub fn system_violations<S: System + ?Sized>(
+        &self,
+        components: &Components,
+        system: &S,
+    ) -> Vec<String> {
+        self.violations(
+            components,
+            system.component_access(),
+            system.is_exclusive(),
+            system.has_deferred(),
+        )
+    }
+
+    /// Returns a description of each way a system with the given properties violates this policy.
+    pub(crate) fn violations(
+        &self,
+        components: &Components,
+        access: &Access<ComponentId>,
+        is_exclusive: bool,
+        has_deferred: bool,
+    ) -> Vec<String> {
+        let mut violations = Vec::new();
+        if is_exclusive {
+            if !self.allow_exclusive {
+                violations.push("is an exclusive system".into());
+            }
+            // Exclusive systems have access to the whole world anyway.
+            return violations;
+        }
+        if has_deferred && !self.allow_deferred {
+            violations.push("has deferred parameters".into());
+        }
+
+        let name = |id: ComponentId| components.get_name(id).unwrap_or("<unknown>");
+        // Types that were never registered can't be accessed by any system.
+        let explicit_components: HashMap<ComponentId, AccessLevel> = self
+            .components
+            .iter()
+            .filter_map(|&(type_id, level)| Some((components.get_id(type_id)?, level)))
+            .collect();
+        let explicit_resources: HashMap<ComponentId, AccessLevel> = self
+            .resources
+            .iter()
+            .filter_map(|&(type_id, level)| Some((components.get_resource_id(type_id)?, level)))
+            .collect();
+
+        for (&id, &level) in &explicit_components {
+            if level < AccessLevel::Write && access.has_component_write(id) {
+                violations.push(format!("writes component `{}`", name(id)));
+            } else if level < AccessLevel::Read && access.has_component_read(id) {
+                violations.push(format!("reads component `{}`", name(id)));
+            }
+        }
+        for (&id, &level) in &explicit_resources {
+            if level < AccessLevel::Write && access.has_resource_write(id) {
+                violations.push(format!("writes resource `{}`", name(id)));
+            } else if level < AccessLevel::Read && access.has_resource_read(id) {
+                violations.push(format!("reads resource `{}`", name(id)));
+            }
+        }
+
+        if self.default_level < AccessLevel::Write {
+            let (writes, all_writes) = access.component_writes();
+            if all_writes {
+                violations.push("writes all components".into());
+            } else {
+                violations.extend(
+                    writes
+                        .filter(|id| !explicit_components.contains_key(id))
+                        .map(|id| format!("writes component `{}`", name(id))),
+                );
+            }
+            if access.has_write_all_resources() {
+                violations.push("writes all resources".into());
+            } else {
+                violations.extend(
+                    access
+                        .resource_writes()
+                        .filter(|id| !explicit_resources.contains_key(id))
+                        .map(|id| format!("writes resource `{}`", name(id))),
+                );
+            }
+        }
+        if self.default_level < AccessLevel::Read {
+            let (reads, all_reads) = access.component_reads_and_writes();
+            if all_reads {
+                violations.push("reads all components".into());
+            } else {
+                violations.extend(
+                    reads
+                        .filter(|id| {
+                            !explicit_components.contains_key(id)
+                                && !access.has_component_write(*id)
+                        })
+                        .map(|id| format!("reads component `{}`", name(id))),
+                );
+            }
+            if access.has_read_all_resources() {
+                violations.push("reads all resources".into());
+            } else {
+                violations.extend(
+                    access
+                        .resource_reads()
+                        .filter(|id| !explicit_resources.contains_key(id))
+                        .map(|id| format!("reads resource `{}`", name(id))),
+                );
+            }
+        }
+        violations
+    }
+}
@@ -0,0 +1,227 @@
+//! Structured analysis of the ambiguities in a [`Schedule`](crate::schedule::Schedule).
+
+use alloc::{string::String, vec::Vec};
+use fixedbitset::FixedBitSet;
+
+use crate::{component::ComponentId, schedule::NodeId};
+
+/// The ambiguities of a [`Schedule`](crate::schedule::Schedule), created with
+/// [`Schedule::ambiguity_report`](crate::schedule::Schedule::ambiguity_report).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AmbiguityReport {
+    /// The systems that are part of at least one ambiguity.
+    pub systems: Vec<AmbiguousSystem>,
+    /// The ambiguities, grouped by the data they conflict on, largest group first.
+    ///
+    /// A pair of systems that conflicts on multiple components or resources is part of multiple groups.
+    pub groups: Vec<AmbiguityGroup>,
+    /// Orderings that would resolve every ambiguity when added to the schedule.
+    ///
+    /// Each ordering follows the order the systems currently run in with the single-threaded executor,
+    /// so adding them can't create cycles. Orderings implied by other suggestions are left out.
+    pub suggested_orderings: Vec<SuggestedOrdering>,
+}
+
+/// A system that is part of an ambiguity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AmbiguousSystem {
+    /// The id of the system in the schedule.
+    pub id: NodeId,
+    /// The name of the system.
+    pub name: String,
+    /// The names of the system sets containing the system, directly or indirectly.
+    ///
+    /// Anonymous sets and the sets of system types are not included. Since plugins usually group their
+    /// systems in their own sets, these can be used to tell which plugin the system belongs to.
+    pub sets: Vec<String>,
+}
+
+/// The data two ambiguous systems conflict on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AmbiguityConflict {
+    /// Both systems access a component or resource, and at least one of them writes to it.
+    Data {
+        /// The id of the component or resource.
+        id: ComponentId,
+        /// The name of the component or resource.
+        name: String,
+    },
+    /// At least one of the systems requires access to the whole [`World`](crate::world::World).
+    World,
+}
+
+/// The ambiguities caused by a single component or resource.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AmbiguityGroup {
+    /// The data the systems conflict on.
+    pub conflict: AmbiguityConflict,
+    /// The pairs of ambiguous systems.
+    pub pairs: Vec<(NodeId, NodeId)>,
+    /// The names of the system sets containing the systems of this group, as in [`AmbiguousSystem::sets`].
+    pub sets: Vec<String>,
+}
+
+/// An ordering that would resolve one or more ambiguities.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SuggestedOrdering {
+    /// The system that should run first.
+    pub before: NodeId,
+    /// The system that should run second.
+    pub after: NodeId,
+    /// The ambiguous pairs resolved by this ordering, ordered like the suggestion.
+    ///
+    /// Pairs that are resolved by a combination of several orderings are attributed to the first one.
+    pub resolves: Vec<(NodeId, NodeId)>,
+}
+
+impl AmbiguityReport {
+    /// Returns the ambiguous system with the given id.
+    pub fn system(&self, id: NodeId) -> Option<&AmbiguousSystem> {
+        self.systems.iter().find(|system| system.id == id)
+    }
+
+    /// Returns `true` if the schedule has no ambiguities.
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+}
+
+/// Computes orderings that resolve all ambiguous `pairs`.
+///
+/// `order` contains the systems in topological order, and `dependents` the indices in `order`
+/// of the systems that directly depend on each system.
+///
+/// Orderings are picked greedily, shortest distance in `order` first, since those are the most
+/// likely to also order other pairs transitively. Orderings made redundant by later ones are then removed.
+pub(crate) fn suggest_orderings(
+    order: &[NodeId],
+    dependents: &[Vec<usize>],
+    pairs: &[(NodeId, NodeId)],
+) -> Vec<SuggestedOrdering> {
+    let position = |id: NodeId| order.iter().position(|other| *other == id);
+    let mut pairs: Vec<(usize, usize)> = pairs
+        .iter()
+        .filter_map(|&(a, b)| {
+            let (a, b) = (position(a)?, position(b)?);
+            Some((a.min(b), a.max(b)))
+        })
+        .collect();
+    pairs.sort_by_key(|&(a, b)| (b - a, a));
+    pairs.dedup();
+
+    let mut reachable = reachability(dependents, &[]);
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    for &(a, b) in &pairs {
+        if reachable[a].contains(b) {
+            continue;
+        }
+        edges.push((a, b));
+        let mut reachable_from_b = reachable[b].clone();
+        reachable_from_b.insert(b);
+        for (x, reachable_from_x) in reachable.iter_mut().enumerate().take(a + 1) {
+            if x == a || reachable_from_x.contains(a) {
+                reachable_from_x.union_with(&reachable_from_b);
+            }
+        }
+    }
+
+    // Remove orderings that are implied by the remaining ones.
+    let mut i = edges.len();
+    while i > 0 {
+        i -= 1;
+        let mut others = edges.clone();
+        others.remove(i);
+        let reachable = reachability(dependents, &others);
+        if pairs.iter().all(|&(a, b)| reachable[a].contains(b)) {
+            edges = others;
+        }
+    }
+
+    // Attribute each pair to the first ordering on a path between its systems.
+    let reachable = reachability(dependents, &edges);
+    let reaches = |from: usize, to: usize| from == to || reachable[from].contains(to);
+    let mut suggestions: Vec<SuggestedOrdering> = edges
+        .iter()
+        .map(|&(a, b)| SuggestedOrdering {
+            before: order[a],
+            after: order[b],
+            resolves: Vec::new(),
+        })
+        .collect();
+    for &(a, b) in &pairs {
+        // Since the pair wasn't ordered before, every path between its systems uses at least one ordering.
+        let index = edges
+            .iter()
+            .position(|&(before, after)| reaches(a, before) && reaches(after, b));
+        if let Some(index) = index {
+            suggestions[index].resolves.push((order[a], order[b]));
+        }
+    }
+    suggestions
+}
+
+/// Computes the set of nodes reachable from each node, given the edges of a DAG in topological order
+/// plus some additional forward `edges`.
+fn reachability(dependents: &[Vec<usize>], edges: &[(usize, usize)]) -> Vec<FixedBitSet> {
+    let n = dependents.len();
+    let mut reachable = vec![FixedBitSet::with_capacity(n); n];
+    for node in (0..n).rev() {
+        let targets = dependents[node].iter().copied().chain(
+            edges
+                .iter()
+                .filter(|(before, _)| *before == node)
+                .map(|(_, after)| *after),
+        );
+        for target in targets {
+            debug_assert!(target > node);
+            let (head, tail) = reachable.split_at_mut(target);
+            head[node].insert(target);
+            head[node].union_with(&tail[0]);
+        }
+    }
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggestions_skip_transitive_pairs() {
+        let order: Vec<_> = (0..3).map(NodeId::System).collect();
+        let dependents = vec![Vec::new(); 3];
+        // Every pair is ambiguous, but ordering 0 -> 1 -> 2 resolves all of them.
+        let pairs = [
+            (order[0], order[2]),
+            (order[0], order[1]),
+            (order[2], order[1]),
+        ];
+        let suggestions = suggest_orderings(&order, &dependents, &pairs);
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(
+            (suggestions[0].before, suggestions[0].after),
+            (order[0], order[1])
+        );
+        assert_eq!(
+            (suggestions[1].before, suggestions[1].after),
+            (order[1], order[2])
+        );
+        let resolved: usize = suggestions.iter().map(|s| s.resolves.len()).sum();
+        assert_eq!(resolved, 3);
+    }
+
+    #[test]
+    fn suggestions_use_existing_orderings() {
+        let order: Vec<_> = (0..3).map(NodeId::System).collect();
+        // 1 already runs before 2.
+        let dependents = vec![Vec::new(), vec![2], Vec::new()];
+        let pairs = [(order[0], order[1]), (order[0], order[2])];
+        let suggestions = suggest_orderings(&order, &dependents, &pairs);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(
+            (suggestions[0].before, suggestions[0].after),
+            (order[0], order[1])
+        );
+        assert_eq!(suggestions[0].resolves.len(), 2);
+    }
+}
@@ -0,0 +1,219 @@
+//! A description of a [`Schedule`], for visualizing and diffing system ordering.
+//!
+//! [`Schedule`]: crate::schedule::Schedule
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+use crate::schedule::NodeId;
+
+/// A snapshot of the structure of a [`Schedule`](crate::schedule::Schedule), created with
+/// [`Schedule::describe`](crate::schedule::Schedule::describe).
+///
+/// It lists every system and system set along with their run conditions, the hierarchy and ordering edges
+/// between them, the detected ambiguities and the final order of the systems. It can be rendered as
+/// [Graphviz DOT](https://graphviz.org/doc/info/lang.html) with [`to_dot`](Self::to_dot), or serialized
+/// to JSON (or any other format) with `serde` when the `serialize` feature is enabled.
+///
+/// All lists except [`topological_order`](Self::topological_order) are sorted by [`NodeId`],
+/// so describing the same schedule twice produces the same output.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ScheduleDescription {
+    /// The label of the schedule.
+    pub label: String,
+    /// The systems in the schedule.
+    pub systems: Vec<SystemDescription>,
+    /// The system sets in the schedule.
+    pub sets: Vec<SystemSetDescription>,
+    /// Pairs of `(set, member)`, where `member` is a system or set that is directly contained in `set`.
+    pub hierarchy: Vec<(NodeId, NodeId)>,
+    /// Pairs of `(before, after)`, where the system or set `before` has to run before `after`.
+    pub dependencies: Vec<(NodeId, NodeId)>,
+    /// Pairs of systems with conflicting data access and no ordering between them.
+    ///
+    /// The first system of each pair always has the lower [`NodeId`].
+    ///
+    /// This is only filled in once the schedule has been initialized.
+    pub ambiguities: Vec<AmbiguityDescription>,
+    /// The systems in topological order, including automatically inserted sync points.
+    ///
+    /// This is the order in which the single-threaded executor runs the systems,
+    /// and is only filled in once the schedule has been initialized.
+    pub topological_order: Vec<NodeId>,
+}
+
+/// A system in a [`ScheduleDescription`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SystemDescription {
+    /// The id of the system in the schedule.
+    pub id: NodeId,
+    /// The name of the system.
+    pub name: String,
+    /// The names of the run conditions of the system.
+    pub conditions: Vec<String>,
+    /// Whether the system requires exclusive [`World`](crate::world::World) access.
+    pub is_exclusive: bool,
+}
+
+/// A system set in a [`ScheduleDescription`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SystemSetDescription {
+    /// The id of the set in the schedule.
+    pub id: NodeId,
+    /// The name of the set.
+    ///
+    /// Anonymous sets are named after their members.
+    pub name: String,
+    /// The names of the run conditions of the set.
+    pub conditions: Vec<String>,
+    /// Whether the set was created by chaining or grouping systems, rather than by a [`SystemSet`](crate::schedule::SystemSet) type.
+    pub is_anonymous: bool,
+    /// Whether the set is the implicit set of a system type.
+    pub is_system_type: bool,
+}
+
+/// A pair of ambiguous systems in a [`ScheduleDescription`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AmbiguityDescription {
+    /// The first system.
+    pub first: NodeId,
+    /// The second system.
+    pub second: NodeId,
+    /// The names of the components and resources both systems access, where at least one of them writes.
+    ///
+    /// If empty, the systems conflict on [`World`](crate::world::World) access.
+    pub conflicts: Vec<String>,
+}
+
+impl ScheduleDescription {
+    /// Returns the name of the system or set with the given [`NodeId`].
+    pub fn name_of(&self, id: NodeId) -> Option<&str> {
+        match id {
+            NodeId::System(_) => self
+                .systems
+                .iter()
+                .find(|system| system.id == id)
+                .map(|system| system.name.as_str()),
+            NodeId::Set(_) => self
+                .sets
+                .iter()
+                .find(|set| set.id == id)
+                .map(|set| set.name.as_str()),
+        }
+    }
+
+    /// Renders the schedule as a [Graphviz DOT](https://graphviz.org/doc/info/lang.html) digraph.
+    ///
+    /// Systems are drawn as boxes and sets as dashed ellipses, with their run conditions listed under their name.
+    /// Ordering edges are solid, set membership edges are dashed, and ambiguities are drawn as undirected red edges
+    /// labeled with the conflicting data. Systems are numbered by their position in the topological order.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph {} {{", dot_string(&self.label)).unwrap();
+        writeln!(dot, "\tlabel={};", dot_string(&self.label)).unwrap();
+        writeln!(dot, "\tnode [shape=box];").unwrap();
+
+        for system in &self.systems {
+            let mut label = match self
+                .topological_order
+                .iter()
+                .position(|id| *id == system.id)
+            {
+                Some(position) => format!("{position}: {}", system.name),
+                None => system.name.clone(),
+            };
+            push_conditions(&mut label, &system.conditions);
+            let style = if system.is_exclusive {
+                ", style=bold"
+            } else {
+                ""
+            };
+            writeln!(
+                dot,
+                "\t{} [label={}{style}];",
+                dot_id(system.id),
+                dot_string(&label)
+            )
+            .unwrap();
+        }
+
+        for set in &self.sets {
+            let mut label = set.name.clone();
+            push_conditions(&mut label, &set.conditions);
+            writeln!(
+                dot,
+                "\t{} [label={}, shape=ellipse, style=dashed];",
+                dot_id(set.id),
+                dot_string(&label)
+            )
+            .unwrap();
+        }
+
+        for (set, member) in &self.hierarchy {
+            writeln!(
+                dot,
+                "\t{} -> {} [style=dashed, arrowhead=empty];",
+                dot_id(*set),
+                dot_id(*member)
+            )
+            .unwrap();
+        }
+
+        for (before, after) in &self.dependencies {
+            writeln!(dot, "\t{} -> {};", dot_id(*before), dot_id(*after)).unwrap();
+        }
+
+        for ambiguity in &self.ambiguities {
+            let conflicts = if ambiguity.conflicts.is_empty() {
+                String::from("World")
+            } else {
+                ambiguity.conflicts.join("\n")
+            };
+            writeln!(
+                dot,
+                "\t{} -> {} [dir=none, constraint=false, color=red, fontcolor=red, label={}];",
+                dot_id(ambiguity.first),
+                dot_id(ambiguity.second),
+                dot_string(&conflicts)
+            )
+            .unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn push_conditions(label: &mut String, conditions: &[String]) {
+    for condition in conditions {
+        write!(label, "\nrun_if: {condition}").unwrap();
+    }
+}
+
+fn dot_id(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system_{index}"),
+        NodeId::Set(index) => format!("set_{index}"),
+    }
+}
+
+fn dot_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
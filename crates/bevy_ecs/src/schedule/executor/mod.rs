@@ -1,9 +1,11 @@
 mod multi_threaded;
+mod recorder;
 mod simple;
 mod single_threaded;
 
 pub use self::{
     multi_threaded::{MainThreadExecutor, MultiThreadedExecutor},
+    recorder::{DurationHistogram, SystemExecutionRecord, SystemExecutionRecorder},
     simple::SimpleExecutor,
     single_threaded::SingleThreadedExecutor,
 };
@@ -11,8 +13,9 @@ pub use self::{
 use fixedbitset::FixedBitSet;
 
 use crate::{
+    error::{BevyError, ErrorContext, ErrorHandler},
     schedule::{BoxedCondition, NodeId},
-    system::BoxedSystem,
+    system::{ScheduleSystem, System},
     world::World,
 };
 
@@ -60,10 +63,13 @@ pub struct SystemSchedule {
     /// List of system node ids.
     pub(super) system_ids: Vec<NodeId>,
     /// Indexed by system node id.
-    pub(super) systems: Vec<BoxedSystem>,
+    pub(super) systems: Vec<ScheduleSystem>,
     /// Indexed by system node id.
     pub(super) system_conditions: Vec<Vec<BoxedCondition>>,
     /// Indexed by system node id.
+    /// The error handler of the system, if one was set for the schedule or one of the system's sets.
+    pub(super) error_handlers: Vec<Option<ErrorHandler>>,
+    /// Indexed by system node id.
     /// Number of systems that the system immediately depends on.
     pub(super) system_dependencies: Vec<usize>,
     /// Indexed by system node id.
@@ -89,6 +95,7 @@ impl SystemSchedule {
         Self {
             systems: Vec::new(),
             system_conditions: Vec::new(),
+            error_handlers: Vec::new(),
             set_conditions: Vec::new(),
             system_ids: Vec::new(),
             set_ids: Vec::new(),
@@ -122,12 +129,27 @@ impl SystemSchedule {
 pub fn apply_deferred(world: &mut World) {}
 
 /// Returns `true` if the [`System`](crate::system::System) is an instance of [`apply_deferred`].
-pub(super) fn is_apply_deferred(system: &BoxedSystem) -> bool {
+pub(super) fn is_apply_deferred(system: &ScheduleSystem) -> bool {
     use crate::system::IntoSystem;
     // deref to use `System::type_id` instead of `Any::type_id`
     system.as_ref().type_id() == apply_deferred.system_type_id()
 }
 
+/// Passes an error returned by `system` to `error_handler`.
+pub(super) fn handle_system_error(
+    error_handler: ErrorHandler,
+    system: &dyn System<In = (), Out = crate::error::Result>,
+    error: BevyError,
+) {
+    error_handler(
+        error,
+        ErrorContext::System {
+            name: system.name(),
+            last_run: system.get_last_run(),
+        },
+    );
+}
+
 /// These functions hide the bottom of the callstack from `RUST_BACKTRACE=1` (assuming the default panic handler is used).
 ///
 /// The full callstack will still be visible with `RUST_BACKTRACE=full`.
@@ -140,6 +162,7 @@ mod __rust_begin_short_backtrace {
     use core::hint::black_box;
 
     use crate::{
+        error::Result,
         system::{ReadOnlySystem, System},
         world::{unsafe_world_cell::UnsafeWorldCell, World},
     };
@@ -148,11 +171,12 @@ mod __rust_begin_short_backtrace {
     /// See `System::run_unsafe`.
     #[inline(never)]
     pub(super) unsafe fn run_unsafe(
-        system: &mut dyn System<In = (), Out = ()>,
+        system: &mut dyn System<In = (), Out = Result>,
         world: UnsafeWorldCell,
-    ) {
-        system.run_unsafe((), world);
+    ) -> Result {
+        let result = system.run_unsafe((), world);
         black_box(());
+        result
     }
 
     /// # Safety
@@ -166,9 +190,10 @@ mod __rust_begin_short_backtrace {
     }
 
     #[inline(never)]
-    pub(super) fn run(system: &mut dyn System<In = (), Out = ()>, world: &mut World) {
-        system.run((), world);
+    pub(super) fn run(system: &mut dyn System<In = (), Out = Result>, world: &mut World) -> Result {
+        let result = system.run((), world);
         black_box(());
+        result
     }
 
     #[inline(never)]
@@ -7,7 +7,7 @@ use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
 use bevy_utils::tracing::info_span;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::Span;
-use bevy_utils::{default, syncunsafecell::SyncUnsafeCell};
+use bevy_utils::{default, syncunsafecell::SyncUnsafeCell, Instant};
 use core::panic::AssertUnwindSafe;
 
 use concurrent_queue::ConcurrentQueue;
@@ -15,22 +15,28 @@ use fixedbitset::FixedBitSet;
 
 use crate::{
     archetype::ArchetypeComponentId,
+    error::ErrorHandler,
     prelude::Resource,
     query::Access,
-    schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
-    system::BoxedSystem,
+    schedule::{
+        is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutionRecord,
+        SystemExecutionRecorder, SystemExecutor, SystemSchedule,
+    },
+    system::ScheduleSystem,
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
 
 use crate as bevy_ecs;
 
-use super::__rust_begin_short_backtrace;
+use super::{__rust_begin_short_backtrace, handle_system_error};
 
 /// Borrowed data used by the [`MultiThreadedExecutor`].
 struct Environment<'env, 'sys> {
     executor: &'env MultiThreadedExecutor,
-    systems: &'sys [SyncUnsafeCell<BoxedSystem>],
+    systems: &'sys [SyncUnsafeCell<ScheduleSystem>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
+    error_handlers: &'sys [Option<ErrorHandler>],
+    default_error_handler: ErrorHandler,
     world_cell: UnsafeWorldCell<'env>,
 }
 
@@ -47,6 +53,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
         schedule: &'sys mut SystemSchedule,
         world: &'env mut World,
     ) -> Self {
+        let default_error_handler = world.default_error_handler();
         Environment {
             executor,
             systems: SyncUnsafeCell::from_mut(schedule.systems.as_mut_slice()).as_slice_of_cells(),
@@ -56,9 +63,15 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 sets_with_conditions_of_systems: &schedule.sets_with_conditions_of_systems,
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
             }),
+            error_handlers: &schedule.error_handlers,
+            default_error_handler,
             world_cell: world.as_unsafe_world_cell(),
         }
     }
+
+    fn error_handler(&self, system_index: usize) -> ErrorHandler {
+        self.error_handlers[system_index].unwrap_or(self.default_error_handler)
+    }
 }
 
 /// Per-system data used by the [`MultiThreadedExecutor`].
@@ -79,6 +92,26 @@ struct SystemResult {
     system_index: usize,
 }
 
+/// Timings of a system task captured by the executor, when a [`SystemExecutionRecorder`] is present.
+struct PendingRecord {
+    recorder: SystemExecutionRecorder,
+    ready: Instant,
+    scheduled: Instant,
+}
+
+impl PendingRecord {
+    fn finish(self, system: &ScheduleSystem, start: Instant) {
+        self.recorder.record(SystemExecutionRecord {
+            system: system.name(),
+            thread: std::thread::current().id(),
+            ready: self.ready,
+            scheduled: self.scheduled,
+            start,
+            end: Instant::now(),
+        });
+    }
+}
+
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
 pub struct MultiThreadedExecutor {
     /// The running state, protected by a mutex so that a reference to the executor can be shared across tasks.
@@ -123,6 +156,10 @@ pub struct ExecutorState {
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
+    /// The recorder found in the world when the schedule started running.
+    recorder: Option<SystemExecutionRecorder>,
+    /// When each system's dependencies completed, only tracked while recording.
+    ready_at: Vec<Instant>,
 }
 
 /// References to data required by the executor.
@@ -194,6 +231,13 @@ impl SystemExecutor for MultiThreadedExecutor {
             .num_dependencies_remaining
             .clone_from(&schedule.system_dependencies);
         state.ready_systems.clone_from(&self.starting_systems);
+        state.recorder = world.get_resource::<SystemExecutionRecorder>().cloned();
+        if state.recorder.is_some() {
+            state.ready_at.clear();
+            state
+                .ready_at
+                .resize(schedule.systems.len(), Instant::now());
+        }
 
         // If stepping is enabled, make sure we skip those systems that should
         // not be run.
@@ -257,6 +301,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         state.evaluated_sets.clear();
         state.skipped_systems.clear();
         state.completed_systems.clear();
+        state.recorder = None;
     }
 
     fn set_apply_final_deferred(&mut self, value: bool) {
@@ -269,7 +314,7 @@ impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
         &self,
         system_index: usize,
         res: Result<(), Box<dyn Any + Send>>,
-        system: &BoxedSystem,
+        system: &ScheduleSystem,
     ) {
         // tell the executor that the system finished
         self.environment
@@ -349,6 +394,8 @@ impl ExecutorState {
             skipped_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
+            recorder: None,
+            ready_at: Vec::new(),
         }
     }
 
@@ -459,7 +506,7 @@ impl ExecutorState {
     fn can_run(
         &mut self,
         system_index: usize,
-        system: &mut BoxedSystem,
+        system: &mut ScheduleSystem,
         conditions: &mut Conditions,
         world: UnsafeWorldCell,
     ) -> bool {
@@ -523,7 +570,7 @@ impl ExecutorState {
     unsafe fn should_run(
         &mut self,
         system_index: usize,
-        system: &mut BoxedSystem,
+        system: &mut ScheduleSystem,
         conditions: &mut Conditions,
         world: UnsafeWorldCell,
     ) -> bool {
@@ -595,20 +642,32 @@ impl ExecutorState {
         let context = *context;
 
         let system_meta = &self.system_task_metadata[system_index];
+        let pending_record = self.pending_record(system_index);
 
         let task = async move {
+            let start = pending_record.as_ref().map(|_| Instant::now());
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
                 // access the world data used by the system.
                 // - `update_archetype_component_access` has been called.
-                unsafe {
+                let result = unsafe {
                     __rust_begin_short_backtrace::run_unsafe(
                         &mut **system,
                         context.environment.world_cell,
-                    );
+                    )
                 };
+                if let Err(error) = result {
+                    handle_system_error(
+                        context.environment.error_handler(system_index),
+                        &**system,
+                        error,
+                    );
+                }
             }));
+            if let (Some(pending_record), Some(start)) = (pending_record, start) {
+                pending_record.finish(system, start);
+            }
             context.system_completed(system_index, res, system);
         };
 
@@ -630,28 +689,43 @@ impl ExecutorState {
         let system = unsafe { &mut *context.environment.systems[system_index].get() };
         // Move the full context object into the new future.
         let context = *context;
+        let pending_record = self.pending_record(system_index);
 
         if is_apply_deferred(system) {
             // TODO: avoid allocation
             let unapplied_systems = self.unapplied_systems.clone();
             self.unapplied_systems.clear();
             let task = async move {
+                let start = pending_record.as_ref().map(|_| Instant::now());
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
                 let res = apply_deferred(&unapplied_systems, context.environment.systems, world);
+                if let (Some(pending_record), Some(start)) = (pending_record, start) {
+                    pending_record.finish(system, start);
+                }
                 context.system_completed(system_index, res, system);
             };
 
             context.scope.spawn_on_scope(task);
         } else {
             let task = async move {
+                let start = pending_record.as_ref().map(|_| Instant::now());
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
-                    __rust_begin_short_backtrace::run(&mut **system, world);
+                    if let Err(error) = __rust_begin_short_backtrace::run(&mut **system, world) {
+                        handle_system_error(
+                            context.environment.error_handler(system_index),
+                            &**system,
+                            error,
+                        );
+                    }
                 }));
+                if let (Some(pending_record), Some(start)) = (pending_record, start) {
+                    pending_record.finish(system, start);
+                }
                 context.system_completed(system_index, res, system);
             };
 
@@ -694,10 +768,22 @@ impl ExecutorState {
             *remaining -= 1;
             if *remaining == 0 && !self.completed_systems.contains(dep_idx) {
                 self.ready_systems.insert(dep_idx);
+                if self.recorder.is_some() {
+                    self.ready_at[dep_idx] = Instant::now();
+                }
             }
         }
     }
 
+    fn pending_record(&self, system_index: usize) -> Option<PendingRecord> {
+        let recorder = self.recorder.clone()?;
+        Some(PendingRecord {
+            recorder,
+            ready: self.ready_at[system_index],
+            scheduled: Instant::now(),
+        })
+    }
+
     fn rebuild_active_access(&mut self) {
         self.active_access.clear();
         for index in self.running_systems.ones() {
@@ -710,7 +796,7 @@ impl ExecutorState {
 
 fn apply_deferred(
     unapplied_systems: &FixedBitSet,
-    systems: &[SyncUnsafeCell<BoxedSystem>],
+    systems: &[SyncUnsafeCell<ScheduleSystem>],
     world: &mut World,
 ) -> Result<(), Box<dyn Any + Send>> {
     for system_index in unapplied_systems.ones() {
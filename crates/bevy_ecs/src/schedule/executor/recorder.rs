@@ -0,0 +1,400 @@
+use alloc::{borrow::Cow, collections::VecDeque, sync::Arc};
+use core::fmt::Write;
+use std::{
+    sync::{Mutex, MutexGuard},
+    thread::ThreadId,
+};
+
+use bevy_utils::{Duration, HashMap, Instant};
+
+use crate::{self as bevy_ecs, system::Resource};
+
+/// Records when, where and for how long the [`MultiThreadedExecutor`](super::MultiThreadedExecutor)
+/// runs each system.
+///
+/// Recording starts when this resource is inserted into the [`World`](crate::world::World), and stops when it is removed.
+/// Every system run produces a [`SystemExecutionRecord`], which includes how long the system had to wait for
+/// conflicting systems to finish after its dependencies completed. The most recent records are kept in a ring buffer,
+/// and can be exported as [Chrome trace events](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU)
+/// with [`to_chrome_trace`](Self::to_chrome_trace), to be viewed in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+/// A [`DurationHistogram`] of the run times of each system is kept for the whole recording.
+///
+/// The recorder is a cheap handle to shared state, so it can be cloned out of the world and read from anywhere.
+#[derive(Resource, Clone)]
+pub struct SystemExecutionRecorder {
+    state: Arc<Mutex<RecorderState>>,
+}
+
+struct RecorderState {
+    origin: Instant,
+    capacity: usize,
+    records: VecDeque<SystemExecutionRecord>,
+    /// The number of records ever recorded, used as a cursor by [`SystemExecutionRecorder::read_since`].
+    recorded: u64,
+    threads: Vec<(ThreadId, Option<String>)>,
+    histograms: HashMap<Cow<'static, str>, DurationHistogram>,
+}
+
+/// The execution of a single system, recorded by a [`SystemExecutionRecorder`].
+#[derive(Clone, Debug)]
+pub struct SystemExecutionRecord {
+    /// The name of the system.
+    pub system: Cow<'static, str>,
+    /// The thread the system ran on.
+    pub thread: ThreadId,
+    /// When all of the system's dependencies had completed.
+    pub ready: Instant,
+    /// When the executor found no conflicting systems running and spawned the system's task.
+    pub scheduled: Instant,
+    /// When the system started running.
+    pub start: Instant,
+    /// When the system finished running.
+    pub end: Instant,
+}
+
+impl SystemExecutionRecord {
+    /// Returns how long the system ran for.
+    pub fn duration(&self) -> Duration {
+        self.end.saturating_duration_since(self.start)
+    }
+
+    /// Returns how long the system waited for systems with conflicting access or run conditions to finish,
+    /// after all of its dependencies completed.
+    pub fn conflict_wait(&self) -> Duration {
+        self.scheduled.saturating_duration_since(self.ready)
+    }
+
+    /// Returns how long the system's task waited for a thread after it was spawned.
+    pub fn spawn_latency(&self) -> Duration {
+        self.start.saturating_duration_since(self.scheduled)
+    }
+}
+
+impl Default for SystemExecutionRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemExecutionRecorder {
+    /// The number of records kept by [`SystemExecutionRecorder::new`].
+    pub const DEFAULT_CAPACITY: usize = 16384;
+
+    /// Creates a recorder that keeps the [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY) most recent records.
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Creates a recorder that keeps the `capacity` most recent records.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(RecorderState {
+                origin: Instant::now(),
+                capacity,
+                records: VecDeque::new(),
+                recorded: 0,
+                threads: Vec::new(),
+                histograms: HashMap::default(),
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RecorderState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Adds a record. Must be called from the thread the system ran on.
+    pub(crate) fn record(&self, record: SystemExecutionRecord) {
+        let mut state = self.lock();
+        if !state.threads.iter().any(|(id, _)| *id == record.thread) {
+            let name = std::thread::current().name().map(ToString::to_string);
+            state.threads.push((record.thread, name));
+        }
+        state
+            .histograms
+            .entry(record.system.clone())
+            .or_default()
+            .record(record.duration());
+        if state.capacity == 0 {
+            state.recorded += 1;
+            return;
+        }
+        if state.records.len() == state.capacity {
+            state.records.pop_front();
+        }
+        state.records.push_back(record);
+        state.recorded += 1;
+    }
+
+    /// Returns the buffered records, oldest first.
+    pub fn records(&self) -> Vec<SystemExecutionRecord> {
+        self.lock().records.iter().cloned().collect()
+    }
+
+    /// Returns the buffered records that were recorded after `cursor`, and advances `cursor` past them.
+    ///
+    /// Starting from a cursor of `0` and calling this repeatedly returns each record once,
+    /// unless it was dropped from the buffer in between.
+    pub fn read_since(&self, cursor: &mut u64) -> Vec<SystemExecutionRecord> {
+        let state = self.lock();
+        let first = state.recorded - state.records.len() as u64;
+        let skip = cursor.saturating_sub(first) as usize;
+        *cursor = state.recorded;
+        state.records.iter().skip(skip).cloned().collect()
+    }
+
+    /// Returns the histogram of the run times of the system with the given name.
+    pub fn histogram(&self, system: &str) -> Option<DurationHistogram> {
+        self.lock().histograms.get(system).cloned()
+    }
+
+    /// Returns the histograms of the run times of all recorded systems.
+    pub fn histograms(&self) -> Vec<(Cow<'static, str>, DurationHistogram)> {
+        self.lock()
+            .histograms
+            .iter()
+            .map(|(system, histogram)| (system.clone(), histogram.clone()))
+            .collect()
+    }
+
+    /// Clears the buffered records and histograms.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.records.clear();
+        state.histograms.clear();
+    }
+
+    /// Exports the buffered records in the Chrome trace event JSON format.
+    ///
+    /// Each system run is a complete event on the thread it ran on, with its conflict wait and
+    /// spawn latency in microseconds as arguments. Timestamps are relative to the creation of the recorder.
+    pub fn to_chrome_trace(&self) -> String {
+        let state = self.lock();
+        let micros = |duration: Duration| duration.as_secs_f64() * 1_000_000.0;
+
+        let mut json = String::from("{\"traceEvents\":[\n");
+        for (tid, (_, name)) in state.threads.iter().enumerate() {
+            let name = match name {
+                Some(name) => json_string(name),
+                None => json_string(&format!("thread {tid}")),
+            };
+            writeln!(
+                json,
+                "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":{tid},\"args\":{{\"name\":{name}}}}},"
+            )
+            .unwrap();
+        }
+        for record in &state.records {
+            let tid = state
+                .threads
+                .iter()
+                .position(|(id, _)| *id == record.thread)
+                .unwrap_or_default();
+            writeln!(
+                json,
+                "{{\"name\":{},\"cat\":\"system\",\"ph\":\"X\",\"pid\":0,\"tid\":{tid},\"ts\":{:.3},\"dur\":{:.3},\"args\":{{\"conflict_wait_us\":{:.3},\"spawn_latency_us\":{:.3}}}}},",
+                json_string(&record.system),
+                micros(record.start.saturating_duration_since(state.origin)),
+                micros(record.duration()),
+                micros(record.conflict_wait()),
+                micros(record.spawn_latency()),
+            )
+            .unwrap();
+        }
+        // Trailing commas aren't valid JSON.
+        if json.ends_with(",\n") {
+            json.truncate(json.len() - 2);
+            json.push('\n');
+        }
+        json.push_str("],\"displayTimeUnit\":\"ms\"}\n");
+        json
+    }
+}
+
+/// A histogram of durations, where each bucket is twice as wide as the previous one.
+///
+/// Bucket `i` counts the durations shorter than `2^i` microseconds that didn't fit in a previous bucket,
+/// and the last bucket counts everything longer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DurationHistogram {
+    buckets: [u64; DurationHistogram::BUCKETS],
+    count: u64,
+    total: Duration,
+    max: Duration,
+}
+
+impl DurationHistogram {
+    /// The number of buckets in the histogram.
+    pub const BUCKETS: usize = 32;
+
+    /// Adds a duration to the histogram.
+    pub fn record(&mut self, duration: Duration) {
+        let micros = duration.as_micros().min(u64::MAX as u128) as u64;
+        let bucket = (u64::BITS - micros.leading_zeros()) as usize;
+        self.buckets[bucket.min(Self::BUCKETS - 1)] += 1;
+        self.count += 1;
+        self.total += duration;
+        self.max = self.max.max(duration);
+    }
+
+    /// Returns the number of recorded durations.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the sum of the recorded durations.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Returns the longest recorded duration.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Returns the mean of the recorded durations, or `None` if the histogram is empty.
+    pub fn mean(&self) -> Option<Duration> {
+        let mean = self.total.as_nanos().checked_div(u128::from(self.count))?;
+        Some(Duration::from_nanos(mean as u64))
+    }
+
+    /// Returns an iterator over the exclusive upper bound of each bucket and the number of durations in it.
+    pub fn buckets(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .map(|(i, count)| (Duration::from_micros(1 << i), *count))
+    }
+
+    /// Returns an upper bound of the given percentile of the recorded durations,
+    /// or `None` if the histogram is empty.
+    ///
+    /// `percentile` is clamped between `0.0` and `100.0`.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((percentile.clamp(0.0, 100.0) / 100.0) * self.count as f64).ceil() as u64;
+        let mut seen = 0;
+        for (upper_bound, count) in self.buckets() {
+            seen += count;
+            if seen >= rank.max(1) {
+                return Some(upper_bound.min(self.max));
+            }
+        }
+        Some(self.max)
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::{Resource, World},
+        schedule::{ExecutorKind, IntoSystemConfigs, Schedule},
+        system::{Res, ResMut},
+    };
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    fn increment(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    fn read(_: Res<Counter>) {}
+
+    #[test]
+    fn records_system_runs() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let recorder = SystemExecutionRecorder::with_capacity(4);
+        world.insert_resource(recorder.clone());
+
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+        schedule.add_systems((increment, read.after(increment)));
+        schedule.run(&mut world);
+
+        let records = recorder.records();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].system.ends_with("increment"));
+        assert!(records[1].system.ends_with("read"));
+        assert!(records[0].end <= records[1].start);
+        assert!(records[1].ready >= records[0].end);
+
+        let mut cursor = 0;
+        assert_eq!(recorder.read_since(&mut cursor).len(), 2);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        // Only the 4 most recent records are kept.
+        assert_eq!(recorder.records().len(), 4);
+        assert_eq!(recorder.read_since(&mut cursor).len(), 4);
+        assert!(recorder.read_since(&mut cursor).is_empty());
+
+        let histogram = recorder.histogram(&records[0].system).unwrap();
+        assert_eq!(histogram.count(), 3);
+
+        let trace = recorder.to_chrome_trace();
+        assert!(trace.starts_with("{\"traceEvents\":["));
+        assert!(trace.contains("\"ph\":\"X\""));
+        assert!(trace.ends_with("],\"displayTimeUnit\":\"ms\"}\n"));
+        assert!(!trace.contains(",\n]"));
+
+        // Removing the resource stops recording.
+        world.remove_resource::<SystemExecutionRecorder>();
+        schedule.run(&mut world);
+        assert_eq!(recorder.read_since(&mut cursor).len(), 0);
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        let mut histogram = DurationHistogram::default();
+        assert_eq!(histogram.percentile(50.0), None);
+        for micros in [0, 1, 3, 3, 100] {
+            histogram.record(Duration::from_micros(micros));
+        }
+        assert_eq!(histogram.count(), 5);
+        assert_eq!(histogram.max(), Duration::from_micros(100));
+        let buckets: Vec<_> = histogram
+            .buckets()
+            .take(8)
+            .map(|(_, count)| count)
+            .collect();
+        assert_eq!(buckets, [1, 1, 2, 0, 0, 0, 0, 1]);
+        assert_eq!(histogram.percentile(50.0), Some(Duration::from_micros(4)));
+        assert_eq!(
+            histogram.percentile(100.0),
+            Some(Duration::from_micros(100))
+        );
+        assert_eq!(histogram.mean(), Some(Duration::from_nanos(21_400)));
+
+        // The mean doesn't overflow once the count no longer fits in a `u32`.
+        let histogram = DurationHistogram {
+            count: u64::from(u32::MAX) * 2,
+            total: Duration::from_micros(u64::from(u32::MAX) * 2),
+            ..Default::default()
+        };
+        assert_eq!(histogram.mean(), Some(Duration::from_micros(1)));
+    }
+}
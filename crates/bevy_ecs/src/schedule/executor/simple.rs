@@ -5,7 +5,8 @@ use fixedbitset::FixedBitSet;
 
 use crate::{
     schedule::{
-        executor::is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+        executor::{handle_system_error, is_apply_deferred},
+        BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
     },
     world::World,
 };
@@ -48,6 +49,7 @@ impl SystemExecutor for SimpleExecutor {
             self.completed_systems |= skipped_systems;
         }
 
+        let default_error_handler = world.default_error_handler();
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -100,11 +102,19 @@ impl SystemExecutor for SimpleExecutor {
             }
 
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
-                __rust_begin_short_backtrace::run(&mut **system, world);
+                __rust_begin_short_backtrace::run(&mut **system, world)
             }));
-            if let Err(payload) = res {
-                eprintln!("Encountered a panic in system `{}`!", &*system.name());
-                std::panic::resume_unwind(payload);
+            match res {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => handle_system_error(
+                    schedule.error_handlers[system_index].unwrap_or(default_error_handler),
+                    &**system,
+                    error,
+                ),
+                Err(payload) => {
+                    eprintln!("Encountered a panic in system `{}`!", &*system.name());
+                    std::panic::resume_unwind(payload);
+                }
             }
         }
 
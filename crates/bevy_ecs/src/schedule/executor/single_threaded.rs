@@ -4,7 +4,10 @@ use core::panic::AssertUnwindSafe;
 use fixedbitset::FixedBitSet;
 
 use crate::{
-    schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    schedule::{
+        handle_system_error, is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor,
+        SystemSchedule,
+    },
     world::World,
 };
 
@@ -54,6 +57,7 @@ impl SystemExecutor for SingleThreadedExecutor {
             self.completed_systems |= skipped_systems;
         }
 
+        let default_error_handler = world.default_error_handler();
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -108,19 +112,27 @@ impl SystemExecutor for SingleThreadedExecutor {
 
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 if system.is_exclusive() {
-                    __rust_begin_short_backtrace::run(&mut **system, world);
+                    __rust_begin_short_backtrace::run(&mut **system, world)
                 } else {
                     // Use run_unsafe to avoid immediately applying deferred buffers
                     let world = world.as_unsafe_world_cell();
                     system.update_archetype_component_access(world);
                     // SAFETY: We have exclusive, single-threaded access to the world and
                     // update_archetype_component_access is being called immediately before this.
-                    unsafe { __rust_begin_short_backtrace::run_unsafe(&mut **system, world) };
+                    unsafe { __rust_begin_short_backtrace::run_unsafe(&mut **system, world) }
                 }
             }));
-            if let Err(payload) = res {
-                eprintln!("Encountered a panic in system `{}`!", &*system.name());
-                std::panic::resume_unwind(payload);
+            match res {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => handle_system_error(
+                    schedule.error_handlers[system_index].unwrap_or(default_error_handler),
+                    &**system,
+                    error,
+                ),
+                Err(payload) => {
+                    eprintln!("Encountered a panic in system `{}`!", &*system.name());
+                    std::panic::resume_unwind(payload);
+                }
             }
             self.unapplied_systems.insert(system_index);
         }
@@ -1,9 +1,12 @@
 use core::fmt::Debug;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
 
 /// Unique identifier for a system or system set stored in a [`ScheduleGraph`].
 ///
 /// [`ScheduleGraph`]: crate::schedule::ScheduleGraph
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum NodeId {
     /// Identifier for a system.
     System(usize),
@@ -1,7 +1,10 @@
 //! Contains APIs for ordering systems and executing them on a [`World`](crate::world::World)
 
+mod access_policy;
+mod ambiguity;
 mod condition;
 mod config;
+mod description;
 mod executor;
 mod graph;
 #[allow(clippy::module_inception)]
@@ -10,7 +13,10 @@ mod set;
 mod stepping;
 
 use self::graph::*;
-pub use self::{condition::*, config::*, executor::*, schedule::*, set::*};
+pub use self::{
+    access_policy::*, ambiguity::*, condition::*, config::*, description::*, executor::*,
+    schedule::*, set::*,
+};
 
 pub use self::graph::NodeId;
 
@@ -713,6 +719,61 @@ mod tests {
             let result = schedule.initialize(&mut world);
             assert!(matches!(result, Err(ScheduleBuildError::Ambiguity(_))));
         }
+
+        #[test]
+        fn access_policy_violation() {
+            use crate::{
+                prelude::{Commands, Component, Query},
+                schedule::{AccessLevel, AccessPolicy},
+            };
+
+            #[derive(Component)]
+            struct Protected;
+
+            #[derive(Component)]
+            struct Shared;
+
+            #[derive(Resource)]
+            struct X;
+
+            let build = |systems: SystemConfigs| {
+                let mut world = World::new();
+                world.insert_resource(X);
+                world.get_resource_or_init::<Schedules>().set_access_policy(
+                    TestSet::A,
+                    AccessPolicy::allow_all()
+                        .with_component::<Protected>(AccessLevel::None)
+                        .with_resource::<X>(AccessLevel::Read)
+                        .allow_exclusive(false),
+                );
+                let mut schedule = Schedule::default();
+                schedule.configure_sets(TestSet::B.in_set(TestSet::A));
+                schedule.add_systems(systems);
+                match schedule.initialize(&mut world) {
+                    Ok(()) => None,
+                    Err(ScheduleBuildError::AccessPolicyViolation(message)) => Some(message),
+                    Err(error) => panic!("{error}"),
+                }
+            };
+
+            let allowed = (|_: Query<&mut Shared>, _: Res<X>, _: Commands| {}).in_set(TestSet::B);
+            assert_eq!(build(allowed), None);
+            // Systems outside of the set are not restricted.
+            assert_eq!(build((|_: Query<&Protected>| {}).into_configs()), None);
+
+            let message =
+                build((|_: Query<&Protected>, _: ResMut<X>| {}).in_set(TestSet::B)).unwrap();
+            assert!(message.contains("reads component `bevy_ecs::schedule::tests::schedule_build_errors::access_policy_violation::Protected`"));
+            assert!(message.contains("writes resource"));
+            assert!(build((|_: &mut World| {}).in_set(TestSet::A))
+                .unwrap()
+                .contains("is an exclusive system"));
+            // Run conditions are also checked.
+            let condition = (|| {})
+                .in_set(TestSet::A)
+                .run_if(|_: Query<&Protected>| true);
+            assert!(build(condition).is_some());
+        }
     }
 
     mod system_ambiguity {
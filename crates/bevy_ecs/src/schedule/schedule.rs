@@ -1,4 +1,4 @@
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeSet, VecDeque};
 use core::fmt::{Debug, Write};
 use core::hash::BuildHasherDefault;
 
@@ -16,9 +16,10 @@ use fixedbitset::FixedBitSet;
 use crate::{
     self as bevy_ecs,
     component::{ComponentId, Components, Tick},
+    error::{BevyError, ErrorHandler},
     prelude::Component,
     schedule::*,
-    system::{BoxedSystem, IntoSystem, Resource, System},
+    system::{InfallibleSystemWrapper, IntoSystem, Resource, ScheduleSystem, System},
     world::World,
 };
 
@@ -32,6 +33,8 @@ pub struct Schedules {
     inner: HashMap<InternedScheduleLabel, Schedule>,
     /// List of [`ComponentId`]s to ignore when reporting system order ambiguity conflicts
     pub ignored_scheduling_ambiguities: BTreeSet<ComponentId>,
+    /// The [`AccessPolicy`] of each system set, checked when schedules are built
+    access_policies: HashMap<InternedSystemSet, AccessPolicy>,
 }
 
 impl Schedules {
@@ -40,6 +43,7 @@ impl Schedules {
         Self {
             inner: HashMap::new(),
             ignored_scheduling_ambiguities: BTreeSet::new(),
+            access_policies: HashMap::new(),
         }
     }
 
@@ -123,6 +127,19 @@ impl Schedules {
         }
     }
 
+    /// Restricts the components and resources that the systems in `set` can access to `policy`, in all schedules.
+    ///
+    /// The policy is checked when a schedule is built, so it must be set before the schedules that contain `set`
+    /// are first run. Replaces any previous policy of `set`. See [`AccessPolicy`] for more information.
+    pub fn set_access_policy(&mut self, set: impl SystemSet, policy: AccessPolicy) {
+        self.access_policies.insert(set.intern(), policy);
+    }
+
+    /// Returns the [`AccessPolicy`] of `set`, if any.
+    pub fn access_policy(&self, set: impl SystemSet) -> Option<&AccessPolicy> {
+        self.access_policies.get(&set.intern())
+    }
+
     /// Ignore system order ambiguities caused by conflicts on [`Component`]s of type `T`.
     pub fn allow_ambiguous_component<T: Component>(&mut self, world: &mut World) {
         self.ignored_scheduling_ambiguities
@@ -351,6 +368,33 @@ impl Schedule {
         self.graph.settings.clone()
     }
 
+    /// Sets the [`ErrorHandler`] for errors returned by systems in this schedule.
+    ///
+    /// This takes precedence over the [`DefaultErrorHandler`](crate::error::DefaultErrorHandler) of the world,
+    /// but not over handlers set with [`set_error_handler_for_set`](Self::set_error_handler_for_set).
+    pub fn set_error_handler(&mut self, error_handler: ErrorHandler) -> &mut Self {
+        self.graph.error_handler = Some(error_handler);
+        self.graph.changed = true;
+        self
+    }
+
+    /// Sets the [`ErrorHandler`] for errors returned by systems in `set`.
+    ///
+    /// If a system is in several sets with an error handler, the handler of the set closest to
+    /// the system in the set hierarchy is used. This takes precedence over the handler set with
+    /// [`set_error_handler`](Self::set_error_handler).
+    pub fn set_error_handler_for_set(
+        &mut self,
+        set: impl SystemSet,
+        error_handler: ErrorHandler,
+    ) -> &mut Self {
+        self.graph
+            .set_error_handlers
+            .insert(set.intern(), error_handler);
+        self.graph.changed = true;
+        self
+    }
+
     /// Returns the schedule's current execution strategy.
     pub fn get_executor_kind(&self) -> ExecutorKind {
         self.executor.kind()
@@ -403,12 +447,16 @@ impl Schedule {
     ///
     /// Moves all systems and run conditions out of the [`ScheduleGraph`].
     pub fn initialize(&mut self, world: &mut World) -> Result<(), ScheduleBuildError> {
+        if self.graph.settings.deterministic_order {
+            world.set_deterministic_query_order(true);
+        }
         if self.graph.changed {
             self.graph.initialize(world);
-            let ignored_ambiguities = world
-                .get_resource_or_init::<Schedules>()
-                .ignored_scheduling_ambiguities
-                .clone();
+            let schedules = world.get_resource_or_init::<Schedules>();
+            let ignored_ambiguities = schedules.ignored_scheduling_ambiguities.clone();
+            self.graph
+                .access_policies
+                .clone_from(&schedules.access_policies);
             self.graph.update_schedule(
                 &mut self.executable,
                 world.components(),
@@ -442,6 +490,230 @@ impl Schedule {
         &self.executable
     }
 
+    /// Describes the systems, sets, run conditions and ordering of this schedule.
+    ///
+    /// The returned [`ScheduleDescription`] can be exported with [`ScheduleDescription::to_dot`],
+    /// or serialized when the `serialize` feature is enabled. Ambiguities and the topological order of the systems
+    /// are only known once the schedule has been initialized, for example by running it once.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// fn a() {}
+    /// fn b() {}
+    ///
+    /// let mut world = World::new();
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems((a, b).chain());
+    /// schedule.initialize(&mut world).unwrap();
+    ///
+    /// let description = schedule.describe(world.components());
+    /// assert_eq!(description.systems.len(), 2);
+    /// assert!(description.to_dot().starts_with("digraph"));
+    /// ```
+    pub fn describe(&self, components: &Components) -> ScheduleDescription {
+        let graph = &self.graph;
+        let systems = self.systems_by_index();
+        let mut set_conditions: Vec<&[BoxedCondition]> = graph
+            .system_set_conditions
+            .iter()
+            .map(Vec::as_slice)
+            .collect();
+        for (i, id) in self.executable.set_ids.iter().enumerate() {
+            set_conditions[id.index()] = &self.executable.set_conditions[i];
+        }
+
+        let system_names = Self::system_names(&systems);
+        let condition_names = |conditions: &[BoxedCondition]| {
+            conditions
+                .iter()
+                .map(|condition| graph.shorten_name(condition.name().to_string()))
+                .collect()
+        };
+
+        let mut hierarchy: Vec<_> = graph.hierarchy.graph.all_edges().collect();
+        hierarchy.sort();
+        let mut dependencies: Vec<_> = graph.dependency.graph.all_edges().collect();
+        dependencies.sort();
+        let mut ambiguities: Vec<_> = graph
+            .conflicting_systems
+            .iter()
+            .map(|(a, b, conflicts)| AmbiguityDescription {
+                first: (*a).min(*b),
+                second: (*a).max(*b),
+                conflicts: conflicts
+                    .iter()
+                    .filter_map(|id| components.get_name(*id))
+                    .map(|name| graph.shorten_name(name.to_string()))
+                    .collect(),
+            })
+            .collect();
+        ambiguities.sort_by_key(|ambiguity| (ambiguity.first, ambiguity.second));
+
+        ScheduleDescription {
+            label: format!("{:?}", self.label),
+            systems: systems
+                .iter()
+                .enumerate()
+                .filter_map(|(index, system)| {
+                    let (system, conditions) = (*system)?;
+                    let id = NodeId::System(index);
+                    Some(SystemDescription {
+                        id,
+                        name: graph.describe_node_name(id, &system_names),
+                        conditions: condition_names(conditions),
+                        is_exclusive: system.is_exclusive(),
+                    })
+                })
+                .collect(),
+            sets: graph
+                .system_sets
+                .iter()
+                .enumerate()
+                .map(|(index, set)| {
+                    let id = NodeId::Set(index);
+                    SystemSetDescription {
+                        id,
+                        name: graph.describe_node_name(id, &system_names),
+                        conditions: condition_names(set_conditions[index]),
+                        is_anonymous: set.is_anonymous(),
+                        is_system_type: set.is_system_type(),
+                    }
+                })
+                .collect(),
+            hierarchy,
+            dependencies,
+            ambiguities,
+            topological_order: self.executable.system_ids.clone(),
+        }
+    }
+
+    /// Analyzes the ambiguities of this schedule: pairs of systems with conflicting data access
+    /// and no ordering between them.
+    ///
+    /// Unlike the log output of [`ScheduleBuildSettings::ambiguity_detection`], the returned [`AmbiguityReport`]
+    /// groups the ambiguities by the component or resource causing them, lists the system sets containing
+    /// each ambiguous system, and suggests a small set of orderings that would resolve all of them.
+    ///
+    /// Ambiguities are only known once the schedule has been initialized, for example by running it once.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource)]
+    /// struct Score(u32);
+    ///
+    /// fn a(_: ResMut<Score>) {}
+    /// fn b(_: ResMut<Score>) {}
+    ///
+    /// let mut world = World::new();
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems((a, b));
+    /// schedule.initialize(&mut world).unwrap();
+    ///
+    /// let report = schedule.ambiguity_report(world.components());
+    /// assert_eq!(report.groups.len(), 1);
+    /// assert_eq!(report.suggested_orderings.len(), 1);
+    /// ```
+    pub fn ambiguity_report(&self, components: &Components) -> AmbiguityReport {
+        let graph = &self.graph;
+        let system_names = Self::system_names(&self.systems_by_index());
+
+        let mut systems: Vec<AmbiguousSystem> = Vec::new();
+        let mut groups: Vec<AmbiguityGroup> = Vec::new();
+        let mut pairs = Vec::with_capacity(graph.conflicting_systems.len());
+        for (a, b, conflicts) in &graph.conflicting_systems {
+            for id in [*a, *b] {
+                if !systems.iter().any(|system| system.id == id) {
+                    systems.push(AmbiguousSystem {
+                        id,
+                        name: graph.describe_node_name(id, &system_names),
+                        sets: graph.names_of_named_sets_containing_node(id, &system_names),
+                    });
+                }
+            }
+            pairs.push((*a, *b));
+
+            let conflicts: Vec<_> = if conflicts.is_empty() {
+                vec![AmbiguityConflict::World]
+            } else {
+                conflicts
+                    .iter()
+                    .map(|&id| AmbiguityConflict::Data {
+                        id,
+                        name: components
+                            .get_name(id)
+                            .map(|name| graph.shorten_name(name.to_string()))
+                            .unwrap_or_default(),
+                    })
+                    .collect()
+            };
+            for conflict in conflicts {
+                match groups.iter_mut().find(|group| group.conflict == conflict) {
+                    Some(group) => group.pairs.push((*a, *b)),
+                    None => groups.push(AmbiguityGroup {
+                        conflict,
+                        pairs: vec![(*a, *b)],
+                        sets: Vec::new(),
+                    }),
+                }
+            }
+        }
+
+        for group in &mut groups {
+            let mut sets: Vec<String> = group
+                .pairs
+                .iter()
+                .flat_map(|(a, b)| [a, b])
+                .filter_map(|id| systems.iter().find(|system| system.id == *id))
+                .flat_map(|system| system.sets.iter().cloned())
+                .collect();
+            sets.sort();
+            sets.dedup();
+            group.sets = sets;
+        }
+        // Show the most impactful conflicts first.
+        groups.sort_by_key(|group| core::cmp::Reverse(group.pairs.len()));
+
+        AmbiguityReport {
+            suggested_orderings: suggest_orderings(
+                &self.executable.system_ids,
+                &self.executable.system_dependents,
+                &pairs,
+            ),
+            systems,
+            groups,
+        }
+    }
+
+    /// Returns each system and its conditions by index, whether they are stored in the graph or,
+    /// once initialized, in the executable schedule.
+    fn systems_by_index(&self) -> Vec<Option<(&ScheduleSystem, &[BoxedCondition])>> {
+        let mut systems: Vec<_> = self
+            .graph
+            .systems
+            .iter()
+            .zip(&self.graph.system_conditions)
+            .map(|(node, conditions)| Some((node.get()?, conditions.as_slice())))
+            .collect();
+        for (i, id) in self.executable.system_ids.iter().enumerate() {
+            systems[id.index()] = Some((
+                &self.executable.systems[i],
+                &self.executable.system_conditions[i],
+            ));
+        }
+        systems
+    }
+
+    fn system_names(systems: &[Option<(&ScheduleSystem, &[BoxedCondition])>]) -> Vec<String> {
+        systems
+            .iter()
+            .map(|system| {
+                system
+                    .map(|(system, _)| system.name().to_string())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
     /// Iterates the change ticks of all systems in the schedule and clamps any older than
     /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
     /// This prevents overflow and thus prevents false positives.
@@ -485,7 +757,8 @@ impl Schedule {
     /// schedule has never been initialized or run.
     pub fn systems(
         &self,
-    ) -> Result<impl Iterator<Item = (NodeId, &BoxedSystem)> + Sized, ScheduleNotInitialized> {
+    ) -> Result<impl Iterator<Item = (NodeId, &ScheduleSystem)> + Sized, ScheduleNotInitialized>
+    {
         if !self.executor_initialized {
             return Err(ScheduleNotInitialized);
         }
@@ -563,23 +836,23 @@ impl SystemSetNode {
     }
 }
 
-/// A [`BoxedSystem`] with metadata, stored in a [`ScheduleGraph`].
+/// A [`ScheduleSystem`] with metadata, stored in a [`ScheduleGraph`].
 struct SystemNode {
-    inner: Option<BoxedSystem>,
+    inner: Option<ScheduleSystem>,
 }
 
 impl SystemNode {
-    pub fn new(system: BoxedSystem) -> Self {
+    pub fn new(system: ScheduleSystem) -> Self {
         Self {
             inner: Some(system),
         }
     }
 
-    pub fn get(&self) -> Option<&BoxedSystem> {
+    pub fn get(&self) -> Option<&ScheduleSystem> {
         self.inner.as_ref()
     }
 
-    pub fn get_mut(&mut self) -> Option<&mut BoxedSystem> {
+    pub fn get_mut(&mut self) -> Option<&mut ScheduleSystem> {
         self.inner.as_mut()
     }
 }
@@ -616,6 +889,12 @@ pub struct ScheduleGraph {
     /// Dependency edges that will **not** automatically insert an instance of `apply_deferred` on the edge.
     no_sync_edges: BTreeSet<(NodeId, NodeId)>,
     auto_sync_node_ids: HashMap<u32, NodeId>,
+    /// Error handler for the systems of the schedule
+    error_handler: Option<ErrorHandler>,
+    /// Error handlers for the systems in specific sets, which take precedence over `error_handler`
+    set_error_handlers: HashMap<InternedSystemSet, ErrorHandler>,
+    /// Access policies of system sets, copied from [`Schedules`] when the schedule is initialized
+    access_policies: HashMap<InternedSystemSet, AccessPolicy>,
 }
 
 impl ScheduleGraph {
@@ -638,11 +917,17 @@ impl ScheduleGraph {
             settings: default(),
             no_sync_edges: BTreeSet::new(),
             auto_sync_node_ids: HashMap::new(),
+            error_handler: None,
+            set_error_handlers: HashMap::new(),
+            access_policies: HashMap::new(),
         }
     }
 
     /// Returns the system at the given [`NodeId`], if it exists.
-    pub fn get_system_at(&self, id: NodeId) -> Option<&dyn System<In = (), Out = ()>> {
+    pub fn get_system_at(
+        &self,
+        id: NodeId,
+    ) -> Option<&dyn System<In = (), Out = Result<(), BevyError>>> {
         if !id.is_system() {
             return None;
         }
@@ -660,7 +945,7 @@ impl ScheduleGraph {
     ///
     /// Panics if it doesn't exist.
     #[track_caller]
-    pub fn system_at(&self, id: NodeId) -> &dyn System<In = (), Out = ()> {
+    pub fn system_at(&self, id: NodeId) -> &dyn System<In = (), Out = Result<(), BevyError>> {
         self.get_system_at(id)
             .ok_or_else(|| format!("system with id {id:?} does not exist in this Schedule"))
             .unwrap()
@@ -687,7 +972,13 @@ impl ScheduleGraph {
     /// Returns an iterator over all systems in this schedule, along with the conditions for each system.
     pub fn systems(
         &self,
-    ) -> impl Iterator<Item = (NodeId, &dyn System<In = (), Out = ()>, &[BoxedCondition])> {
+    ) -> impl Iterator<
+        Item = (
+            NodeId,
+            &dyn System<In = (), Out = Result<(), BevyError>>,
+            &[BoxedCondition],
+        ),
+    > {
         self.systems
             .iter()
             .zip(self.system_conditions.iter())
@@ -1107,6 +1398,9 @@ impl ScheduleGraph {
         // check that there are no edges to system-type sets that have multiple instances
         self.check_system_type_set_ambiguity(&set_systems)?;
 
+        // check that the systems of sets with an access policy follow it
+        self.check_access_policies(&set_systems, components)?;
+
         let mut dependency_flattened = self.get_dependency_flattened(&set_systems);
 
         // modify graph with auto sync points
@@ -1140,10 +1434,76 @@ impl ScheduleGraph {
         self.optionally_check_conflicts(&conflicting_systems, components, schedule_label)?;
         self.conflicting_systems = conflicting_systems;
 
+        if self.settings.deterministic_order {
+            self.order_conflicting_systems(
+                &mut dependency_flattened_dag,
+                &flat_results.disconnected,
+            );
+        }
+
         // build the schedule
         Ok(self.build_schedule_inner(dependency_flattened_dag, hier_results.reachable))
     }
 
+    /// Returns an [`AccessPolicyViolation`](ScheduleBuildError::AccessPolicyViolation) error if a system or
+    /// run condition in a set violates the [`AccessPolicy`] of the set.
+    fn check_access_policies(
+        &self,
+        set_systems: &HashMap<NodeId, Vec<NodeId>>,
+        components: &Components,
+    ) -> Result<(), ScheduleBuildError> {
+        let mut message = String::new();
+        for (set, policy) in &self.access_policies {
+            let Some(set_id) = self.system_set_ids.get(set) else {
+                continue;
+            };
+            let mut check = |name: &str, violations: Vec<String>| {
+                for violation in violations {
+                    writeln!(message, " -- {name} in set `{set:?}` {violation}").unwrap();
+                }
+            };
+            // The conditions of the set and of the sets nested in it
+            let mut sets = vec![*set_id];
+            let mut visited = HashSet::new();
+            while let Some(set_id) = sets.pop() {
+                if !visited.insert(set_id) {
+                    continue;
+                }
+                for condition in &self.system_set_conditions[set_id.index()] {
+                    check(
+                        &condition.name(),
+                        policy.violations(components, condition.component_access(), false, false),
+                    );
+                }
+                sets.extend(
+                    self.hierarchy
+                        .graph
+                        .neighbors_directed(set_id, Outgoing)
+                        .filter(NodeId::is_set),
+                );
+            }
+            for &system_id in set_systems.get(set_id).into_iter().flatten() {
+                let system = self.systems[system_id.index()].get().unwrap();
+                check(
+                    &system.name(),
+                    policy.system_violations(components, &**system),
+                );
+                for condition in &self.system_conditions[system_id.index()] {
+                    check(
+                        &condition.name(),
+                        policy.violations(components, condition.component_access(), false, false),
+                    );
+                }
+            }
+        }
+
+        if message.is_empty() {
+            Ok(())
+        } else {
+            Err(ScheduleBuildError::AccessPolicyViolation(message))
+        }
+    }
+
     // modify the graph to have sync nodes for any dependents after a system with deferred system params
     fn auto_insert_apply_deferred(
         &mut self,
@@ -1196,8 +1556,8 @@ impl ScheduleGraph {
         let id = NodeId::System(self.systems.len());
 
         self.systems
-            .push(SystemNode::new(Box::new(IntoSystem::into_system(
-                apply_deferred,
+            .push(SystemNode::new(Box::new(InfallibleSystemWrapper::new(
+                Box::new(IntoSystem::into_system(apply_deferred)),
             ))));
         self.system_conditions.push(Vec::new());
 
@@ -1387,6 +1747,48 @@ impl ScheduleGraph {
         conflicting_systems
     }
 
+    /// Adds an edge between each pair of unordered systems that conflict, following the topological sort.
+    ///
+    /// The topological sort is still valid afterwards, since every edge goes forward in it.
+    fn order_conflicting_systems(
+        &self,
+        dag: &mut Dag,
+        flat_results_disconnected: &[(NodeId, NodeId)],
+    ) {
+        let position: HashMap<NodeId, usize> = dag
+            .topsort
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect();
+        for &(a, b) in flat_results_disconnected {
+            let system_a = self.systems[a.index()].get().unwrap();
+            let system_b = self.systems[b.index()].get().unwrap();
+            // Automatically inserted sync points are exclusive, but don't access any data themselves.
+            // Ordering them against every unrelated system would serialize the whole schedule.
+            if self.is_auto_sync_node(a) || self.is_auto_sync_node(b) {
+                continue;
+            }
+            let conflicting = system_a.is_exclusive()
+                || system_b.is_exclusive()
+                || (system_a.has_deferred() && system_b.has_deferred())
+                || !system_a
+                    .component_access()
+                    .is_compatible(system_b.component_access());
+            if conflicting {
+                if position[&a] < position[&b] {
+                    dag.graph.add_edge(a, b);
+                } else {
+                    dag.graph.add_edge(b, a);
+                }
+            }
+        }
+    }
+
+    fn is_auto_sync_node(&self, id: NodeId) -> bool {
+        self.auto_sync_node_ids.values().any(|&node| node == id)
+    }
+
     fn build_schedule_inner(
         &self,
         dependency_flattened_dag: Dag,
@@ -1477,6 +1879,7 @@ impl ScheduleGraph {
         SystemSchedule {
             systems: Vec::with_capacity(sys_count),
             system_conditions: Vec::with_capacity(sys_count),
+            error_handlers: Vec::with_capacity(sys_count),
             set_conditions: Vec::with_capacity(set_with_conditions_count),
             system_ids: dg_system_ids,
             set_ids: hg_set_ids,
@@ -1487,6 +1890,28 @@ impl ScheduleGraph {
         }
     }
 
+    /// Returns the error handler of the system: the handler of the closest set containing it,
+    /// or else the handler of the schedule.
+    fn error_handler_of(&self, id: NodeId) -> Option<ErrorHandler> {
+        if !self.set_error_handlers.is_empty() {
+            let mut visited = HashSet::new();
+            let mut queue = VecDeque::from([id]);
+            while let Some(node) = queue.pop_front() {
+                for parent in self.hierarchy.graph.neighbors_directed(node, Incoming) {
+                    if !visited.insert(parent) {
+                        continue;
+                    }
+                    let set = &self.system_sets[parent.index()].inner;
+                    if let Some(&error_handler) = self.set_error_handlers.get(set) {
+                        return Some(error_handler);
+                    }
+                    queue.push_back(parent);
+                }
+            }
+        }
+        self.error_handler
+    }
+
     /// Updates the `SystemSchedule` from the `ScheduleGraph`.
     fn update_schedule(
         &mut self,
@@ -1526,6 +1951,7 @@ impl ScheduleGraph {
             let conditions = core::mem::take(&mut self.system_conditions[id.index()]);
             schedule.systems.push(system);
             schedule.system_conditions.push(conditions);
+            schedule.error_handlers.push(self.error_handler_of(id));
         }
 
         for &id in &schedule.set_ids {
@@ -1554,7 +1980,7 @@ trait ProcessNodeConfig: Sized {
     fn process_config(schedule_graph: &mut ScheduleGraph, config: NodeConfig<Self>) -> NodeId;
 }
 
-impl ProcessNodeConfig for BoxedSystem {
+impl ProcessNodeConfig for ScheduleSystem {
     fn process_config(schedule_graph: &mut ScheduleGraph, config: NodeConfig<Self>) -> NodeId {
         schedule_graph.add_system_inner(config).unwrap()
     }
@@ -1625,6 +2051,60 @@ impl ScheduleGraph {
         )
     }
 
+    /// Like [`Self::get_node_name`], but without listing the sets containing systems, and using
+    /// `system_names` since the systems may have been moved into the executable schedule.
+    fn describe_node_name(&self, id: NodeId, system_names: &[String]) -> String {
+        let name = match id {
+            NodeId::System(index) => system_names[index].clone(),
+            NodeId::Set(index) => {
+                let set = &self.system_sets[index];
+                if set.is_anonymous() {
+                    let members: Vec<_> = self
+                        .hierarchy
+                        .graph
+                        .edges_directed(id, Outgoing)
+                        .map(|(_, member_id)| self.describe_node_name(member_id, system_names))
+                        .collect();
+                    format!("({})", members.join(", "))
+                } else {
+                    set.name()
+                }
+            }
+        };
+        self.shorten_name(name)
+    }
+
+    /// Returns the names of the sets containing the given node, excluding system type sets and anonymous sets.
+    fn names_of_named_sets_containing_node(
+        &self,
+        id: NodeId,
+        system_names: &[String],
+    ) -> Vec<String> {
+        let mut sets = HashSet::new();
+        self.traverse_sets_containing_node(id, &mut |set_id| {
+            let set = &self.system_sets[set_id.index()];
+            sets.insert(set_id) && !set.is_system_type()
+        });
+        let mut names: Vec<_> = sets
+            .into_iter()
+            .filter(|set_id| {
+                let set = &self.system_sets[set_id.index()];
+                !set.is_system_type() && !set.is_anonymous()
+            })
+            .map(|set_id| self.describe_node_name(set_id, system_names))
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn shorten_name(&self, name: String) -> String {
+        if self.settings.use_shortnames {
+            ShortName(&name).to_string()
+        } else {
+            name
+        }
+    }
+
     fn get_node_kind(&self, id: &NodeId) -> &'static str {
         match id {
             NodeId::System(_) => "system",
@@ -1968,6 +2448,9 @@ pub enum ScheduleBuildError {
     /// This error is disabled by default, but can be opted-in using [`ScheduleBuildSettings`].
     #[display("Systems with conflicting access have indeterminate run order.\n{_0}")]
     Ambiguity(String),
+    /// A system or run condition accesses data that is denied by the [`AccessPolicy`] of one of its sets.
+    #[display("Systems violate the access policy of their sets.\n{_0}")]
+    AccessPolicyViolation(String),
     /// Tried to run a schedule before all of its systems have been initialized.
     #[display("Systems in schedule have not been initialized.")]
     Uninitialized,
@@ -2016,6 +2499,37 @@ pub struct ScheduleBuildSettings {
     ///
     /// Defaults to `true`.
     pub report_sets: bool,
+    /// If set to true, systems with conflicting access and no explicit order are run in the order of the
+    /// topological sort of the schedule, which is the order in which the single-threaded executor runs them.
+    /// This applies to ambiguities that are ignored or allowed with `ambiguous_with` too.
+    ///
+    /// Systems with [deferred](crate::system::System::has_deferred) parameters, like [`Commands`](crate::system::Commands),
+    /// are considered conflicting with each other, since the entities they reserve and the order of the events
+    /// they send depend on the order in which they run.
+    ///
+    /// Sync points inserted by [`auto_insert_apply_deferred`](Self::auto_insert_apply_deferred) are not ordered
+    /// against unrelated systems. The commands of a system that isn't ordered relative to such a sync point
+    /// may be applied there or at a later one, so systems that read data changed by those commands should
+    /// be ordered after them explicitly.
+    ///
+    /// With this, the multi-threaded executor produces the same results as the single-threaded executor, and
+    /// running the same schedule on the same world allocates the same [`Entity`](crate::entity::Entity) ids.
+    /// This is useful for lockstep networking and replays, at the cost of less parallelism. Use
+    /// [`World::checksum`](crate::world::World::checksum) to compare the resulting worlds between runs.
+    /// Use [`App::configure_schedules`] to enable this for every schedule of an app.
+    ///
+    /// Initializing the schedule also enables [`World::set_deterministic_query_order`](crate::world::World::set_deterministic_query_order)
+    /// on its world, so that queries iterate tables and archetypes in an order that doesn't depend on the order
+    /// in which they were created. Entities within a table or archetype are still iterated in the order in which
+    /// they were moved there; use [`QueryIter::sort`](crate::query::QueryIter::sort) with
+    /// [`Entity`](crate::entity::Entity) to iterate in an order that only depends on the entities. Work spawned
+    /// by systems onto task pools, like [`Query::par_iter`](crate::system::Query::par_iter) with
+    /// [`ParallelCommands`](crate::system::ParallelCommands), is not ordered either.
+    ///
+    /// [`App::configure_schedules`]: https://docs.rs/bevy/latest/bevy/app/struct.App.html#method.configure_schedules
+    ///
+    /// Defaults to `false`.
+    pub deterministic_order: bool,
 }
 
 impl Default for ScheduleBuildSettings {
@@ -2034,6 +2548,7 @@ impl ScheduleBuildSettings {
             auto_insert_apply_deferred: true,
             use_shortnames: true,
             report_sets: true,
+            deterministic_order: false,
         }
     }
 }
@@ -2641,4 +3156,233 @@ mod tests {
             .expect("CheckSystemRan Resource Should Exist");
         assert_eq!(value.0, 2);
     }
+
+    #[test]
+    fn describe_schedule() {
+        use crate::schedule::{Condition, NodeId};
+
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Set;
+
+        fn first(_: ResMut<Resource1>) {}
+        fn second(_: ResMut<Resource1>) {}
+        fn third(_: ResMut<Resource1>) {}
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            use_shortnames: true,
+            ..Default::default()
+        });
+        schedule.configure_sets(Set.run_if((|| true).and(|| true)));
+        schedule.add_systems(((first, second).chain().in_set(Set), third));
+        schedule.initialize(&mut world).unwrap();
+
+        let description = schedule.describe(world.components());
+        let names: Vec<_> = description
+            .systems
+            .iter()
+            .map(|system| system.name.as_str())
+            .collect();
+        assert!(names.iter().any(|name| name.ends_with("first")));
+        assert_eq!(description.systems.len(), 3);
+
+        let set = description
+            .sets
+            .iter()
+            .find(|set| set.name == "Set")
+            .unwrap();
+        assert_eq!(set.conditions.len(), 1);
+        let first_id = description.systems[0].id;
+        let second_id = description.systems[1].id;
+        assert!(description.dependencies.contains(&(first_id, second_id)));
+        assert!(description
+            .hierarchy
+            .iter()
+            .any(|&(parent, _)| parent == set.id));
+
+        // `third` is ambiguous with both chained systems.
+        assert_eq!(description.ambiguities.len(), 2);
+        assert!(description.ambiguities[0].conflicts[0].ends_with("Resource1"));
+
+        let order = &description.topological_order;
+        assert_eq!(order.len(), 3);
+        let position = |id: NodeId| order.iter().position(|other| *other == id).unwrap();
+        assert!(position(first_id) < position(second_id));
+
+        // The description is the same before and after running the schedule.
+        schedule.run(&mut world);
+        assert_eq!(schedule.describe(world.components()), description);
+
+        let dot = description.to_dot();
+        assert!(dot.contains("system_0 -> system_1;"));
+        assert!(dot.contains("color=red"));
+
+        #[cfg(feature = "serialize")]
+        {
+            let ron = ron::to_string(&description).unwrap();
+            assert_eq!(
+                ron::from_str::<crate::schedule::ScheduleDescription>(&ron).unwrap(),
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn ambiguity_report() {
+        use crate::schedule::AmbiguityConflict;
+
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct PhysicsSet;
+
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct AudioSet;
+
+        fn physics_a(_: ResMut<Resource1>) {}
+        fn physics_b(_: ResMut<Resource1>, _: ResMut<Resource2>) {}
+        fn audio(_: ResMut<Resource1>, _: Res<Resource2>) {}
+        fn exclusive(_: &mut World) {}
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            use_shortnames: true,
+            ..Default::default()
+        });
+        schedule.add_systems((
+            (physics_a, physics_b).in_set(PhysicsSet),
+            audio.in_set(AudioSet),
+            exclusive.after(physics_a).after(physics_b).after(audio),
+        ));
+        schedule.initialize(&mut world).unwrap();
+
+        let report = schedule.ambiguity_report(world.components());
+        assert_eq!(report.systems.len(), 3);
+        assert_eq!(report.groups.len(), 2);
+
+        let resource1 = &report.groups[0];
+        assert!(
+            matches!(&resource1.conflict, AmbiguityConflict::Data { name, .. } if name == "Resource1")
+        );
+        assert_eq!(resource1.pairs.len(), 3);
+        assert_eq!(resource1.sets, ["AudioSet", "PhysicsSet"]);
+        assert_eq!(report.groups[1].pairs.len(), 1);
+
+        let audio_id = report
+            .systems
+            .iter()
+            .find(|system| system.name.ends_with("audio"))
+            .unwrap();
+        assert_eq!(audio_id.sets, ["AudioSet"]);
+
+        // Two orderings are enough to resolve the three ambiguous pairs.
+        assert_eq!(report.suggested_orderings.len(), 2);
+        let resolved: usize = report
+            .suggested_orderings
+            .iter()
+            .map(|ordering| ordering.resolves.len())
+            .sum();
+        assert_eq!(resolved, 3);
+
+        let mut unambiguous = Schedule::default();
+        unambiguous.add_systems((physics_a, physics_b).chain());
+        unambiguous.initialize(&mut world).unwrap();
+        assert!(unambiguous.ambiguity_report(world.components()).is_empty());
+    }
+
+    #[test]
+    fn deterministic_order() {
+        use crate::{
+            component::Component, schedule::ExecutorKind, world::checksum::ChecksumConfig,
+        };
+
+        #[derive(Component, Hash)]
+        struct Order(usize);
+
+        #[derive(Resource, Default, Hash)]
+        struct Log(Vec<usize>);
+
+        fn schedule(executor: ExecutorKind) -> Schedule {
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            schedule.set_build_settings(ScheduleBuildSettings {
+                deterministic_order: true,
+                ..Default::default()
+            });
+            for i in 0..8 {
+                schedule.add_systems(move |mut log: ResMut<Log>| log.0.push(i));
+                schedule.add_systems(move |mut commands: Commands| {
+                    commands.spawn(Order(i));
+                });
+            }
+            schedule
+        }
+
+        // Every pair of conflicting systems is ordered, so the executor has no choice left.
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut single_threaded = schedule(ExecutorKind::SingleThreaded);
+        single_threaded.initialize(&mut world).unwrap();
+        let executable = single_threaded.executable();
+        let roots = executable
+            .system_dependencies
+            .iter()
+            .filter(|&&dependencies| dependencies == 0)
+            .count();
+        assert_eq!(roots, 2);
+        for _ in 0..3 {
+            single_threaded.run(&mut world);
+        }
+
+        let mut config = ChecksumConfig::new();
+        config.component::<Order>().resource::<Log>();
+        let expected = world.checksum(&config);
+
+        bevy_tasks::ComputeTaskPool::get_or_init(|| {
+            bevy_tasks::TaskPoolBuilder::new().num_threads(4).build()
+        });
+        for _ in 0..3 {
+            let mut world = World::new();
+            world.init_resource::<Log>();
+            let mut multi_threaded = schedule(ExecutorKind::MultiThreaded);
+            for _ in 0..3 {
+                multi_threaded.run(&mut world);
+            }
+            assert_eq!(world.checksum(&config), expected);
+        }
+    }
+
+    #[test]
+    fn deterministic_order_ignores_auto_sync_points() {
+        use crate::{entity::Entity, system::Query};
+
+        #[derive(Resource)]
+        struct R;
+
+        fn spawn(mut commands: Commands) {
+            commands.spawn_empty();
+        }
+        fn after_spawn(_: Query<Entity>) {}
+        fn unrelated(_: Option<Res<R>>) {}
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            deterministic_order: true,
+            ..Default::default()
+        });
+        schedule.add_systems(((spawn, after_spawn).chain(), unrelated));
+        schedule.initialize(&mut world).unwrap();
+
+        // `spawn`, the sync point after it and `after_spawn`, and `unrelated` which depends on none of them.
+        let executable = schedule.executable();
+        assert_eq!(executable.systems.len(), 4);
+        let unrelated = executable
+            .systems
+            .iter()
+            .position(|system| system.name().contains("unrelated"))
+            .unwrap();
+        assert_eq!(executable.system_dependencies[unrelated], 0);
+        assert!(executable.system_dependents[unrelated].is_empty());
+    }
 }
@@ -82,6 +82,12 @@ impl BlobVec {
         self.len
     }
 
+    /// Returns the number of elements the vector can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     /// Returns `true` if the vector contains no elements.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -171,6 +177,43 @@ impl BlobVec {
         self.capacity = new_capacity;
     }
 
+    /// Shrinks the capacity of the vector to its length.
+    ///
+    /// Does nothing for ZSTs, whose capacity is always `usize::MAX`.
+    pub fn shrink_to_fit(&mut self) {
+        if self.item_layout.size() == 0 || self.capacity == self.len {
+            return;
+        }
+        let current_layout =
+            array_layout(&self.item_layout, self.capacity).expect("array layout should be valid");
+        if self.len == 0 {
+            // SAFETY:
+            // - ptr was be allocated via this allocator, since the capacity is non-zero and the item isn't a ZST
+            // - the layout of the ptr is `array_layout(self.item_layout, self.capacity)`
+            unsafe { alloc::alloc::dealloc(self.get_ptr_mut().as_ptr(), current_layout) };
+            let align =
+                NonZero::<usize>::new(self.item_layout.align()).expect("alignment must be > 0");
+            self.data = bevy_ptr::dangling_with_align(align);
+        } else {
+            let new_layout =
+                array_layout(&self.item_layout, self.len).expect("array layout should be valid");
+            // SAFETY:
+            // - ptr was be allocated via this allocator
+            // - the layout of the ptr is `array_layout(self.item_layout, self.capacity)`
+            // - `item_layout.size() > 0` and `self.len > 0`, so the layout size is non-zero
+            // - the new size is smaller than the current size, so it can't overflow
+            let new_data = unsafe {
+                alloc::alloc::realloc(
+                    self.get_ptr_mut().as_ptr(),
+                    current_layout,
+                    new_layout.size(),
+                )
+            };
+            self.data = NonNull::new(new_data).unwrap_or_else(|| handle_alloc_error(new_layout));
+        }
+        self.capacity = self.len;
+    }
+
     /// Initializes the value at `index` to `value`. This function does not do any bounds checking.
     ///
     /// # Safety
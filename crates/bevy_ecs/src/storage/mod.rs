@@ -31,6 +31,8 @@ pub use resource::*;
 pub use sparse_set::*;
 pub use table::*;
 
+use crate::{self as bevy_ecs, system::Resource};
+
 /// The raw data stores of a [`World`](crate::world::World)
 #[derive(Default)]
 pub struct Storages {
@@ -43,3 +45,66 @@ pub struct Storages {
     /// Backing storage for `!Send` resources.
     pub non_send_resources: Resources<false>,
 }
+
+/// Decides which storages are shrunk by [`World::compact_storage_with`](crate::world::World::compact_storage_with).
+///
+/// Tables, sparse sets and archetypes grow their allocations as entities are added to them, but never shrink
+/// them when entities are removed. A storage is shrunk to fit its content when at least
+/// [`min_spare_capacity`](Self::min_spare_capacity) slots, and at least [`min_spare_ratio`](Self::min_spare_ratio)
+/// of its capacity, are unused. Empty storages are shrunk to a capacity of zero, freeing all of their memory.
+///
+/// Shrinking reallocates the storage, so it is best done after large changes, like loading a new level.
+/// When inserted as a resource, this is the policy used by the [`compact_storage`] system.
+///
+/// # Limitations
+///
+/// - Freeing or reusing empty tables and archetypes is not supported. They are shrunk to a capacity of zero,
+///   but never removed: their ids stay valid for the lifetime of the [`World`](crate::world::World), since
+///   queries and bundles cache them. Each one keeps a small fixed-size footprint, so worlds that create many
+///   short-lived archetypes still grow over time.
+/// - Nothing is compacted automatically by default. Add the [`compact_storage`] system to a schedule, for
+///   example on a state transition, or use the `StorageCompactionPlugin` of `bevy_app` to run it periodically.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct CompactionPolicy {
+    /// The minimum fraction of the capacity of a storage that must be unused for it to be shrunk.
+    pub min_spare_ratio: f32,
+    /// The minimum number of unused slots a storage must have to be shrunk.
+    pub min_spare_capacity: usize,
+}
+
+impl CompactionPolicy {
+    /// Shrinks every storage with unused capacity.
+    pub const ALL: Self = Self {
+        min_spare_ratio: 0.0,
+        min_spare_capacity: 1,
+    };
+
+    /// Returns `true` if a storage with `len` used slots out of `capacity` should be shrunk.
+    pub fn should_shrink(&self, len: usize, capacity: usize) -> bool {
+        let spare = capacity - len;
+        spare > 0
+            && spare >= self.min_spare_capacity
+            && spare as f32 >= self.min_spare_ratio * capacity as f32
+    }
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> Self {
+        Self {
+            min_spare_ratio: 0.5,
+            min_spare_capacity: 64,
+        }
+    }
+}
+
+/// A system that shrinks the storages of the world following the [`CompactionPolicy`] resource,
+/// or the default policy if there is none.
+///
+/// See [`World::compact_storage_with`](crate::world::World::compact_storage_with).
+pub fn compact_storage(world: &mut crate::world::World) {
+    let policy = world
+        .get_resource::<CompactionPolicy>()
+        .cloned()
+        .unwrap_or_default();
+    world.compact_storage_with(&policy);
+}
@@ -2,7 +2,7 @@ use crate::{
     change_detection::MaybeUnsafeCellLocation,
     component::{ComponentId, ComponentInfo, ComponentTicks, Tick, TickCells},
     entity::Entity,
-    storage::{Column, TableRow},
+    storage::{Column, CompactionPolicy, TableRow},
 };
 use bevy_ptr::{OwningPtr, Ptr};
 #[cfg(feature = "track_change_detection")]
@@ -106,6 +106,17 @@ impl<I: SparseSetIndex, V> SparseArray<I, V> {
         self.values.clear();
     }
 
+    /// Shrinks the capacity of the array to fit its last value.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let len = self
+            .values
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |i| i + 1);
+        self.values.truncate(len);
+        self.values.shrink_to_fit();
+    }
+
     /// Converts the [`SparseArray`] into an immutable variant.
     pub(crate) fn into_immutable(self) -> ImmutableSparseArray<I, V> {
         ImmutableSparseArray {
@@ -155,6 +166,19 @@ impl ComponentSparseSet {
         self.dense.len()
     }
 
+    /// Returns the number of component values the sparse set can store without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.dense.capacity()
+    }
+
+    /// Shrinks the capacity of the sparse set to its number of component values.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.dense.shrink_to_fit();
+        self.entities.shrink_to_fit();
+        self.sparse.shrink_to_fit();
+    }
+
     /// Returns `true` if the sparse set contains no component values.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -640,6 +664,16 @@ impl SparseSets {
         self.sets.get_mut(component_info.id()).unwrap()
     }
 
+    /// Shrinks the [`ComponentSparseSet`]s whose unused capacity matches `policy`.
+    pub(crate) fn compact(&mut self, policy: &CompactionPolicy) {
+        for set in self.sets.values_mut() {
+            // The capacity of the dense values is always `usize::MAX` for ZSTs.
+            if policy.should_shrink(set.len(), set.entities.capacity()) {
+                set.shrink_to_fit();
+            }
+        }
+    }
+
     /// Gets a mutable reference to the [`ComponentSparseSet`] of a [`ComponentId`].
     pub(crate) fn get_mut(&mut self, component_id: ComponentId) -> Option<&mut ComponentSparseSet> {
         self.sets.get_mut(component_id)
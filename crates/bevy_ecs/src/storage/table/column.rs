@@ -120,6 +120,18 @@ impl ThinColumn {
         self.changed_by.realloc(current_capacity, new_capacity);
     }
 
+    /// Deallocates the memory of this empty [`ThinColumn`], which can then be allocated again with [`Self::alloc`].
+    ///
+    /// # Safety
+    /// - `current_capacity` must be the current capacity of this column
+    /// - The column must not contain any element.
+    /// - The caller should make sure their saved `capacity` value is updated to 0 after this operation.
+    pub(crate) unsafe fn dealloc(&mut self, current_capacity: usize) {
+        // Dropping the arrays with a length of 0 only frees their memory, and sets their capacity to 0,
+        // which is the state `alloc` expects.
+        self.drop(current_capacity, 0);
+    }
+
     /// Call [`alloc`](std::alloc::alloc) to allocate memory for this [`ThinColumn`]
     /// The caller should make sure their saved `capacity` value is updated to `new_capacity` after this operation.
     pub(crate) fn alloc(&mut self, new_capacity: NonZeroUsize) {
@@ -397,6 +409,21 @@ impl Column {
         self.data.len()
     }
 
+    /// Gets the number of elements the column can store without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Shrinks the capacity of the column to its length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+        self.added_ticks.shrink_to_fit();
+        self.changed_ticks.shrink_to_fit();
+        #[cfg(feature = "track_change_detection")]
+        self.changed_by.shrink_to_fit();
+    }
+
     /// Checks if the column is empty. Returns `true` if there are no elements, `false` otherwise.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -3,7 +3,7 @@ use crate::{
     component::{ComponentId, ComponentInfo, ComponentTicks, Components, Tick},
     entity::Entity,
     query::DebugCheckedUnwrap,
-    storage::{blob_vec::BlobVec, ImmutableSparseSet, SparseSet},
+    storage::{blob_vec::BlobVec, CompactionPolicy, ImmutableSparseSet, SparseSet},
 };
 use bevy_ptr::{OwningPtr, Ptr, UnsafeCellDeref};
 use bevy_utils::HashMap;
@@ -519,6 +519,40 @@ impl Table {
         }
     }
 
+    /// Shrinks the capacity of the table to its number of entities, freeing the memory of empty tables.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let column_cap = self.capacity();
+        self.entities.shrink_to_fit();
+        // use entities vector capacity as driving capacity for all related allocations
+        let new_capacity = self.entities.capacity();
+        if new_capacity == column_cap {
+            return;
+        }
+
+        // If any of these allocations trigger an unwind, the wrong capacity will be used while dropping this table - UB.
+        // To avoid this, we use `AbortOnPanic`. If the allocation triggered a panic, the `AbortOnPanic`'s Drop impl will be
+        // called, and abort the program.
+        let _guard = AbortOnPanic;
+        match NonZeroUsize::new(new_capacity) {
+            // SAFETY:
+            // - `column_cap` is indeed the columns' capacity
+            // - the table is empty, so the columns don't contain any element
+            None => unsafe {
+                for col in self.columns.values_mut() {
+                    col.dealloc(column_cap);
+                }
+            },
+            // SAFETY:
+            // - `column_cap` is indeed the columns' capacity, and it's greater than `new_capacity`
+            Some(new_capacity) => unsafe {
+                for col in self.columns.values_mut() {
+                    col.realloc(NonZeroUsize::new_unchecked(column_cap), new_capacity);
+                }
+            },
+        }
+        core::mem::forget(_guard); // The allocation was successful, so we don't drop the guard.
+    }
+
     /// Allocate memory for the columns in the [`Table`]
     ///
     /// The current capacity of the columns should be 0, if it's not 0, then the previous data will be overwritten and leaked.
@@ -619,6 +653,11 @@ impl Table {
         }
     }
 
+    /// Iterates over the [`ComponentId`]s of the columns of the [`Table`].
+    pub fn components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.columns.indices()
+    }
+
     /// Iterates over the [`ThinColumn`]s of the [`Table`].
     pub fn iter_columns(&self) -> impl Iterator<Item = &ThinColumn> {
         self.columns.values()
@@ -776,6 +815,15 @@ impl Tables {
             table.check_change_ticks(change_tick);
         }
     }
+
+    /// Shrinks the [`Table`]s whose unused capacity matches `policy`.
+    pub(crate) fn compact(&mut self, policy: &CompactionPolicy) {
+        for table in &mut self.tables {
+            if policy.should_shrink(table.entity_count(), table.capacity()) {
+                table.shrink_to_fit();
+            }
+        }
+    }
 }
 
 impl Index<TableId> for Tables {
@@ -12,6 +12,7 @@ use crate::{
     change_detection::Mut,
     component::{Component, ComponentId, ComponentInfo},
     entity::{Entities, Entity, EntityCloneBuilder},
+    error::{self, ErrorHandler, HandleError, Never, Result},
     event::{Event, SendEvent},
     observer::{Observer, TriggerEvent, TriggerTargets},
     schedule::ScheduleLabel,
@@ -632,7 +633,45 @@ impl<'w, 's> Commands<'w, 's> {
     /// # bevy_ecs::system::assert_is_system(add_three_to_counter_system);
     /// # bevy_ecs::system::assert_is_system(add_twenty_five_to_counter_system);
     /// ```
-    pub fn queue<C: Command>(&mut self, command: C) {
+    ///
+    /// If the command returns a [`Result`], errors are passed to the [`DefaultErrorHandler`](error::DefaultErrorHandler)
+    /// of the world. Use [`queue_handled`](Self::queue_handled) to pick a different [`ErrorHandler`].
+    pub fn queue<C: Command<T> + HandleError<T>, T>(&mut self, command: C) {
+        self.queue_internal(command.handle_error());
+    }
+
+    /// Pushes a generic [`Command`] to the command queue, passing the errors it returns to `error_handler`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// use bevy_ecs::error::{ignore, Result};
+    ///
+    /// #[derive(Resource)]
+    /// struct Score(u32);
+    ///
+    /// fn reset_score(mut commands: Commands) {
+    ///     // Do nothing if the resource doesn't exist.
+    ///     commands.queue_handled(
+    ///         |world: &mut World| -> Result {
+    ///             world.get_resource_mut::<Score>().ok_or("no score")?.0 = 0;
+    ///             Ok(())
+    ///         },
+    ///         ignore,
+    ///     );
+    /// }
+    /// # bevy_ecs::system::assert_is_system(reset_score);
+    /// ```
+    pub fn queue_handled<C: Command<T> + HandleError<T>, T>(
+        &mut self,
+        command: C,
+        error_handler: ErrorHandler,
+    ) {
+        self.queue_internal(command.handle_error_with(error_handler));
+    }
+
+    fn queue_internal(&mut self, command: impl Command) {
         match &mut self.queue {
             InternalQueue::CommandQueue(queue) => {
                 queue.push(command);
@@ -1141,7 +1180,9 @@ impl<'w, 's> Commands<'w, 's> {
 /// ```
 pub trait EntityCommand<Marker = ()>: Send + 'static {
     /// Executes this command for the given [`Entity`].
-    fn apply(self, entity: Entity, world: &mut World);
+    ///
+    /// Returned errors are passed to an [`ErrorHandler`], see [`EntityCommands::queue_handled`].
+    fn apply(self, entity: Entity, world: &mut World) -> Result;
 
     /// Returns a [`Command`] which executes this [`EntityCommand`] for the given [`Entity`].
     ///
@@ -1150,7 +1191,7 @@ pub trait EntityCommand<Marker = ()>: Send + 'static {
     /// footprint than `(Entity, Self)`.
     /// In most cases the provided implementation is sufficient.
     #[must_use = "commands do nothing unless applied to a `World`"]
-    fn with_entity(self, entity: Entity) -> impl Command
+    fn with_entity(self, entity: Entity) -> impl Command<Result>
     where
         Self: Sized,
     {
@@ -1668,7 +1709,7 @@ impl<'a> EntityCommands<'a> {
     /// ```
     #[track_caller]
     pub fn despawn(&mut self) {
-        self.queue(despawn());
+        self.queue_handled(despawn(), error::warn);
     }
 
     /// Despawns the entity.
@@ -1676,7 +1717,7 @@ impl<'a> EntityCommands<'a> {
     /// the same function as [`Self::despawn`] without emitting warnings.
     #[track_caller]
     pub fn try_despawn(&mut self) {
-        self.queue(try_despawn());
+        self.queue_handled(despawn(), error::ignore);
     }
 
     /// Pushes an [`EntityCommand`] to the queue, which will get executed for the current [`Entity`].
@@ -1695,11 +1736,46 @@ impl<'a> EntityCommands<'a> {
     /// # }
     /// # bevy_ecs::system::assert_is_system(my_system);
     /// ```
+    ///
+    /// Errors returned by the command, including the entity not existing when the command is applied,
+    /// are passed to the [`DefaultErrorHandler`](error::DefaultErrorHandler) of the world.
+    /// Use [`queue_handled`](Self::queue_handled) to pick a different [`ErrorHandler`].
     pub fn queue<M: 'static>(&mut self, command: impl EntityCommand<M>) -> &mut Self {
         self.commands.queue(command.with_entity(self.entity));
         self
     }
 
+    /// Pushes an [`EntityCommand`] to the queue, which will get executed for the current [`Entity`].
+    /// Errors returned by the command are passed to `error_handler`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// use bevy_ecs::error::warn;
+    ///
+    /// # #[derive(Component)]
+    /// # struct Target;
+    /// fn mark_target(mut commands: Commands, target: Res<CurrentTarget>) {
+    ///     // The target may have been despawned in the meantime, which is fine.
+    ///     commands
+    ///         .entity(target.0)
+    ///         .queue_handled(|mut entity: EntityWorldMut| { entity.insert(Target); }, warn);
+    /// }
+    /// # #[derive(Resource)]
+    /// # struct CurrentTarget(Entity);
+    /// # bevy_ecs::system::assert_is_system(mark_target);
+    /// ```
+    pub fn queue_handled<M: 'static>(
+        &mut self,
+        command: impl EntityCommand<M>,
+        error_handler: ErrorHandler,
+    ) -> &mut Self {
+        self.commands
+            .queue_handled(command.with_entity(self.entity), error_handler);
+        self
+    }
+
     /// Removes all components except the given [`Bundle`] from the entity.
     ///
     /// This can also be used to remove all the components from the entity by passing it an empty Bundle.
@@ -1884,12 +1960,12 @@ impl<'a, T: Component> EntityEntryCommands<'a, T> {
     }
 }
 
-impl<F> Command for F
+impl<F, Out> Command<Out> for F
 where
-    F: FnOnce(&mut World) + Send + 'static,
+    F: FnOnce(&mut World) -> Out + Send + 'static,
 {
-    fn apply(self, world: &mut World) {
-        self(world);
+    fn apply(self, world: &mut World) -> Out {
+        self(world)
     }
 }
 
@@ -1897,8 +1973,18 @@ impl<F> EntityCommand<World> for F
 where
     F: FnOnce(EntityWorldMut) + Send + 'static,
 {
-    fn apply(self, id: Entity, world: &mut World) {
-        self(world.entity_mut(id));
+    fn apply(self, id: Entity, world: &mut World) -> Result {
+        self(world.get_entity_mut(id)?);
+        Ok(())
+    }
+}
+
+impl<F> EntityCommand<(World, Result)> for F
+where
+    F: FnOnce(EntityWorldMut) -> Result + Send + 'static,
+{
+    fn apply(self, id: Entity, world: &mut World) -> Result {
+        self(world.get_entity_mut(id)?)
     }
 }
 
@@ -1906,8 +1992,36 @@ impl<F> EntityCommand for F
 where
     F: FnOnce(Entity, &mut World) + Send + 'static,
 {
-    fn apply(self, id: Entity, world: &mut World) {
+    fn apply(self, id: Entity, world: &mut World) -> Result {
         self(id, world);
+        Ok(())
+    }
+}
+
+impl<F> EntityCommand<Result> for F
+where
+    F: FnOnce(Entity, &mut World) -> Result + Send + 'static,
+{
+    fn apply(self, id: Entity, world: &mut World) -> Result {
+        self(id, world)
+    }
+}
+
+impl<F> EntityCommand<(World, Never)> for F
+where
+    F: FnOnce(EntityWorldMut) -> Never + Send + 'static,
+{
+    fn apply(self, id: Entity, world: &mut World) -> Result {
+        match self(world.get_entity_mut(id)?) {}
+    }
+}
+
+impl<F> EntityCommand<Never> for F
+where
+    F: FnOnce(Entity, &mut World) -> Never + Send + 'static,
+{
+    fn apply(self, id: Entity, world: &mut World) -> Result {
+        match self(id, world) {}
     }
 }
 
@@ -2048,40 +2162,30 @@ where
 }
 
 /// A [`Command`] that despawns a specific entity.
-/// This will emit a warning if the entity does not exist.
-///
-/// # Note
-///
-/// This won't clean up external references to the entity (such as parent-child relationships
-/// if you're using `bevy_hierarchy`), which may leave the world in an invalid state.
-#[track_caller]
-fn despawn() -> impl EntityCommand {
-    let caller = Location::caller();
-    move |entity: Entity, world: &mut World| {
-        world.despawn_with_caller(entity, caller, true);
-    }
-}
-
-/// A [`Command`] that despawns a specific entity.
-/// This will not emit a warning if the entity does not exist.
+/// This will return an error if the entity does not exist.
 ///
 /// # Note
 ///
 /// This won't clean up external references to the entity (such as parent-child relationships
 /// if you're using `bevy_hierarchy`), which may leave the world in an invalid state.
 #[track_caller]
-fn try_despawn() -> impl EntityCommand {
+fn despawn() -> impl EntityCommand<Result> {
     let caller = Location::caller();
-    move |entity: Entity, world: &mut World| {
-        world.despawn_with_caller(entity, caller, false);
+    move |entity: Entity, world: &mut World| -> Result {
+        if world.despawn_with_caller(entity, caller, false) {
+            Ok(())
+        } else {
+            Err(format!("error[B0003]: {caller}: Could not despawn entity {entity:?} because it doesn't exist in this World. See: https://bevyengine.org/learn/errors/b0003").into())
+        }
     }
 }
 
 /// An [`EntityCommand`] that adds the components in a [`Bundle`] to an entity.
+/// This will return an error if the entity does not exist.
 #[track_caller]
-fn insert<T: Bundle>(bundle: T, mode: InsertMode) -> impl EntityCommand {
+fn insert<T: Bundle>(bundle: T, mode: InsertMode) -> impl EntityCommand<Result> {
     let caller = Location::caller();
-    move |entity: Entity, world: &mut World| {
+    move |entity: Entity, world: &mut World| -> Result {
         if let Ok(mut entity) = world.get_entity_mut(entity) {
             entity.insert_with_caller(
                 bundle,
@@ -2089,17 +2193,19 @@ fn insert<T: Bundle>(bundle: T, mode: InsertMode) -> impl EntityCommand {
                 #[cfg(feature = "track_change_detection")]
                 caller,
             );
+            Ok(())
         } else {
-            panic!("error[B0003]: {caller}: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World. See: https://bevyengine.org/learn/errors/b0003", core::any::type_name::<T>(), entity);
+            Err(format!("error[B0003]: {caller}: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World. See: https://bevyengine.org/learn/errors/b0003", core::any::type_name::<T>(), entity).into())
         }
     }
 }
 
 /// An [`EntityCommand`] that adds the component using its `FromWorld` implementation.
+/// This will return an error if the entity does not exist.
 #[track_caller]
-fn insert_from_world<T: Component + FromWorld>(mode: InsertMode) -> impl EntityCommand {
+fn insert_from_world<T: Component + FromWorld>(mode: InsertMode) -> impl EntityCommand<Result> {
     let caller = Location::caller();
-    move |entity: Entity, world: &mut World| {
+    move |entity: Entity, world: &mut World| -> Result {
         let value = T::from_world(world);
         if let Ok(mut entity) = world.get_entity_mut(entity) {
             entity.insert_with_caller(
@@ -2108,8 +2214,9 @@ fn insert_from_world<T: Component + FromWorld>(mode: InsertMode) -> impl EntityC
                 #[cfg(feature = "track_change_detection")]
                 caller,
             );
+            Ok(())
         } else {
-            panic!("error[B0003]: {caller}: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World. See: https://bevyengine.org/learn/errors/b0003", core::any::type_name::<T>(), entity);
+            Err(format!("error[B0003]: {caller}: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World. See: https://bevyengine.org/learn/errors/b0003", core::any::type_name::<T>(), entity).into())
         }
     }
 }
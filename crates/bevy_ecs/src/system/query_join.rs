@@ -0,0 +1,254 @@
+use crate::{
+    self as bevy_ecs,
+    component::Component,
+    entity::Entity,
+    query::{QueryData, QueryEntityError, QueryFilter, QueryItem, ROQueryItem},
+    relationship::Relationship,
+    system::{Query, SystemParam},
+};
+
+/// A [`Component`] that stores the [`Entity`] a [`JoinQuery`] follows.
+///
+/// This is implemented for all [`Relationship`] components, and can be implemented for any other
+/// component that references an entity.
+pub trait JoinKey: Component {
+    /// Returns the entity this component references.
+    fn join_target(&self) -> Entity;
+}
+
+impl<R: Relationship> JoinKey for R {
+    fn join_target(&self) -> Entity {
+        self.get()
+    }
+}
+
+/// A [`SystemParam`] that follows the entity stored in the [`JoinKey`] component `K` of each source entity,
+/// and fetches `D` on the source together with `T` on the target.
+///
+/// Only the sources matching `F` whose target matches `T` and `TF` are returned, like an inner join.
+///
+/// This is made of two [`Query`]s: `Query<(&K, D), F>` for the sources and `Query<T, TF>` for the targets.
+/// Their access is registered like any other query, so a system that fetches the same component mutably on both
+/// sides needs disjoint filters, like `With<Weapon>` and `Without<Weapon>`.
+///
+/// Several sources can reference the same target, so the target data can be mutated with
+/// [`for_each_mut`](Self::for_each_mut) and [`get_mut`](Self::get_mut), which never hand out two items for
+/// the same target at once, but parallel iteration only gives read-only access to targets.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::JoinQuery;
+/// #[derive(Component)]
+/// #[relationship(relationship_target = Armory)]
+/// struct Owner(Entity);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = Owner)]
+/// struct Armory(Vec<Entity>);
+///
+/// #[derive(Component)]
+/// struct Weapon { damage: f32 }
+///
+/// #[derive(Component)]
+/// struct Strength(f32);
+///
+/// fn scale_damage(mut weapons: JoinQuery<Owner, &mut Weapon, &Strength>) {
+///     weapons.par_for_each_mut(|mut weapon, strength| {
+///         weapon.damage *= strength.0;
+///     });
+/// }
+/// # bevy_ecs::system::assert_is_system(scale_damage);
+/// ```
+#[derive(SystemParam)]
+pub struct JoinQuery<'w, 's, K, D, T, F = (), TF = ()>
+where
+    K: JoinKey,
+    D: QueryData + 'static,
+    T: QueryData + 'static,
+    F: QueryFilter + 'static,
+    TF: QueryFilter + 'static,
+{
+    sources: Query<'w, 's, (&'static K, D), F>,
+    targets: Query<'w, 's, T, TF>,
+}
+
+impl<'w, 's, K, D, T, F, TF> JoinQuery<'w, 's, K, D, T, F, TF>
+where
+    K: JoinKey,
+    D: QueryData + 'static,
+    T: QueryData + 'static,
+    F: QueryFilter + 'static,
+    TF: QueryFilter + 'static,
+{
+    /// Returns an iterator over the read-only items of each source and its target.
+    pub fn iter(&self) -> impl Iterator<Item = (ROQueryItem<'_, D>, ROQueryItem<'_, T>)> + '_ {
+        self.sources.iter().filter_map(|(key, source)| {
+            let target = self.targets.get(key.join_target()).ok()?;
+            Some((source, target))
+        })
+    }
+
+    /// Calls `f` with the items of each source and its target.
+    ///
+    /// Unlike an iterator, this can give mutable access to the targets, since several sources can share a target.
+    pub fn for_each_mut(&mut self, mut f: impl FnMut(QueryItem<'_, D>, QueryItem<'_, T>)) {
+        for (key, source) in self.sources.iter_mut() {
+            if let Ok(target) = self.targets.get_mut(key.join_target()) {
+                f(source, target);
+            }
+        }
+    }
+
+    /// Calls `f` in parallel with the read-only items of each source and its target.
+    pub fn par_for_each(&self, f: impl Fn(ROQueryItem<'_, D>, ROQueryItem<'_, T>) + Send + Sync) {
+        let f = &f;
+        let targets = &self.targets;
+        self.sources.par_iter().for_each(move |(key, source)| {
+            if let Ok(target) = targets.get(key.join_target()) {
+                f(source, target);
+            }
+        });
+    }
+
+    /// Calls `f` in parallel with the items of each source and the read-only items of its target.
+    pub fn par_for_each_mut(
+        &mut self,
+        f: impl Fn(QueryItem<'_, D>, ROQueryItem<'_, T>) + Send + Sync,
+    ) {
+        let f = &f;
+        let targets = &self.targets;
+        self.sources.par_iter_mut().for_each(move |(key, source)| {
+            if let Ok(target) = targets.get(key.join_target()) {
+                f(source, target);
+            }
+        });
+    }
+
+    /// Returns the read-only items of the `source` entity and its target.
+    pub fn get(
+        &self,
+        source: Entity,
+    ) -> Result<(ROQueryItem<'_, D>, ROQueryItem<'_, T>), QueryEntityError> {
+        let (key, source) = self.sources.get(source)?;
+        let target = self.targets.get(key.join_target())?;
+        Ok((source, target))
+    }
+
+    /// Returns the items of the `source` entity and its target.
+    pub fn get_mut(
+        &mut self,
+        source: Entity,
+    ) -> Result<(QueryItem<'_, D>, QueryItem<'_, T>), QueryEntityError> {
+        let (key, source) = self.sources.get_mut(source)?;
+        let target = self.targets.get_mut(key.join_target())?;
+        Ok((source, target))
+    }
+
+    /// Returns the query over the sources and their [`JoinKey`].
+    pub fn sources(&self) -> &Query<'w, 's, (&'static K, D), F> {
+        &self.sources
+    }
+
+    /// Returns the query over the targets.
+    pub fn targets(&self) -> &Query<'w, 's, T, TF> {
+        &self.targets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        prelude::*,
+        system::{JoinKey, JoinQuery, RunSystemOnce},
+    };
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Armory)]
+    struct Owner(Entity);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = Owner)]
+    struct Armory(Vec<Entity>);
+
+    #[derive(Component)]
+    struct Target(Entity);
+
+    impl JoinKey for Target {
+        fn join_target(&self) -> Entity {
+            self.0
+        }
+    }
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Value(u32);
+
+    #[test]
+    fn join_query() {
+        let mut world = World::new();
+        let owner_a = world.spawn(Value(10)).id();
+        let owner_b = world.spawn(Value(20)).id();
+        let no_value = world.spawn_empty().id();
+        let a1 = world.spawn((Value(1), Owner(owner_a))).id();
+        let a2 = world.spawn((Value(2), Owner(owner_a))).id();
+        let b = world.spawn((Value(3), Owner(owner_b))).id();
+        world.spawn((Value(4), Owner(no_value)));
+
+        world
+            .run_system_once(
+                |mut join: JoinQuery<Owner, &Value, &mut Value, (), Without<Owner>>| {
+                    join.for_each_mut(|source, mut target| target.0 += source.0);
+                },
+            )
+            .unwrap();
+        assert_eq!(world.get::<Value>(owner_a), Some(&Value(13)));
+        assert_eq!(world.get::<Value>(owner_b), Some(&Value(23)));
+
+        let sums = world
+            .run_system_once(
+                |join: JoinQuery<Owner, (Entity, &Value), &Value, (), Without<Owner>>| {
+                    let mut sums: Vec<_> = join
+                        .iter()
+                        .map(|((entity, source), target)| (entity, source.0 + target.0))
+                        .collect();
+                    sums.sort();
+                    sums
+                },
+            )
+            .unwrap();
+        assert_eq!(sums, [(a1, 14), (a2, 15), (b, 26)]);
+    }
+
+    #[test]
+    fn join_query_parallel() {
+        let mut world = World::new();
+        let target = world.spawn(Value(5)).id();
+        let sources: Vec<Entity> = (0..100)
+            .map(|i| world.spawn((Value(i), Target(target))).id())
+            .collect();
+
+        world
+            .run_system_once(
+                |mut join: JoinQuery<Target, &mut Value, &Value, (), Without<Target>>| {
+                    join.par_for_each_mut(|mut source, target| source.0 += target.0);
+                },
+            )
+            .unwrap();
+        for (i, source) in sources.into_iter().enumerate() {
+            assert_eq!(world.get::<Value>(source), Some(&Value(i as u32 + 5)));
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "accesses component(s)Value in a way that conflicts with a previous system parameter"
+    )]
+    fn join_query_conflicting_access() {
+        let mut world = World::new();
+        world
+            .run_system_once(|_: JoinQuery<Owner, &mut Value, &Value>| {})
+            .unwrap();
+    }
+}
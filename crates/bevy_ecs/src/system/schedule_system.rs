@@ -0,0 +1,129 @@
+use alloc::borrow::Cow;
+use core::any::TypeId;
+
+use crate::{
+    archetype::ArchetypeComponentId,
+    component::{ComponentId, Tick},
+    error::Result,
+    query::Access,
+    schedule::InternedSystemSet,
+    system::{input::SystemIn, BoxedSystem, System},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
+};
+
+/// A type-erased system stored in a [`Schedule`](crate::schedule::Schedule).
+///
+/// Systems that return `()` are wrapped in an [`InfallibleSystemWrapper`] so that all systems
+/// in a schedule return a [`Result`].
+pub type ScheduleSystem = BoxedSystem<(), Result>;
+
+/// A [`System`] that wraps a system returning `()`, and always returns `Ok(())`.
+///
+/// The wrapper is transparent: it has the same name, [`TypeId`] and default system sets as the wrapped system.
+pub struct InfallibleSystemWrapper(BoxedSystem<(), ()>);
+
+impl InfallibleSystemWrapper {
+    /// Wraps `system`.
+    pub fn new(system: BoxedSystem<(), ()>) -> Self {
+        Self(system)
+    }
+}
+
+impl System for InfallibleSystemWrapper {
+    type In = ();
+    type Out = Result;
+
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        self.0.name()
+    }
+
+    #[inline]
+    fn type_id(&self) -> TypeId {
+        self.0.type_id()
+    }
+
+    #[inline]
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.0.component_access()
+    }
+
+    #[inline]
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.0.archetype_component_access()
+    }
+
+    #[inline]
+    fn is_send(&self) -> bool {
+        self.0.is_send()
+    }
+
+    #[inline]
+    fn is_exclusive(&self) -> bool {
+        self.0.is_exclusive()
+    }
+
+    #[inline]
+    fn has_deferred(&self) -> bool {
+        self.0.has_deferred()
+    }
+
+    #[inline]
+    unsafe fn run_unsafe(&mut self, input: SystemIn<'_, Self>, world: UnsafeWorldCell) -> Result {
+        // SAFETY: `self.0.run_unsafe` has the same invariants as `self.run_unsafe`.
+        unsafe { self.0.run_unsafe(input, world) };
+        Ok(())
+    }
+
+    #[inline]
+    fn run(&mut self, input: SystemIn<'_, Self>, world: &mut World) -> Result {
+        self.0.run(input, world);
+        Ok(())
+    }
+
+    #[inline]
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.0.apply_deferred(world);
+    }
+
+    #[inline]
+    fn queue_deferred(&mut self, world: DeferredWorld) {
+        self.0.queue_deferred(world);
+    }
+
+    #[inline]
+    unsafe fn validate_param_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        // SAFETY: Delegate to the wrapped system, which has the same invariants.
+        unsafe { self.0.validate_param_unsafe(world) }
+    }
+
+    #[inline]
+    fn initialize(&mut self, world: &mut World) {
+        self.0.initialize(world);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        self.0.update_archetype_component_access(world);
+    }
+
+    #[inline]
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.0.check_change_tick(change_tick);
+    }
+
+    #[inline]
+    fn default_system_sets(&self) -> Vec<InternedSystemSet> {
+        self.0.default_system_sets()
+    }
+
+    #[inline]
+    fn get_last_run(&self) -> Tick {
+        self.0.get_last_run()
+    }
+
+    #[inline]
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.0.set_last_run(last_run);
+    }
+}
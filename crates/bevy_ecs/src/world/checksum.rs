@@ -0,0 +1,184 @@
+//! Hashing the state of a [`World`], to check that two runs of the same app produce the same results.
+//!
+//! A [`ChecksumConfig`] selects which components and resources are hashed by [`World::checksum`].
+//! The checksum depends only on the alive entities and the values of the selected components and resources,
+//! not on the order in which archetypes and tables were created or in which entities are stored in them.
+//! It is computed with fixed seeds, so checksums of the same build on the same platform can be compared
+//! between runs.
+//!
+//! Together with [`ScheduleBuildSettings::deterministic_order`](crate::schedule::ScheduleBuildSettings::deterministic_order),
+//! this can be used to test that a schedule behaves deterministically:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::{schedule::ScheduleBuildSettings, world::checksum::ChecksumConfig};
+//!
+//! #[derive(Component, Hash)]
+//! struct Position(i32);
+//!
+//! fn spawn(mut commands: Commands) {
+//!     commands.spawn(Position(0));
+//! }
+//!
+//! fn step(mut positions: Query<&mut Position>) {
+//!     for mut position in &mut positions {
+//!         position.0 += 1;
+//!     }
+//! }
+//!
+//! fn run() -> u64 {
+//!     let mut world = World::new();
+//!     let mut schedule = Schedule::default();
+//!     schedule.set_build_settings(ScheduleBuildSettings {
+//!         deterministic_order: true,
+//!         ..Default::default()
+//!     });
+//!     schedule.add_systems((spawn, step));
+//!     for _ in 0..10 {
+//!         schedule.run(&mut world);
+//!     }
+//!
+//!     let mut config = ChecksumConfig::new();
+//!     config.component::<Position>();
+//!     world.checksum(&config)
+//! }
+//!
+//! assert_eq!(run(), run());
+//! ```
+
+use crate::{
+    archetype::Archetype, component::Component, entity::Entity, system::Resource, world::World,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bevy_utils::FixedState;
+use core::hash::{BuildHasher, Hash, Hasher};
+
+type HashFn = Box<dyn Fn(&World, &mut dyn Hasher) + Send + Sync>;
+
+/// Describes which components and resources are hashed by [`World::checksum`].
+///
+/// See the [module docs](crate::world::checksum) for more info.
+#[derive(Default)]
+pub struct ChecksumConfig {
+    hashers: Vec<HashFn>,
+}
+
+impl ChecksumConfig {
+    /// Creates an empty [`ChecksumConfig`], which only hashes the alive entities.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hashes the component `C` of every entity, in the order of the entities.
+    ///
+    /// Disabled entities are included, see [`entity_disabling`](crate::entity_disabling).
+    pub fn component<C: Component + Hash>(&mut self) -> &mut Self {
+        self.hashers
+            .push(Box::new(|world: &World, mut hasher: &mut dyn Hasher| {
+                let id = world.component_id::<C>();
+                // Walk the archetypes rather than querying, so that default query filters
+                // don't hide disabled entities.
+                let mut values: Vec<(Entity, &C)> = world
+                    .archetypes()
+                    .iter()
+                    .filter(|archetype| id.is_some_and(|id| archetype.contains(id)))
+                    .flat_map(Archetype::entities)
+                    .filter_map(|archetype_entity| {
+                        let entity = archetype_entity.id();
+                        world.get::<C>(entity).map(|value| (entity, value))
+                    })
+                    .collect();
+                values.sort_unstable_by_key(|(entity, _)| *entity);
+                values.hash(&mut hasher);
+            }));
+        self
+    }
+
+    /// Hashes the resource `R`, or its absence.
+    pub fn resource<R: Resource + Hash>(&mut self) -> &mut Self {
+        self.hashers
+            .push(Box::new(|world: &World, mut hasher: &mut dyn Hasher| {
+                world.get_resource::<R>().hash(&mut hasher);
+            }));
+        self
+    }
+}
+
+impl World {
+    /// Returns a hash of the alive entities of this world, and of the components and resources selected by `config`.
+    ///
+    /// Entities that are reserved but not flushed yet are not included.
+    ///
+    /// See the [`checksum`](crate::world::checksum) module docs for more info.
+    pub fn checksum(&self, config: &ChecksumConfig) -> u64 {
+        let mut hasher = FixedState.build_hasher();
+        let mut entities: Vec<Entity> = self.iter_entities().map(|entity| entity.id()).collect();
+        entities.sort_unstable();
+        entities.hash(&mut hasher);
+        for hash in &config.hashers {
+            hash(self, &mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+
+    #[derive(Component, Hash)]
+    struct A(u32);
+
+    #[derive(Component, Hash)]
+    #[component(storage = "SparseSet")]
+    struct B(u32);
+
+    #[derive(Resource, Hash)]
+    struct R(u32);
+
+    #[test]
+    fn checksum_ignores_storage_layout() {
+        let mut config = ChecksumConfig::new();
+        config.component::<A>().component::<B>().resource::<R>();
+
+        let mut first = World::new();
+        first.spawn((A(1), B(2)));
+        first.spawn(A(3));
+        first.insert_resource(R(4));
+
+        // The same entities, with their archetypes created in another order.
+        let mut second = World::new();
+        let entity = second.spawn_empty().id();
+        second.spawn(A(3));
+        second.entity_mut(entity).insert(B(2)).insert(A(1));
+        second.insert_resource(R(4));
+        assert_eq!(first.checksum(&config), second.checksum(&config));
+
+        second.entity_mut(entity).insert(A(5));
+        assert_ne!(first.checksum(&config), second.checksum(&config));
+
+        // Components that aren't part of the config are ignored, but entities are not.
+        assert_eq!(
+            first.checksum(&ChecksumConfig::new()),
+            second.checksum(&ChecksumConfig::new())
+        );
+        second.spawn_empty();
+        assert_ne!(
+            first.checksum(&ChecksumConfig::new()),
+            second.checksum(&ChecksumConfig::new())
+        );
+    }
+
+    #[test]
+    fn checksum_includes_disabled_entities() {
+        let mut config = ChecksumConfig::new();
+        config.component::<A>();
+
+        let mut world = World::new();
+        let entity = world.spawn((A(1), crate::entity_disabling::Disabled)).id();
+        let before = world.checksum(&config);
+        world.entity_mut(entity).insert(A(2));
+        assert_ne!(before, world.checksum(&config));
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     component::ComponentId,
     entity::Entity,
     event::{Event, EventId, Events, SendBatchIds},
-    observer::{Observers, TriggerTargets},
+    observer::{Observers, Propagation, TriggerTargets},
     prelude::{Component, QueryState},
     query::{QueryData, QueryFilter},
     system::{Commands, Query, Resource},
@@ -492,6 +492,28 @@ impl<'w> DeferredWorld<'w> {
         }
     }
 
+    /// Triggers all `on_despawn` hooks for [`ComponentId`] in target.
+    ///
+    /// # Safety
+    /// Caller must ensure [`ComponentId`] in target exist in self.
+    #[inline]
+    pub(crate) unsafe fn trigger_on_despawn(
+        &mut self,
+        archetype: &Archetype,
+        entity: Entity,
+        targets: impl Iterator<Item = ComponentId>,
+    ) {
+        if archetype.has_despawn_hook() {
+            for component_id in targets {
+                // SAFETY: Caller ensures that these components exist
+                let hooks = unsafe { self.components().get_info_unchecked(component_id) }.hooks();
+                if let Some(hook) = hooks.on_despawn {
+                    hook(DeferredWorld { world: self.world }, entity, component_id);
+                }
+            }
+        }
+    }
+
     /// Triggers all event observers for [`ComponentId`] in target.
     ///
     /// # Safety
@@ -509,7 +531,7 @@ impl<'w> DeferredWorld<'w> {
             entity,
             components,
             &mut (),
-            &mut false,
+            &mut Propagation::new(false, entity),
         );
     }
 
@@ -524,10 +546,11 @@ impl<'w> DeferredWorld<'w> {
         mut entity: Entity,
         components: &[ComponentId],
         data: &mut E,
-        mut propagate: bool,
+        propagate: bool,
     ) where
         T: Traversal,
     {
+        let mut propagation = Propagation::new(propagate, entity);
         loop {
             Observers::invoke::<_>(
                 self.reborrow(),
@@ -535,17 +558,17 @@ impl<'w> DeferredWorld<'w> {
                 entity,
                 components.iter().copied(),
                 data,
-                &mut propagate,
+                &mut propagation,
             );
-            if !propagate {
+            if !propagation.is_enabled() {
                 break;
             }
-            if let Some(traverse_to) = self
-                .get_entity(entity)
-                .ok()
-                .and_then(|entity| entity.get_components::<T>())
-                .and_then(T::traverse)
-            {
+            if let Some(traverse_to) = propagation.advance().or_else(|| {
+                self.get_entity(entity)
+                    .ok()
+                    .and_then(|entity| entity.get_components::<T>())
+                    .and_then(T::traverse)
+            }) {
                 entity = traverse_to;
             } else {
                 break;
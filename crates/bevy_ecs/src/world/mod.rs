@@ -1,5 +1,6 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+pub mod checksum;
 pub(crate) mod command_queue;
 mod component_constants;
 mod deferred_world;
@@ -8,6 +9,8 @@ mod entity_ref;
 pub mod error;
 mod filtered_resource;
 mod identifier;
+#[cfg(feature = "bevy_reflect")]
+pub mod snapshot;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 
@@ -39,12 +42,13 @@ use crate::{
         Tick,
     },
     entity::{AllocAtWithoutReplacement, Entities, Entity, EntityHashSet, EntityLocation},
+    entity_disabling::{DefaultQueryFilters, Disabled},
     event::{Event, EventId, Events, SendBatchIds},
-    observer::Observers,
+    observer::{Observers, ResourceLifecycle},
     query::{DebugCheckedUnwrap, QueryData, QueryEntityError, QueryFilter, QueryState},
     removal_detection::RemovedComponentEvents,
     schedule::{Schedule, ScheduleLabel, Schedules},
-    storage::{ResourceData, Storages},
+    storage::{CompactionPolicy, ResourceData, Storages},
     system::{Commands, Resource},
     world::{
         command_queue::RawCommandQueue,
@@ -93,13 +97,40 @@ use unsafe_world_cell::{UnsafeEntityCell, UnsafeWorldCell};
 ///     commands.queue(AddToCounter(42));
 /// }
 /// ```
-pub trait Command: Send + 'static {
+///
+/// Commands can fail by returning a [`Result`](crate::error::Result). The error is then passed to an
+/// [`ErrorHandler`](crate::error::ErrorHandler), see [the `error` module](crate::error) for more information.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::Command;
+/// use bevy_ecs::error::{warn, Result};
+///
+/// struct DespawnAll(Vec<Entity>);
+///
+/// impl Command<Result> for DespawnAll {
+///     fn apply(self, world: &mut World) -> Result {
+///         for entity in self.0 {
+///             world.get_entity_mut(entity)?.despawn();
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// fn some_system(mut commands: Commands) {
+///     // Uses the default error handler of the world.
+///     commands.queue(DespawnAll(vec![]));
+///     // Only logs a warning if an entity doesn't exist.
+///     commands.queue_handled(DespawnAll(vec![]), warn);
+/// }
+/// ```
+pub trait Command<Out = ()>: Send + 'static {
     /// Applies this command, causing it to mutate the provided `world`.
     ///
     /// This method is used to define what a command "does" when it is ultimately applied.
     /// Because this method takes `self`, you can store data or settings on the type that implements this trait.
     /// This data is set by the system or other source of the command, and then ultimately read in this method.
-    fn apply(self, world: &mut World);
+    fn apply(self, world: &mut World) -> Out;
 }
 
 /// Stores and exposes operations on [entities](Entity), [components](Component), resources,
@@ -135,6 +166,10 @@ pub struct World {
     pub(crate) last_check_tick: Tick,
     pub(crate) last_trigger_id: u32,
     pub(crate) command_queue: RawCommandQueue,
+    pub(crate) default_query_filters: DefaultQueryFilters,
+    pub(crate) deterministic_query_order: bool,
+    /// Set once resource hooks or observers of resource lifecycle events have been registered.
+    pub(crate) resource_lifecycle_triggers: bool,
 }
 
 impl Default for World {
@@ -155,6 +190,9 @@ impl Default for World {
             last_check_tick: Tick::new(0),
             last_trigger_id: 0,
             command_queue: RawCommandQueue::new(),
+            default_query_filters: DefaultQueryFilters::empty(),
+            deterministic_query_order: false,
+            resource_lifecycle_triggers: false,
         };
         world.bootstrap();
         world
@@ -183,6 +221,10 @@ impl World {
         assert_eq!(ON_INSERT, self.register_component::<OnInsert>());
         assert_eq!(ON_REPLACE, self.register_component::<OnReplace>());
         assert_eq!(ON_REMOVE, self.register_component::<OnRemove>());
+
+        let disabled = self.register_component::<Disabled>();
+        self.default_query_filters
+            .register_disabling_component(disabled);
     }
     /// Creates a new empty [`World`].
     ///
@@ -242,6 +284,43 @@ impl World {
         &self.components
     }
 
+    /// Retrieves this world's [`DefaultQueryFilters`], which hide entities with disabling components
+    /// (such as [`Disabled`]) from queries that don't explicitly ask for them.
+    #[inline]
+    pub fn default_query_filters(&self) -> &DefaultQueryFilters {
+        &self.default_query_filters
+    }
+
+    /// Retrieves a mutable reference to this world's [`DefaultQueryFilters`].
+    ///
+    /// Changes only apply to queries created afterwards, so this should be configured
+    /// before any systems are initialized or queries are built.
+    #[inline]
+    pub fn default_query_filters_mut(&mut self) -> &mut DefaultQueryFilters {
+        &mut self.default_query_filters
+    }
+
+    /// Returns `true` if queries iterate the tables and archetypes they match in a deterministic order.
+    ///
+    /// See [`World::set_deterministic_query_order`].
+    #[inline]
+    pub fn deterministic_query_order(&self) -> bool {
+        self.deterministic_query_order
+    }
+
+    /// Makes queries iterate the tables and archetypes they match in the order of their sorted
+    /// [`ComponentId`]s, instead of the order in which they were created.
+    ///
+    /// The iteration order then doesn't depend on the history of archetype creation, as long as components
+    /// are registered in the same order. Entities are still iterated in the order in which they are
+    /// stored in each table or archetype, which depends on the order in which they were moved there.
+    ///
+    /// Schedules with [`ScheduleBuildSettings::deterministic_order`](crate::schedule::ScheduleBuildSettings::deterministic_order)
+    /// enable this when they are initialized.
+    pub fn set_deterministic_query_order(&mut self, enabled: bool) {
+        self.deterministic_query_order = enabled;
+    }
+
     /// Retrieves this world's [`Storages`] collection.
     #[inline]
     pub fn storages(&self) -> &Storages {
@@ -1930,9 +2009,14 @@ impl World {
     #[inline]
     pub fn remove_resource<R: Resource>(&mut self) -> Option<R> {
         let component_id = self.components.get_resource_id(TypeId::of::<R>())?;
+        let triggered = self.trigger_resource_removal(component_id, false);
         let (ptr, _, _) = self.storages.resources.get_mut(component_id)?.remove()?;
         // SAFETY: `component_id` was gotten via looking up the `R` type
-        unsafe { Some(ptr.read::<R>()) }
+        let value = unsafe { ptr.read::<R>() };
+        if triggered {
+            self.flush();
+        }
+        Some(value)
     }
 
     /// Removes a `!Send` resource from the world and returns it, if present.
@@ -1949,13 +2033,18 @@ impl World {
     #[inline]
     pub fn remove_non_send_resource<R: 'static>(&mut self) -> Option<R> {
         let component_id = self.components.get_resource_id(TypeId::of::<R>())?;
+        let triggered = self.trigger_resource_removal(component_id, true);
         let (ptr, _, _) = self
             .storages
             .non_send_resources
             .get_mut(component_id)?
             .remove()?;
         // SAFETY: `component_id` was gotten via looking up the `R` type
-        unsafe { Some(ptr.read::<R>()) }
+        let value = unsafe { ptr.read::<R>() };
+        if triggered {
+            self.flush();
+        }
+        Some(value)
     }
 
     /// Returns `true` if a resource of type `R` exists. Otherwise returns `false`.
@@ -2205,14 +2294,13 @@ impl World {
         let last_change_tick = self.last_change_tick();
 
         let component_id = self.components.register_resource::<R>();
-        let data = self.initialize_resource_internal(component_id);
-        if !data.is_present() {
+        if !self.initialize_resource_internal(component_id).is_present() {
             OwningPtr::make(func(), |ptr| {
                 // SAFETY: component_id was just initialized and corresponds to resource of type R.
                 unsafe {
-                    data.insert(
+                    self.insert_resource_by_id(
+                        component_id,
                         ptr,
-                        change_tick,
                         #[cfg(feature = "track_change_detection")]
                         caller,
                     );
@@ -2220,11 +2308,15 @@ impl World {
             });
         }
 
-        // SAFETY: The resource must be present, as we would have inserted it if it was empty.
-        let data = unsafe {
-            data.get_mut(last_change_tick, change_tick)
-                .debug_checked_unwrap()
-        };
+        let data = self
+            .initialize_resource_internal(component_id)
+            .get_mut(last_change_tick, change_tick)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Resource {} was removed by the hooks or observers of its insertion",
+                    core::any::type_name::<R>()
+                )
+            });
         // SAFETY: The underlying type of the resource is `R`.
         unsafe { data.with_type::<R>() }
     }
@@ -2296,11 +2388,15 @@ impl World {
                 .get_mut(component_id)
                 .debug_checked_unwrap()
         };
-        // SAFETY: The resource must be present, as we would have inserted it if it was empty.
-        let data = unsafe {
-            data.get_mut(last_change_tick, change_tick)
-                .debug_checked_unwrap()
-        };
+        // The resource was inserted if it was empty, but its hooks or observers may have removed it.
+        let data = data
+            .get_mut(last_change_tick, change_tick)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Resource {} was removed by the hooks or observers of its insertion",
+                    core::any::type_name::<R>()
+                )
+            });
         // SAFETY: The underlying type of the resource is `R`.
         unsafe { data.with_type::<R>() }
     }
@@ -2983,6 +3079,13 @@ impl World {
     ) {
         let change_tick = self.change_tick();
 
+        let present = self
+            .storages
+            .resources
+            .get(component_id)
+            .is_some_and(ResourceData::is_present);
+        let mut triggered =
+            present && self.trigger_resource_lifecycle(component_id, ResourceLifecycle::Replace);
         let resource = self.initialize_resource_internal(component_id);
         // SAFETY: `value` is valid for `component_id`, ensured by caller
         unsafe {
@@ -2993,6 +3096,13 @@ impl World {
                 caller,
             );
         }
+        if !present {
+            triggered |= self.trigger_resource_lifecycle(component_id, ResourceLifecycle::Add);
+        }
+        triggered |= self.trigger_resource_lifecycle(component_id, ResourceLifecycle::Insert);
+        if triggered {
+            self.flush();
+        }
     }
 
     /// Inserts a new `!Send` resource with the given `value`. Will replace the value if it already
@@ -3017,6 +3127,13 @@ impl World {
     ) {
         let change_tick = self.change_tick();
 
+        let present = self
+            .storages
+            .non_send_resources
+            .get(component_id)
+            .is_some_and(ResourceData::is_present);
+        let mut triggered =
+            present && self.trigger_resource_lifecycle(component_id, ResourceLifecycle::Replace);
         let resource = self.initialize_non_send_internal(component_id);
         // SAFETY: `value` is valid for `component_id`, ensured by caller
         unsafe {
@@ -3027,6 +3144,13 @@ impl World {
                 caller,
             );
         }
+        if !present {
+            triggered |= self.trigger_resource_lifecycle(component_id, ResourceLifecycle::Add);
+        }
+        triggered |= self.trigger_resource_lifecycle(component_id, ResourceLifecycle::Insert);
+        if triggered {
+            self.flush();
+        }
     }
 
     /// # Panics
@@ -3314,6 +3438,27 @@ impl World {
         self.entities.clear();
     }
 
+    /// Shrinks the allocations of all tables, sparse sets and archetypes to fit their entities.
+    ///
+    /// Empty tables and archetypes are shrunk to a capacity of zero, but are not removed, see the limitations
+    /// of [`CompactionPolicy`].
+    ///
+    /// Storages never shrink on their own, so this can be used after despawning many entities, like when
+    /// unloading a level. See [`CompactionPolicy`] to only shrink the storages with a lot of unused memory.
+    pub fn compact_storage(&mut self) {
+        self.compact_storage_with(&CompactionPolicy::ALL);
+    }
+
+    /// Shrinks the allocations of the tables, sparse sets and archetypes whose unused capacity matches `policy`.
+    ///
+    /// See [`compact_storage`](crate::storage::compact_storage) to do this from a schedule.
+    pub fn compact_storage_with(&mut self, policy: &CompactionPolicy) {
+        self.flush();
+        self.storages.tables.compact(policy);
+        self.storages.sparse_sets.compact(policy);
+        self.archetypes.compact(policy);
+    }
+
     /// Clears all resources in this [`World`].
     ///
     /// **Note:** Any resource fetch to this [`World`] will fail unless they are re-initialized,
@@ -3658,10 +3803,14 @@ impl World {
     /// **You should prefer to use the typed API [`World::remove_resource`] where possible and only
     /// use this in cases where the actual types are not known at compile time.**
     pub fn remove_resource_by_id(&mut self, component_id: ComponentId) -> Option<()> {
+        let triggered = self.trigger_resource_removal(component_id, false);
         self.storages
             .resources
             .get_mut(component_id)?
             .remove_and_drop();
+        if triggered {
+            self.flush();
+        }
         Some(())
     }
 
@@ -3673,10 +3822,14 @@ impl World {
     /// # Panics
     /// This function will panic if it isn't called from the same thread that the resource was inserted from.
     pub fn remove_non_send_by_id(&mut self, component_id: ComponentId) -> Option<()> {
+        let triggered = self.trigger_resource_removal(component_id, true);
         self.storages
             .non_send_resources
             .get_mut(component_id)?
             .remove_and_drop();
+        if triggered {
+            self.flush();
+        }
         Some(())
     }
 
@@ -3902,6 +4055,7 @@ mod tests {
         component::{ComponentDescriptor, ComponentInfo, StorageType},
         entity::EntityHashSet,
         ptr::OwningPtr,
+        storage::CompactionPolicy,
         system::Resource,
         world::error::EntityFetchError,
     };
@@ -4540,4 +4694,48 @@ mod tests {
                 .map(|_| {})
         );
     }
+
+    #[test]
+    fn compact_storage() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Table(u64);
+
+        #[derive(Component, PartialEq, Debug)]
+        #[component(storage = "SparseSet")]
+        struct Sparse(String);
+
+        let mut world = World::new();
+        let entities: Vec<_> = (0..1000)
+            .map(|i| world.spawn((Table(i), Sparse(i.to_string()))).id())
+            .collect();
+        for &entity in &entities[10..] {
+            world.despawn(entity);
+        }
+        let empty = world.spawn(Table(0)).id();
+        world.entity_mut(empty).remove::<Table>();
+
+        let spare_bytes = world.memory_stats().spare_bytes();
+        world.compact_storage_with(&CompactionPolicy {
+            min_spare_ratio: 0.5,
+            min_spare_capacity: 10_000,
+        });
+        assert_eq!(world.memory_stats().spare_bytes(), spare_bytes);
+
+        world.compact_storage();
+        let stats = world.memory_stats();
+        assert_eq!(stats.spare_bytes(), 0);
+        let table = world.entity(entities[0]).archetype().table_id();
+        assert_eq!(stats.tables[table.as_usize()].entity_capacity, 10);
+        for (i, &entity) in entities[..10].iter().enumerate() {
+            assert_eq!(world.get::<Table>(entity), Some(&Table(i as u64)));
+            assert_eq!(world.get::<Sparse>(entity), Some(&Sparse(i.to_string())));
+        }
+
+        // Storages that were freed can grow again.
+        world.clear_entities();
+        world.compact_storage();
+        let entity = world.spawn((Table(5), Sparse("5".into()))).id();
+        assert_eq!(world.get::<Table>(entity), Some(&Table(5)));
+        assert_eq!(world.get::<Sparse>(entity), Some(&Sparse("5".into())));
+    }
 }
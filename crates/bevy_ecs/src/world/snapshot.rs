@@ -0,0 +1,1276 @@
+//! Capturing and restoring the state of a [`World`], for rollback networking and undo.
+//!
+//! A [`WorldSnapshot`] records the values of a selected set of components and resources, along with the
+//! entity allocation state of the [`World`]. Restoring it brings those components and resources back
+//! exactly as they were, respawns despawned entities with their original IDs, despawns entities spawned
+//! since, and makes the next spawned entities reuse the same IDs as they would have at the time of the snapshot.
+//!
+//! Change ticks are never moved backwards: restored components and resources are marked as changed at the
+//! current change tick, so systems see a restore like any other write.
+//!
+//! Which components and resources are captured, and how they are cloned, is described by a [`SnapshotConfig`].
+//! Captured values are stored as reflected values, so snapshots can be sent over the network or saved
+//! with [`SnapshotSerializer`] when the `serialize` feature is enabled. Types implementing [`Clone`] are
+//! captured without going through reflection, which is fast enough to take a snapshot every frame.
+//! Types that are only known through the type registry can be captured using [`ReflectComponent`]
+//! and [`ReflectResource`].
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::world::snapshot::SnapshotConfig;
+//! use bevy_reflect::Reflect;
+//!
+//! #[derive(Component, Reflect, Clone, PartialEq, Debug)]
+//! struct Position(f32);
+//!
+//! let mut config = SnapshotConfig::new();
+//! config.component::<Position>();
+//!
+//! let mut world = World::new();
+//! let player = world.spawn(Position(0.0)).id();
+//! let snapshot = world.snapshot(&config);
+//!
+//! world.entity_mut(player).insert(Position(10.0));
+//! let projectile = world.spawn(Position(5.0)).id();
+//!
+//! world.restore_snapshot(&config, snapshot).unwrap();
+//! assert_eq!(world.get::<Position>(player), Some(&Position(0.0)));
+//! assert!(world.get_entity(projectile).is_err());
+//! // The entity ID of the projectile is handed out again.
+//! assert_eq!(world.spawn_empty().id(), projectile);
+//! ```
+//!
+//! Components that are not part of the [`SnapshotConfig`] are left untouched on entities that
+//! exist both before and after restoring, and are lost on entities that are despawned or respawned.
+//! Existing components are overwritten in place, while components that need to be inserted or removed
+//! run their hooks and observers as usual.
+//!
+//! Observers and systems registered with [`World::register_system`] are not part of snapshots: their
+//! entities are neither captured nor despawned when restoring, so observers added after taking a
+//! snapshot keep running.
+//!
+//! Restoring consumes the snapshot, so each captured value is cloned exactly once. To restore the same
+//! state again later, take a new snapshot right after restoring it.
+
+use crate::{
+    archetype::{Archetype, ArchetypeEntity},
+    component::{Component, ComponentId, Tick},
+    entity::{Entity, EntityAllocatorState, EntityHashSet},
+    observer::{Observer, ObserverState},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    system::{Resource, SystemIdMarker},
+    world::{AllocAtWithoutReplacement, World},
+};
+use alloc::{boxed::Box, vec::Vec};
+use bevy_reflect::{FromReflect, PartialReflect, Reflect, ReflectFromReflect};
+use bevy_utils::HashSet;
+use core::any::{type_name, TypeId};
+use derive_more::derive::{Display, Error};
+
+#[cfg(feature = "serialize")]
+pub use serialize::*;
+
+/// The captured values of a component, by entity.
+type ComponentData = Vec<(Entity, Box<dyn PartialReflect>)>;
+/// The captured value of a resource, or `None` if it didn't exist.
+type ResourceData = Option<Box<dyn PartialReflect>>;
+
+/// Captures and restores a single component or resource type.
+struct SnapshotFns<T> {
+    type_id: TypeId,
+    capture: Box<dyn Fn(&World) -> T + Send + Sync>,
+    restore: Box<dyn Fn(&mut World, T) -> Result<(), SnapshotError> + Send + Sync>,
+}
+
+/// Describes which components and resources are captured by a [`WorldSnapshot`], and how they are cloned.
+///
+/// See the [module docs](crate::world::snapshot) for more info.
+#[derive(Default)]
+pub struct SnapshotConfig {
+    components: Vec<SnapshotFns<ComponentData>>,
+    resources: Vec<SnapshotFns<ResourceData>>,
+}
+
+impl SnapshotConfig {
+    /// Creates an empty [`SnapshotConfig`], which only captures the entity allocation state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captures the component `C` by cloning it.
+    pub fn component<C: Component + FromReflect + Clone>(&mut self) -> &mut Self {
+        self.component_with::<C>(C::clone)
+    }
+
+    /// Captures the component `C` using a custom `clone` function.
+    ///
+    /// This can be used for components that don't implement [`Clone`], or whose clone should
+    /// share data with the original (for example through an [`Arc`](alloc::sync::Arc)).
+    pub fn component_with<C: Component + FromReflect>(
+        &mut self,
+        clone: impl Fn(&C) -> C + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.add_component(SnapshotFns {
+            type_id: TypeId::of::<C>(),
+            capture: Box::new(move |world| match world.component_id::<C>() {
+                Some(id) => entities_with(world, id)
+                    .filter_map(|entity| {
+                        let value: Box<dyn PartialReflect> =
+                            Box::new(clone(world.get::<C>(entity)?));
+                        Some((entity, value))
+                    })
+                    .collect(),
+                None => Vec::new(),
+            }),
+            restore: Box::new(|world, data| {
+                let captured = data
+                    .into_iter()
+                    .map(|(entity, value)| Ok((entity, take_value::<C>(value)?)))
+                    .collect::<Result<Vec<_>, SnapshotError>>()?;
+                let id = world.register_component::<C>();
+                let expected = captured.iter().map(|(entity, _)| *entity);
+                for entity in entities_not_in(world, id, expected) {
+                    // A hook or observer may have despawned the entity already.
+                    if let Ok(mut entity) = world.get_entity_mut(entity) {
+                        entity.remove::<C>();
+                    }
+                }
+                for (entity, value) in captured {
+                    let mut entity_mut = world
+                        .get_entity_mut(entity)
+                        .map_err(|_| SnapshotError::EntityDespawned(entity))?;
+                    if let Some(mut component) = entity_mut.get_mut::<C>() {
+                        *component = value;
+                    } else {
+                        entity_mut.insert(value);
+                    }
+                }
+                Ok(())
+            }),
+        })
+    }
+
+    /// Captures the resource `R` by cloning it.
+    pub fn resource<R: Resource + FromReflect + Clone>(&mut self) -> &mut Self {
+        self.resource_with::<R>(R::clone)
+    }
+
+    /// Captures the resource `R` using a custom `clone` function.
+    pub fn resource_with<R: Resource + FromReflect>(
+        &mut self,
+        clone: impl Fn(&R) -> R + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.add_resource(SnapshotFns {
+            type_id: TypeId::of::<R>(),
+            capture: Box::new(move |world| {
+                let value: Box<dyn PartialReflect> = Box::new(clone(world.get_resource::<R>()?));
+                Some(value)
+            }),
+            restore: Box::new(|world, data| {
+                let Some(value) = data else {
+                    world.remove_resource::<R>();
+                    return Ok(());
+                };
+                let value = take_value::<R>(value)?;
+                if let Some(mut resource) = world.get_resource_mut::<R>() {
+                    *resource = value;
+                } else {
+                    world.insert_resource(value);
+                }
+                Ok(())
+            }),
+        })
+    }
+
+    /// Captures the component with the given [`TypeId`] through reflection.
+    ///
+    /// The type must be registered in the `type_registry`, with [`ReflectComponent`] and [`ReflectFromReflect`] type data.
+    pub fn reflect_component(
+        &mut self,
+        type_registry: &AppTypeRegistry,
+        type_id: TypeId,
+    ) -> Result<&mut Self, SnapshotError> {
+        let registry = type_registry.read();
+        let registration = registry
+            .get(type_id)
+            .ok_or(SnapshotError::UnregisteredType(type_id))?;
+        let type_path = registration.type_info().type_path();
+        let reflect_component = registration
+            .data::<ReflectComponent>()
+            .ok_or(SnapshotError::MissingReflectComponent(type_path))?
+            .clone();
+        let from_reflect = registration
+            .data::<ReflectFromReflect>()
+            .ok_or(SnapshotError::MissingReflectFromReflect(type_path))?
+            .clone();
+        drop(registry);
+
+        let capture_reflect_component = reflect_component.clone();
+        let capture_from_reflect = from_reflect.clone();
+        let type_registry = type_registry.clone();
+        Ok(self.add_component(SnapshotFns {
+            type_id,
+            capture: Box::new(move |world| match world.components().get_id(type_id) {
+                Some(id) => entities_with(world, id)
+                    .filter_map(|entity| {
+                        let value = capture_reflect_component.reflect(world.entity(entity))?;
+                        let value =
+                            capture_from_reflect.from_reflect(value.as_partial_reflect())?;
+                        Some((entity, value.into_partial_reflect()))
+                    })
+                    .collect(),
+                None => Vec::new(),
+            }),
+            restore: Box::new(move |world, data| {
+                let captured = data
+                    .into_iter()
+                    .map(|(entity, value)| {
+                        let value = reflect_value(value, type_id, &from_reflect, type_path)?;
+                        Ok((entity, value))
+                    })
+                    .collect::<Result<Vec<_>, SnapshotError>>()?;
+                let id = reflect_component.register_component(world);
+                let expected = captured.iter().map(|(entity, _)| *entity);
+                for entity in entities_not_in(world, id, expected) {
+                    // A hook or observer may have despawned the entity already.
+                    if let Ok(mut entity) = world.get_entity_mut(entity) {
+                        reflect_component.remove(&mut entity);
+                    }
+                }
+                let registry = type_registry.read();
+                for (entity, value) in captured {
+                    let mut entity_mut = world
+                        .get_entity_mut(entity)
+                        .map_err(|_| SnapshotError::EntityDespawned(entity))?;
+                    match reflect_component.reflect_mut(&mut entity_mut) {
+                        Some(mut component) => {
+                            component
+                                .set(value)
+                                .expect("snapshot data should match the component type");
+                        }
+                        None => {
+                            reflect_component.insert(
+                                &mut entity_mut,
+                                value.as_partial_reflect(),
+                                &registry,
+                            );
+                        }
+                    }
+                }
+                Ok(())
+            }),
+        }))
+    }
+
+    /// Captures the resource with the given [`TypeId`] through reflection.
+    ///
+    /// The type must be registered in the `type_registry`, with [`ReflectResource`] and [`ReflectFromReflect`] type data.
+    pub fn reflect_resource(
+        &mut self,
+        type_registry: &AppTypeRegistry,
+        type_id: TypeId,
+    ) -> Result<&mut Self, SnapshotError> {
+        let registry = type_registry.read();
+        let registration = registry
+            .get(type_id)
+            .ok_or(SnapshotError::UnregisteredType(type_id))?;
+        let type_path = registration.type_info().type_path();
+        let reflect_resource = registration
+            .data::<ReflectResource>()
+            .ok_or(SnapshotError::MissingReflectResource(type_path))?
+            .clone();
+        let from_reflect = registration
+            .data::<ReflectFromReflect>()
+            .ok_or(SnapshotError::MissingReflectFromReflect(type_path))?
+            .clone();
+        drop(registry);
+
+        let capture_reflect_resource = reflect_resource.clone();
+        let capture_from_reflect = from_reflect.clone();
+        let type_registry = type_registry.clone();
+        Ok(self.add_resource(SnapshotFns {
+            type_id,
+            capture: Box::new(move |world| {
+                let value = capture_reflect_resource.reflect(world)?;
+                let value = capture_from_reflect.from_reflect(value.as_partial_reflect())?;
+                Some(value.into_partial_reflect())
+            }),
+            restore: Box::new(move |world, data| {
+                let Some(value) = data else {
+                    reflect_resource.remove(world);
+                    return Ok(());
+                };
+                let value = reflect_value(value, type_id, &from_reflect, type_path)?;
+                match reflect_resource.reflect_mut(world) {
+                    Some(mut resource) => {
+                        resource
+                            .set(value)
+                            .expect("snapshot data should match the resource type");
+                    }
+                    None => {
+                        reflect_resource.insert(
+                            world,
+                            value.as_partial_reflect(),
+                            &type_registry.read(),
+                        );
+                    }
+                }
+                Ok(())
+            }),
+        }))
+    }
+
+    fn add_component(&mut self, fns: SnapshotFns<ComponentData>) -> &mut Self {
+        self.components.retain(|other| other.type_id != fns.type_id);
+        self.components.push(fns);
+        self
+    }
+
+    fn add_resource(&mut self, fns: SnapshotFns<ResourceData>) -> &mut Self {
+        self.resources.retain(|other| other.type_id != fns.type_id);
+        self.resources.push(fns);
+        self
+    }
+}
+
+/// Converts a captured or deserialized value back into a `T`.
+fn take_value<T: FromReflect>(value: Box<dyn PartialReflect>) -> Result<T, SnapshotError> {
+    value.try_take::<T>().or_else(|value| {
+        T::from_reflect(&*value).ok_or(SnapshotError::InvalidValue(type_name::<T>()))
+    })
+}
+
+/// Converts a captured or deserialized value back into the concrete type with the given [`TypeId`].
+fn reflect_value(
+    value: Box<dyn PartialReflect>,
+    type_id: TypeId,
+    from_reflect: &ReflectFromReflect,
+    type_path: &'static str,
+) -> Result<Box<dyn Reflect>, SnapshotError> {
+    let value = match value.try_into_reflect() {
+        Ok(value) if value.as_any().type_id() == type_id => return Ok(value),
+        Ok(value) => value.into_partial_reflect(),
+        Err(value) => value,
+    };
+    from_reflect
+        .from_reflect(&*value)
+        .ok_or(SnapshotError::InvalidValue(type_path))
+}
+
+/// The state of a [`World`] captured according to a [`SnapshotConfig`].
+///
+/// See the [module docs](crate::world::snapshot) for more info.
+pub struct WorldSnapshot {
+    entities: Vec<Entity>,
+    allocator: EntityAllocatorState,
+    change_tick: Tick,
+    components: Vec<(TypeId, ComponentData)>,
+    resources: Vec<(TypeId, ResourceData)>,
+}
+
+impl WorldSnapshot {
+    /// Returns the entities that were alive when this snapshot was taken.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Returns the change tick of the [`World`] when this snapshot was taken.
+    pub fn change_tick(&self) -> Tick {
+        self.change_tick
+    }
+}
+
+/// An error that occurs when configuring or restoring a [`WorldSnapshot`].
+#[derive(Error, Display, Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The type is not registered in the type registry.
+    #[display("The type with ID {_0:?} is not registered in the type registry.")]
+    #[error(ignore)]
+    UnregisteredType(TypeId),
+    /// The type does not have [`ReflectComponent`] type data.
+    #[display("The type `{_0}` does not have `ReflectComponent` type data.")]
+    #[error(ignore)]
+    MissingReflectComponent(&'static str),
+    /// The type does not have [`ReflectResource`] type data.
+    #[display("The type `{_0}` does not have `ReflectResource` type data.")]
+    #[error(ignore)]
+    MissingReflectResource(&'static str),
+    /// The type does not have [`ReflectFromReflect`] type data.
+    #[display("The type `{_0}` does not have `ReflectFromReflect` type data.")]
+    #[error(ignore)]
+    MissingReflectFromReflect(&'static str),
+    /// The snapshot was taken with a [`SnapshotConfig`] capturing different types.
+    #[display("The snapshot was taken with a configuration that captures different types.")]
+    ConfigMismatch,
+    /// A value of the snapshot could not be converted to its type, which can happen for deserialized snapshots.
+    #[display("A value of the snapshot could not be converted to `{_0}`.")]
+    #[error(ignore)]
+    InvalidValue(&'static str),
+    /// The index of an entity captured by the snapshot is used by an observer or registered system
+    /// spawned after the snapshot was taken.
+    #[display("The index of the entity {_0} is used by an observer or registered system.")]
+    #[error(ignore)]
+    EntityIndexInUse(Entity),
+    /// A hook or observer triggered while restoring the snapshot despawned an entity captured by it.
+    #[display("The entity {_0} was despawned while restoring the snapshot.")]
+    #[error(ignore)]
+    EntityDespawned(Entity),
+}
+
+impl World {
+    /// Captures the components and resources selected by `config`, along with the entity
+    /// allocation state of this world.
+    ///
+    /// See the [`snapshot`](crate::world::snapshot) module docs for more info.
+    pub fn snapshot(&mut self, config: &SnapshotConfig) -> WorldSnapshot {
+        self.flush();
+        WorldSnapshot {
+            entities: snapshot_entities(self).collect(),
+            components: config
+                .components
+                .iter()
+                .map(|fns| (fns.type_id, (fns.capture)(self)))
+                .collect(),
+            resources: config
+                .resources
+                .iter()
+                .map(|fns| (fns.type_id, (fns.capture)(self)))
+                .collect(),
+            change_tick: self.read_change_tick(),
+            allocator: self.entities.allocator_state(),
+        }
+    }
+
+    /// Restores a [`WorldSnapshot`] taken with the same `config`.
+    ///
+    /// Entities spawned since the snapshot are despawned, despawned entities are respawned with their
+    /// original IDs, and the captured components and resources are restored. Finally, the entity allocator
+    /// is reset to its captured state. Observers and registered systems are left untouched.
+    ///
+    /// The change tick of the world is not reset: every restored component and resource is marked as
+    /// changed at the current change tick, and components that are inserted again are also marked as added.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SnapshotError::ConfigMismatch`] if `config` does not capture the same types
+    /// as the configuration used to take the `snapshot`, and [`SnapshotError::EntityIndexInUse`] if an
+    /// observer or registered system spawned since the snapshot reuses the index of a captured entity.
+    /// The world is left untouched in both cases.
+    ///
+    /// Returns [`SnapshotError::EntityDespawned`] if a hook or observer triggered by the restore despawns
+    /// an entity captured by the snapshot, and [`SnapshotError::InvalidValue`] if a deserialized value
+    /// can't be converted to its type. The world is then only partially restored.
+    pub fn restore_snapshot(
+        &mut self,
+        config: &SnapshotConfig,
+        snapshot: WorldSnapshot,
+    ) -> Result<(), SnapshotError> {
+        fn matches<T, U>(fns: &[SnapshotFns<T>], data: &[(TypeId, U)]) -> bool {
+            fns.len() == data.len()
+                && fns
+                    .iter()
+                    .zip(data)
+                    .all(|(fns, (type_id, _))| fns.type_id == *type_id)
+        }
+        if !matches(&config.components, &snapshot.components)
+            || !matches(&config.resources, &snapshot.resources)
+        {
+            return Err(SnapshotError::ConfigMismatch);
+        }
+
+        self.flush();
+        let internal: HashSet<u32> = internal_entities(self).map(Entity::index).collect();
+        if let Some(entity) = snapshot
+            .entities
+            .iter()
+            .find(|entity| internal.contains(&entity.index()))
+        {
+            return Err(SnapshotError::EntityIndexInUse(*entity));
+        }
+
+        let alive: EntityHashSet = snapshot.entities.iter().copied().collect();
+        self.despawn_entities_not_in(&alive);
+        for entity in &snapshot.entities {
+            match self.entities.alloc_at_without_replacement(*entity) {
+                AllocAtWithoutReplacement::DidNotExist => {
+                    // SAFETY: the entity was just allocated.
+                    unsafe { self.spawn_at_empty_internal(*entity) };
+                }
+                AllocAtWithoutReplacement::Exists(_) => {}
+                AllocAtWithoutReplacement::ExistsWithWrongGeneration => {
+                    unreachable!("entities that are not part of the snapshot were just despawned")
+                }
+            }
+        }
+
+        for (fns, (_, data)) in config.components.iter().zip(snapshot.components) {
+            (fns.restore)(self, data)?;
+        }
+        for (fns, (_, data)) in config.resources.iter().zip(snapshot.resources) {
+            (fns.restore)(self, data)?;
+        }
+
+        // Hooks and observers triggered by the restore may have spawned or despawned entities.
+        self.flush();
+        self.despawn_entities_not_in(&alive);
+        if let Some(entity) = snapshot
+            .entities
+            .iter()
+            .find(|entity| self.get_entity(**entity).is_err())
+        {
+            return Err(SnapshotError::EntityDespawned(*entity));
+        }
+
+        self.entities.restore_allocator_state(&snapshot.allocator);
+        Ok(())
+    }
+
+    /// Despawns every entity that isn't in `alive`, apart from observers and registered systems,
+    /// until commands queued by despawning stop spawning new ones.
+    fn despawn_entities_not_in(&mut self, alive: &EntityHashSet) {
+        loop {
+            let extra: Vec<Entity> = snapshot_entities(self)
+                .filter(|entity| !alive.contains(entity))
+                .collect();
+            if extra.is_empty() {
+                break;
+            }
+            for entity in extra {
+                self.despawn(entity);
+            }
+            self.flush();
+        }
+    }
+}
+
+/// Returns `true` if the entities of `archetype` are observers or registered systems.
+fn is_internal(world: &World, archetype: &Archetype) -> bool {
+    [
+        world.component_id::<Observer>(),
+        world.component_id::<ObserverState>(),
+        world.component_id::<SystemIdMarker>(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|id| archetype.contains(id))
+}
+
+/// Iterates the entities that are captured by snapshots.
+fn snapshot_entities(world: &World) -> impl Iterator<Item = Entity> + '_ {
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| !is_internal(world, archetype))
+        .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+}
+
+/// Iterates the observers and registered systems, which are not captured by snapshots.
+fn internal_entities(world: &World) -> impl Iterator<Item = Entity> + '_ {
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| is_internal(world, archetype))
+        .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+}
+
+/// Iterates all captured entities that have the component `id`.
+fn entities_with(world: &World, id: ComponentId) -> impl Iterator<Item = Entity> + '_ {
+    world
+        .archetypes()
+        .iter()
+        .filter(move |archetype| archetype.contains(id) && !is_internal(world, archetype))
+        .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+}
+
+/// Collects all captured entities that have the component `id`, but are not part of `expected`.
+fn entities_not_in(
+    world: &World,
+    id: ComponentId,
+    expected: impl Iterator<Item = Entity>,
+) -> Vec<Entity> {
+    let expected: EntityHashSet = expected.collect();
+    entities_with(world, id)
+        .filter(|entity| !expected.contains(entity))
+        .collect()
+}
+
+#[cfg(feature = "serialize")]
+mod serialize {
+    use core::{any::TypeId, fmt};
+
+    use alloc::{boxed::Box, string::String, vec::Vec};
+    use bevy_reflect::{
+        serde::{TypedReflectDeserializer, TypedReflectSerializer},
+        PartialReflect, TypeRegistration, TypeRegistry,
+    };
+    use serde::{
+        de::{DeserializeSeed, Error as _, SeqAccess, Visitor},
+        ser::{SerializeSeq, SerializeTuple},
+        Deserializer, Serialize, Serializer,
+    };
+
+    use super::{ComponentData, ResourceData, WorldSnapshot};
+    use crate::{component::Tick, entity::Entity};
+
+    /// Serializes a [`WorldSnapshot`] as a `(change_tick, allocator, entities, components, resources)` tuple,
+    /// using the [`TypedReflectSerializer`] for the captured values.
+    ///
+    /// Every captured type must be registered in the [`TypeRegistry`], including types captured by cloning.
+    pub struct SnapshotSerializer<'a> {
+        snapshot: &'a WorldSnapshot,
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'a> SnapshotSerializer<'a> {
+        /// Creates a serializer of `snapshot`, whose captured types are registered in `registry`.
+        pub fn new(snapshot: &'a WorldSnapshot, registry: &'a TypeRegistry) -> Self {
+            Self { snapshot, registry }
+        }
+    }
+
+    impl Serialize for SnapshotSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut tuple = serializer.serialize_tuple(5)?;
+            tuple.serialize_element(&self.snapshot.change_tick.get())?;
+            tuple.serialize_element(&self.snapshot.allocator)?;
+            tuple.serialize_element(&self.snapshot.entities)?;
+            tuple.serialize_element(&ComponentsSerializer {
+                components: &self.snapshot.components,
+                registry: self.registry,
+            })?;
+            tuple.serialize_element(&ResourcesSerializer {
+                resources: &self.snapshot.resources,
+                registry: self.registry,
+            })?;
+            tuple.end()
+        }
+    }
+
+    fn type_path<E: serde::ser::Error>(
+        registry: &TypeRegistry,
+        type_id: TypeId,
+    ) -> Result<&'static str, E> {
+        registry
+            .get(type_id)
+            .map(|registration| registration.type_info().type_path())
+            .ok_or_else(|| {
+                E::custom(format_args!(
+                    "the type with ID {type_id:?} is not registered"
+                ))
+            })
+    }
+
+    struct ComponentsSerializer<'a> {
+        components: &'a [(TypeId, ComponentData)],
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for ComponentsSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.components.len()))?;
+            for (type_id, values) in self.components {
+                let values = ComponentValuesSerializer {
+                    values,
+                    registry: self.registry,
+                };
+                seq.serialize_element(&(type_path(self.registry, *type_id)?, values))?;
+            }
+            seq.end()
+        }
+    }
+
+    struct ComponentValuesSerializer<'a> {
+        values: &'a ComponentData,
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for ComponentValuesSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.values.iter().map(|(entity, value)| {
+                (entity, TypedReflectSerializer::new(&**value, self.registry))
+            }))
+        }
+    }
+
+    struct ResourcesSerializer<'a> {
+        resources: &'a [(TypeId, ResourceData)],
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for ResourcesSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.resources.len()))?;
+            for (type_id, value) in self.resources {
+                let value = value
+                    .as_deref()
+                    .map(|value| TypedReflectSerializer::new(value, self.registry));
+                seq.serialize_element(&(type_path(self.registry, *type_id)?, value))?;
+            }
+            seq.end()
+        }
+    }
+
+    /// Deserializes a [`WorldSnapshot`] serialized with [`SnapshotSerializer`].
+    pub struct SnapshotDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'a> SnapshotDeserializer<'a> {
+        /// Creates a deserializer of snapshots whose captured types are registered in `registry`.
+        pub fn new(registry: &'a TypeRegistry) -> Self {
+            Self { registry }
+        }
+    }
+
+    impl<'de> DeserializeSeed<'de> for SnapshotDeserializer<'_> {
+        type Value = WorldSnapshot;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<WorldSnapshot, D::Error> {
+            deserializer.deserialize_tuple(5, self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for SnapshotDeserializer<'_> {
+        type Value = WorldSnapshot;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a (change_tick, allocator, entities, components, resources) tuple")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<WorldSnapshot, A::Error> {
+            let change_tick: u32 = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+            let allocator = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+            let entities: Vec<Entity> = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(2, &self))?;
+            let components = seq
+                .next_element_seed(SeqDeserializer(ComponentDeserializer {
+                    registry: self.registry,
+                }))?
+                .ok_or_else(|| A::Error::invalid_length(3, &self))?;
+            let resources = seq
+                .next_element_seed(SeqDeserializer(ResourceDeserializer {
+                    registry: self.registry,
+                }))?
+                .ok_or_else(|| A::Error::invalid_length(4, &self))?;
+            let snapshot = WorldSnapshot {
+                entities,
+                allocator,
+                change_tick: Tick::new(change_tick),
+                components,
+                resources,
+            };
+            if !snapshot.allocator.is_consistent_with(&snapshot.entities) {
+                return Err(A::Error::custom(
+                    "the entity allocator state does not match the entities of the snapshot",
+                ));
+            }
+            Ok(snapshot)
+        }
+    }
+
+    fn registration<'a, E: serde::de::Error>(
+        registry: &'a TypeRegistry,
+        type_path: &str,
+    ) -> Result<&'a TypeRegistration, E> {
+        registry
+            .get_with_type_path(type_path)
+            .ok_or_else(|| E::custom(format_args!("the type `{type_path}` is not registered")))
+    }
+
+    /// Deserializes a sequence of values with the seed `S`.
+    #[derive(Clone, Copy)]
+    struct SeqDeserializer<S>(S);
+
+    impl<'de, S: DeserializeSeed<'de> + Copy> DeserializeSeed<'de> for SeqDeserializer<S> {
+        type Value = Vec<S::Value>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'de, S: DeserializeSeed<'de> + Copy> Visitor<'de> for SeqDeserializer<S> {
+        type Value = Vec<S::Value>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a sequence")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut values = Vec::new();
+            while let Some(value) = seq.next_element_seed(self.0)? {
+                values.push(value);
+            }
+            Ok(values)
+        }
+    }
+
+    /// Deserializes the `(type_path, values)` tuple of a component.
+    #[derive(Clone, Copy)]
+    struct ComponentDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'de> DeserializeSeed<'de> for ComponentDeserializer<'_> {
+        type Value = (TypeId, ComponentData);
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_tuple(2, self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for ComponentDeserializer<'_> {
+        type Value = (TypeId, ComponentData);
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a (type_path, values) tuple")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let type_path: String = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+            let registration = registration(self.registry, &type_path)?;
+            let values = seq
+                .next_element_seed(SeqDeserializer(EntityValueDeserializer {
+                    registration,
+                    registry: self.registry,
+                }))?
+                .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+            Ok((registration.type_id(), values))
+        }
+    }
+
+    /// Deserializes the `(entity, value)` tuple of a component value.
+    #[derive(Clone, Copy)]
+    struct EntityValueDeserializer<'a> {
+        registration: &'a TypeRegistration,
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'de> DeserializeSeed<'de> for EntityValueDeserializer<'_> {
+        type Value = (Entity, Box<dyn PartialReflect>);
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_tuple(2, self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for EntityValueDeserializer<'_> {
+        type Value = (Entity, Box<dyn PartialReflect>);
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an (entity, value) tuple")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let entity = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+            let value = seq
+                .next_element_seed(TypedReflectDeserializer::new(
+                    self.registration,
+                    self.registry,
+                ))?
+                .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+            Ok((entity, value))
+        }
+    }
+
+    /// Deserializes the `(type_path, value)` tuple of a resource.
+    #[derive(Clone, Copy)]
+    struct ResourceDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'de> DeserializeSeed<'de> for ResourceDeserializer<'_> {
+        type Value = (TypeId, ResourceData);
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_tuple(2, self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for ResourceDeserializer<'_> {
+        type Value = (TypeId, ResourceData);
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a (type_path, value) tuple")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let type_path: String = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+            let registration = registration(self.registry, &type_path)?;
+            let value = seq
+                .next_element_seed(OptionalValueDeserializer {
+                    registration,
+                    registry: self.registry,
+                })?
+                .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+            Ok((registration.type_id(), value))
+        }
+    }
+
+    /// Deserializes the optional value of a resource.
+    struct OptionalValueDeserializer<'a> {
+        registration: &'a TypeRegistration,
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'de> DeserializeSeed<'de> for OptionalValueDeserializer<'_> {
+        type Value = ResourceData;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<ResourceData, D::Error> {
+            deserializer.deserialize_option(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for OptionalValueDeserializer<'_> {
+        type Value = ResourceData;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an optional value")
+        }
+
+        fn visit_none<E: serde::de::Error>(self) -> Result<ResourceData, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<ResourceData, D::Error> {
+            TypedReflectDeserializer::new(self.registration, self.registry)
+                .deserialize(deserializer)
+                .map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::prelude::{Commands, Component, OnAdd, Resource, Trigger};
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Clone, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Clone, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct Stunned;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    struct Untracked(u32);
+
+    #[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+    struct Score(u32);
+
+    fn config() -> SnapshotConfig {
+        let mut config = SnapshotConfig::new();
+        config
+            .component::<Health>()
+            .component::<Stunned>()
+            .resource::<Score>();
+        config
+    }
+
+    #[test]
+    fn restores_components_and_entities() {
+        let config = config();
+        let mut world = World::new();
+        let a = world.spawn((Health(10), Untracked(1))).id();
+        let b = world.spawn((Health(20), Stunned)).id();
+        let c = world.spawn(Health(30)).id();
+        let snapshot = world.snapshot(&config);
+
+        world
+            .entity_mut(a)
+            .insert((Health(0), Stunned, Untracked(2)));
+        world.entity_mut(b).remove::<Stunned>();
+        world.despawn(c);
+        let d = world.spawn(Health(40)).id();
+
+        world.restore_snapshot(&config, snapshot).unwrap();
+        assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+        assert_eq!(world.get::<Stunned>(a), None);
+        assert_eq!(world.get::<Untracked>(a), Some(&Untracked(2)));
+        assert_eq!(world.get::<Stunned>(b), Some(&Stunned));
+        assert_eq!(world.get::<Health>(c), Some(&Health(30)));
+        assert!(world.get_entity(d).is_err());
+        assert_eq!(world.entities().len(), 3);
+    }
+
+    #[test]
+    fn restores_entity_allocation() {
+        let config = SnapshotConfig::new();
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        world.spawn_empty();
+        world.despawn(a);
+        let snapshot = world.snapshot(&config);
+
+        let expected = [world.spawn_empty().id(), world.spawn_empty().id()];
+        world.restore_snapshot(&config, snapshot).unwrap();
+        let respawned = [world.spawn_empty().id(), world.spawn_empty().id()];
+        assert_eq!(expected, respawned);
+    }
+
+    #[test]
+    fn restores_resources() {
+        let config = config();
+        let mut world = World::new();
+        let snapshot = world.snapshot(&config);
+        world.insert_resource(Score(1));
+        world.restore_snapshot(&config, snapshot).unwrap();
+        assert!(world.get_resource::<Score>().is_none());
+
+        world.insert_resource(Score(2));
+        let snapshot = world.snapshot(&config);
+        world.resource_mut::<Score>().0 = 3;
+        world.restore_snapshot(&config, snapshot).unwrap();
+        assert_eq!(world.resource::<Score>(), &Score(2));
+    }
+
+    #[test]
+    fn restores_with_current_change_tick() {
+        let config = config();
+        let mut world = World::new();
+        let a = world.spawn(Health(1)).id();
+        let b = world.spawn(Health(1)).id();
+        world.insert_resource(Score(1));
+        let added = world.entity(a).get_change_ticks::<Health>().unwrap().added;
+        let snapshot = world.snapshot(&config);
+
+        world.increment_change_tick();
+        world.entity_mut(a).get_mut::<Health>().unwrap().0 = 2;
+        world.entity_mut(b).remove::<Health>();
+        world.resource_mut::<Score>().0 = 2;
+        world.increment_change_tick();
+        let tick = world.read_change_tick();
+
+        world.restore_snapshot(&config, snapshot).unwrap();
+        assert_eq!(world.read_change_tick(), tick);
+        // Overwritten components keep their added tick.
+        let restored = world.entity(a).get_change_ticks::<Health>().unwrap();
+        assert_eq!(restored.added, added);
+        assert_eq!(restored.changed, tick);
+        // Inserted components are added again.
+        let restored = world.entity(b).get_change_ticks::<Health>().unwrap();
+        assert_eq!(restored.added, tick);
+        assert_eq!(restored.changed, tick);
+        let restored = world.get_resource_change_ticks::<Score>().unwrap();
+        assert_eq!(restored.changed, tick);
+    }
+
+    #[test]
+    fn custom_clone_strategy() {
+        let mut config = SnapshotConfig::new();
+        config.component_with::<Untracked>(|value| Untracked(value.0 + 1));
+        let mut world = World::new();
+        let entity = world.spawn(Untracked(1)).id();
+        let snapshot = world.snapshot(&config);
+        world.restore_snapshot(&config, snapshot).unwrap();
+        assert_eq!(world.get::<Untracked>(entity), Some(&Untracked(2)));
+    }
+
+    #[test]
+    fn entity_despawned_while_restoring() {
+        let config = config();
+        let mut world = World::new();
+        let a = world.spawn(Health(1)).id();
+        let b = world.spawn(Stunned).id();
+        world.add_observer(move |_: Trigger<OnAdd, Health>, mut commands: Commands| {
+            commands.entity(b).despawn();
+        });
+        let snapshot = world.snapshot(&config);
+
+        world.entity_mut(a).remove::<Health>();
+        assert_eq!(
+            world.restore_snapshot(&config, snapshot),
+            Err(SnapshotError::EntityDespawned(b))
+        );
+    }
+
+    #[test]
+    fn keeps_observers_and_registered_systems() {
+        let config = config();
+        let mut world = World::new();
+        let a = world.spawn(Health(1)).id();
+        let snapshot = world.snapshot(&config);
+
+        let observer = world
+            .add_observer(|trigger: Trigger<OnAdd, Health>, mut commands: Commands| {
+                commands.entity(trigger.entity()).insert(Stunned);
+            })
+            .id();
+        let system = world.register_system(|| {});
+        world.restore_snapshot(&config, snapshot).unwrap();
+
+        assert!(world.get_entity(observer).is_ok());
+        world.run_system(system).unwrap();
+        let b = world.spawn(Health(2)).id();
+        world.flush();
+        assert_ne!(b, observer);
+        assert_ne!(b, a);
+        assert_eq!(world.get::<Stunned>(b), Some(&Stunned));
+    }
+
+    #[test]
+    fn entity_index_in_use() {
+        let config = config();
+        let mut world = World::new();
+        let a = world.spawn(Health(1)).id();
+        let snapshot = world.snapshot(&config);
+
+        world.despawn(a);
+        let observer = world.add_observer(|_: Trigger<OnAdd, Health>| {}).id();
+        assert_eq!(observer.index(), a.index());
+        assert_eq!(
+            world.restore_snapshot(&config, snapshot),
+            Err(SnapshotError::EntityIndexInUse(a))
+        );
+        assert!(world.get_entity(observer).is_ok());
+    }
+
+    #[test]
+    fn config_mismatch() {
+        let mut world = World::new();
+        let snapshot = world.snapshot(&config());
+        let mut other = SnapshotConfig::new();
+        other.component::<Health>();
+        assert_eq!(
+            world.restore_snapshot(&other, snapshot),
+            Err(SnapshotError::ConfigMismatch)
+        );
+    }
+
+    #[test]
+    fn reflect_strategy() {
+        #[derive(Component, Reflect, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct Name(String);
+
+        #[derive(Resource, Reflect, Debug, PartialEq)]
+        #[reflect(Resource)]
+        struct Level(Vec<u32>);
+
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Name>();
+        registry.write().register::<Level>();
+
+        let mut config = SnapshotConfig::new();
+        config
+            .reflect_component(&registry, TypeId::of::<Name>())
+            .unwrap()
+            .reflect_resource(&registry, TypeId::of::<Level>())
+            .unwrap();
+        assert_eq!(
+            config
+                .reflect_component(&registry, TypeId::of::<Health>())
+                .err(),
+            Some(SnapshotError::UnregisteredType(TypeId::of::<Health>()))
+        );
+
+        let mut world = World::new();
+        let a = world.spawn(Name("a".into())).id();
+        let b = world.spawn_empty().id();
+        world.insert_resource(Level(vec![1, 2]));
+        let snapshot = world.snapshot(&config);
+
+        world.entity_mut(a).insert(Name("renamed".into()));
+        world.entity_mut(b).insert(Name("b".into()));
+        world.resource_mut::<Level>().0.push(3);
+
+        world.restore_snapshot(&config, snapshot).unwrap();
+        assert_eq!(world.get::<Name>(a), Some(&Name("a".into())));
+        assert_eq!(world.get::<Name>(b), None);
+        assert_eq!(world.resource::<Level>(), &Level(vec![1, 2]));
+
+        // The same state can be restored again by taking a new snapshot.
+        let snapshot = world.snapshot(&config);
+        world.despawn(a);
+        world.restore_snapshot(&config, snapshot).unwrap();
+        assert_eq!(world.get::<Name>(a), Some(&Name("a".into())));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn serialize_snapshot() {
+        use serde::de::DeserializeSeed;
+
+        #[derive(Component, Reflect, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct Name(String);
+
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Health>();
+        registry.write().register::<Stunned>();
+        registry.write().register::<Score>();
+        registry.write().register::<Name>();
+        let mut config = config();
+        config
+            .reflect_component(&registry, TypeId::of::<Name>())
+            .unwrap();
+
+        let mut world = World::new();
+        let a = world.spawn((Health(10), Name("a".into()))).id();
+        let b = world.spawn(Health(20)).id();
+        world.despawn(b);
+        world.insert_resource(Score(3));
+        let snapshot = world.snapshot(&config);
+
+        let registry = registry.read();
+        let serialized = ron::to_string(&SnapshotSerializer::new(&snapshot, &registry)).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = SnapshotDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(deserialized.entities(), snapshot.entities());
+
+        world
+            .entity_mut(a)
+            .insert((Health(0), Name("renamed".into())));
+        world.resource_mut::<Score>().0 = 4;
+        let c = world.spawn(Health(30)).id();
+        world.restore_snapshot(&config, deserialized).unwrap();
+        assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+        assert_eq!(world.get::<Name>(a), Some(&Name("a".into())));
+        assert_eq!(world.resource::<Score>(), &Score(3));
+        assert!(world.get_entity(c).is_err());
+        assert_eq!(world.spawn_empty().id(), c);
+
+        // Snapshots with an allocator state that doesn't match their entities are rejected.
+        let tampered = serialized.replace("len:1", "len:2");
+        let mut deserializer = ron::Deserializer::from_str(&tampered).unwrap();
+        assert!(SnapshotDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .is_err());
+    }
+}
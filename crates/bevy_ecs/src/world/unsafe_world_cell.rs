@@ -232,6 +232,16 @@ impl<'w> UnsafeWorldCell<'w> {
         &unsafe { self.world_metadata() }.archetypes
     }
 
+    /// Returns `true` if queries iterate in a deterministic order.
+    ///
+    /// See [`World::set_deterministic_query_order`].
+    #[inline]
+    pub fn deterministic_query_order(self) -> bool {
+        // SAFETY:
+        // - we only access world metadata
+        unsafe { self.world_metadata() }.deterministic_query_order
+    }
+
     /// Retrieves this world's [`Components`] collection.
     #[inline]
     pub fn components(self) -> &'w Components {
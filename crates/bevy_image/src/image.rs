@@ -5,7 +5,7 @@ use super::dds::*;
 #[cfg(feature = "ktx2")]
 use super::ktx2::*;
 
-use bevy_asset::{Asset, RenderAssetUsages};
+use bevy_asset::{Asset, AssetMemoryUsage, RenderAssetUsages};
 use bevy_color::{Color, ColorToComponents, Gray, LinearRgba, Srgba, Xyza};
 use bevy_math::{AspectRatio, UVec2, UVec3, Vec2};
 use bevy_reflect::std_traits::ReflectDefault;
@@ -664,6 +664,15 @@ impl Default for Image {
     }
 }
 
+impl AssetMemoryUsage for Image {
+    /// Reports the size of the image in the main world, including its pixel [`data`](Self::data). This does not
+    /// include the GPU texture. Images without [`RenderAssetUsages::MAIN_WORLD`] are removed from the main world
+    /// once they are sent to the render world.
+    fn memory_usage(&self) -> usize {
+        size_of::<Self>() + self.data.capacity()
+    }
+}
+
 impl Image {
     /// Creates a new image from raw binary data and the corresponding metadata.
     ///
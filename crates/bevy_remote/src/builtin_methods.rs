@@ -48,12 +48,22 @@ pub const BRP_REPARENT_METHOD: &str = "bevy/reparent";
 /// The method path for a `bevy/list` request.
 pub const BRP_LIST_METHOD: &str = "bevy/list";
 
+/// The method path for a `bevy/list_resources` request.
+pub const BRP_LIST_RESOURCES_METHOD: &str = "bevy/list_resources";
+
 /// The method path for a `bevy/get+watch` request.
 pub const BRP_GET_AND_WATCH_METHOD: &str = "bevy/get+watch";
 
 /// The method path for a `bevy/list+watch` request.
 pub const BRP_LIST_AND_WATCH_METHOD: &str = "bevy/list+watch";
 
+/// The method path for a `bevy/memory_stats` request.
+pub const BRP_MEMORY_STATS_METHOD: &str = "bevy/memory_stats";
+
+/// The method path for a `bevy/asset_graph` request.
+#[cfg(feature = "bevy_asset")]
+pub const BRP_ASSET_GRAPH_METHOD: &str = "bevy/asset_graph";
+
 /// `bevy/get`: Retrieves one or more components from the entity with the given
 /// ID.
 ///
@@ -291,6 +301,84 @@ pub struct BrpListWatchingResponse {
     removed: Vec<String>,
 }
 
+/// `bevy/asset_graph`: Retrieves the dependency graph around the asset at the given path.
+///
+/// The server responds with an [`AssetDependencyGraph`](bevy_asset::AssetDependencyGraph).
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrpAssetGraphParams {
+    /// The asset path of the asset.
+    pub path: String,
+}
+
+/// The response to a `bevy/memory_stats` request.
+///
+/// See [`MemoryStats`](bevy_ecs::memory_stats::MemoryStats).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrpMemoryStatsResponse {
+    /// The number of bytes used by component values.
+    pub component_bytes: usize,
+    /// The number of bytes allocated for component values but not used.
+    pub spare_bytes: usize,
+    /// The statistics of each archetype.
+    pub archetypes: Vec<BrpArchetypeStats>,
+    /// The statistics of each table.
+    pub tables: Vec<BrpTableStats>,
+    /// The statistics of each sparse set.
+    pub sparse_sets: Vec<BrpSparseSetStats>,
+}
+
+/// The statistics of an archetype in a [`BrpMemoryStatsResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrpArchetypeStats {
+    /// The index of the archetype.
+    pub id: usize,
+    /// The index of the table storing the table components of the archetype.
+    pub table: usize,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The names of the components of the archetype.
+    pub components: Vec<String>,
+    /// The number of other archetypes that have exactly one more or one less component.
+    pub single_component_neighbors: usize,
+}
+
+/// The statistics of a table in a [`BrpMemoryStatsResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrpTableStats {
+    /// The index of the table.
+    pub id: usize,
+    /// The number of entities in the table.
+    pub entity_count: usize,
+    /// The number of entities the table can store without reallocating.
+    pub entity_capacity: usize,
+    /// The statistics of each column of the table.
+    pub columns: Vec<BrpStorageStats>,
+}
+
+/// The statistics of a table column or a sparse set in a [`BrpMemoryStatsResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrpStorageStats {
+    /// The name of the stored component.
+    pub component: String,
+    /// The size of a component value, in bytes.
+    pub item_size: usize,
+    /// The number of bytes used by the component values.
+    pub bytes: usize,
+    /// The number of bytes allocated for component values but not used.
+    pub spare_bytes: usize,
+}
+
+/// The statistics of a sparse set in a [`BrpMemoryStatsResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrpSparseSetStats {
+    /// The number of component values in the sparse set.
+    pub len: usize,
+    /// The memory used by the sparse set.
+    #[serde(flatten)]
+    pub storage: BrpStorageStats,
+}
+
 /// The response to a `bevy/query` request.
 pub type BrpQueryResponse = Vec<BrpQueryRow>;
 
@@ -747,6 +835,93 @@ pub fn process_remote_list_request(In(params): In<Option<Value>>, world: &World)
     serde_json::to_value(response).map_err(BrpError::internal)
 }
 
+/// Handles a `bevy/list_resources` request (list all resources) coming from a client.
+pub fn process_remote_list_resources_request(In(_): In<Option<Value>>, world: &World) -> BrpResult {
+    let mut response: BrpListResponse = world
+        .iter_resources()
+        .map(|(info, _)| info.name().to_owned())
+        .collect();
+    response.sort();
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/memory_stats` request coming from a client.
+pub fn process_remote_memory_stats_request(In(_): In<Option<Value>>, world: &World) -> BrpResult {
+    let stats = world.memory_stats();
+    let component_name = |id: ComponentId| {
+        world
+            .components()
+            .get_name(id)
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    };
+    let storage = |id, item_size, bytes, spare_bytes| BrpStorageStats {
+        component: component_name(id),
+        item_size,
+        bytes,
+        spare_bytes,
+    };
+
+    let response = BrpMemoryStatsResponse {
+        component_bytes: stats.component_bytes(),
+        spare_bytes: stats.spare_bytes(),
+        archetypes: stats
+            .archetypes
+            .iter()
+            .map(|archetype| BrpArchetypeStats {
+                id: archetype.id.index(),
+                table: archetype.table_id.as_usize(),
+                entity_count: archetype.entity_count,
+                components: world.archetypes()[archetype.id]
+                    .components()
+                    .map(component_name)
+                    .collect(),
+                single_component_neighbors: archetype.single_component_neighbors,
+            })
+            .collect(),
+        tables: stats
+            .tables
+            .iter()
+            .map(|table| BrpTableStats {
+                id: table.id.as_usize(),
+                entity_count: table.entity_count,
+                entity_capacity: table.entity_capacity,
+                columns: table
+                    .columns
+                    .iter()
+                    .map(|c| storage(c.component_id, c.item_size, c.bytes, c.spare_bytes))
+                    .collect(),
+            })
+            .collect(),
+        sparse_sets: stats
+            .sparse_sets
+            .iter()
+            .map(|s| BrpSparseSetStats {
+                len: s.len,
+                storage: storage(s.component_id, s.item_size, s.bytes, s.spare_bytes),
+            })
+            .collect(),
+    };
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/asset_graph` request coming from a client.
+#[cfg(feature = "bevy_asset")]
+pub fn process_remote_asset_graph_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpAssetGraphParams { path } = parse_some(params)?;
+    let graph = world
+        .get_resource::<bevy_asset::AssetServer>()
+        .and_then(|asset_server| asset_server.get_path_dependency_graph(&path))
+        .ok_or_else(|| BrpError::asset_not_found(&path))?;
+
+    serde_json::to_value(graph).map_err(BrpError::internal)
+}
+
 /// Handles a `bevy/list` request (list all components) coming from a client.
 pub fn process_remote_list_watching_request(
     In(params): In<Option<Value>>,
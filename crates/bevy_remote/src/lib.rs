@@ -212,6 +212,46 @@
 //!
 //! `result`: An array of fully-qualified type names of components.
 //!
+//! ### bevy/list\_resources
+//!
+//! List all resources present in the world, whether or not they are reflected.
+//!
+//! `params`: None.
+//!
+//! `result`: An array of fully-qualified type names of resources.
+//!
+//! ### bevy/memory\_stats
+//!
+//! Report the memory used by the components of every archetype, table and sparse set, to diagnose
+//! archetype fragmentation.
+//!
+//! `params`: None.
+//!
+//! `result`:
+//! - `component_bytes`: The number of bytes used by component values.
+//! - `spare_bytes`: The number of bytes allocated for component values but not used.
+//! - `archetypes`: An array of archetypes, each with its `id`, `table`, `entity_count`,
+//!   `components` and `single_component_neighbors`: the number of other archetypes that have
+//!   exactly one more or one less component.
+//! - `tables`: An array of tables, each with its `id`, `entity_count`, `entity_capacity` and
+//!   `columns`. Each column has the fully-qualified type name of its `component`, its `item_size`,
+//!   and the `bytes` and `spare_bytes` of its values.
+//! - `sparse_sets`: An array of sparse sets, each with the `len`, `component`, `item_size`,
+//!   `bytes` and `spare_bytes` of its values.
+//!
+//! ### bevy/asset\_graph
+//!
+//! Report the dependency graph around an asset: everything it recursively depends on and everything
+//! that recursively depends on it. This requires the `bevy_asset` feature.
+//!
+//! `params`:
+//! - `path`: The asset path of the asset, which must have been loaded by the `AssetServer`.
+//!
+//! `result`:
+//! - `nodes`: An array of assets, with the requested asset first. Each asset has its `id`, its `path`,
+//!   its `load_state`, the `byte_size` read by its loader, and the indices in `nodes` of its direct
+//!   `dependencies` and `dependants`.
+//!
 //! ### bevy/get+watch
 //!
 //! Watch the values of one or more components from an entity.
@@ -340,6 +380,20 @@ impl RemotePlugin {
         }
     }
 
+    /// Add the built-in methods that inspect the `AssetServer`.
+    #[cfg(feature = "bevy_asset")]
+    fn with_asset_methods(self) -> Self {
+        self.with_method(
+            builtin_methods::BRP_ASSET_GRAPH_METHOD,
+            builtin_methods::process_remote_asset_graph_request,
+        )
+    }
+
+    #[cfg(not(feature = "bevy_asset"))]
+    fn with_asset_methods(self) -> Self {
+        self
+    }
+
     /// Add a remote method to the plugin using the given `name` and `handler`.
     #[must_use]
     pub fn with_method<M>(
@@ -404,6 +458,15 @@ impl Default for RemotePlugin {
                 builtin_methods::BRP_LIST_METHOD,
                 builtin_methods::process_remote_list_request,
             )
+            .with_method(
+                builtin_methods::BRP_LIST_RESOURCES_METHOD,
+                builtin_methods::process_remote_list_resources_request,
+            )
+            .with_method(
+                builtin_methods::BRP_MEMORY_STATS_METHOD,
+                builtin_methods::process_remote_memory_stats_request,
+            )
+            .with_asset_methods()
             .with_watching_method(
                 builtin_methods::BRP_GET_AND_WATCH_METHOD,
                 builtin_methods::process_remote_get_watching_request,
@@ -689,6 +752,16 @@ impl BrpError {
             data: None,
         }
     }
+
+    /// Asset wasn't found.
+    #[must_use]
+    pub fn asset_not_found(path: &str) -> Self {
+        Self {
+            code: error_codes::ASSET_NOT_FOUND,
+            message: format!("Asset `{path}` not found"),
+            data: None,
+        }
+    }
 }
 
 /// Error codes used by BRP.
@@ -724,6 +797,9 @@ pub mod error_codes {
 
     /// Cannot reparent an entity to itself.
     pub const SELF_REPARENT: i16 = -23404;
+
+    /// Could not find asset.
+    pub const ASSET_NOT_FOUND: i16 = -23405;
 }
 
 /// The result of a request.
@@ -0,0 +1,625 @@
+use crate::{DynamicEntity, DynamicScene};
+use alloc::{borrow::ToOwned, boxed::Box, format, string::String, vec::Vec};
+use bevy_ecs::entity::Entity;
+use bevy_reflect::{PartialReflect, ReflectRef, VariantType};
+use bevy_utils::HashMap;
+use core::{
+    fmt::{self, Write},
+    hash::Hasher,
+};
+use derive_more::derive::{Display, Error};
+
+impl DynamicScene {
+    /// Computes a hash of the entities, components and resources of this scene.
+    ///
+    /// The hash only depends on the values of the scene, and not on the order of its entities, components
+    /// or resources. This makes it suitable to detect that two [`World`](bevy_ecs::world::World)s diverged,
+    /// for example between the peers of a lockstep game:
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_scene::{DynamicSceneBuilder, SceneFilter};
+    /// # use core::any::TypeId;
+    /// fn world_hash(world: &World) -> u64 {
+    ///     DynamicSceneBuilder::from_world(world)
+    ///         // Use the same `SceneFilter` semantics as when saving a scene.
+    ///         .with_component_filter(SceneFilter::deny_all().allow_by_id(TypeId::of::<Name>()))
+    ///         .extract_entities(world.iter_entities().map(|entity| entity.id()))
+    ///         .extract_resources()
+    ///         .build()
+    ///         .stable_hash()
+    ///         .unwrap()
+    /// }
+    /// # #[derive(Component, Reflect)]
+    /// # #[reflect(Component)]
+    /// # struct Name;
+    /// # use bevy_reflect::Reflect;
+    /// # use bevy_ecs::reflect::ReflectComponent;
+    /// ```
+    ///
+    /// Entities are hashed with their [`Entity`] id, so both worlds must allocate entities in the same order.
+    /// Values are hashed field by field. Opaque types like strings are hashed with
+    /// [`PartialReflect::reflect_hash`], and `f32` and `f64` with their bit pattern.
+    /// Since `reflect_hash` doesn't give the same result on every platform, only compare hashes computed
+    /// by the same build on the same platform.
+    ///
+    /// Returns an error if the scene contains an opaque value that doesn't support `reflect_hash`,
+    /// in which case `#[reflect(Hash)]` needs to be added to its type.
+    pub fn stable_hash(&self) -> Result<u64, StableHashError> {
+        let mut hasher = StableHasher::default();
+        let mut entities: Vec<&DynamicEntity> = self.entities.iter().collect();
+        entities.sort_by_key(|entity| entity.entity);
+        write_len(&mut hasher, entities.len());
+        for entity in entities {
+            hasher.write(&entity.entity.to_bits().to_le_bytes());
+            hash_values(&mut hasher, &entity.components)?;
+        }
+        hash_values(&mut hasher, &self.resources)?;
+        Ok(hasher.finish())
+    }
+
+    /// Returns the differences between this scene and `other`.
+    ///
+    /// Entities are matched by their [`Entity`] id, and components and resources by their type path.
+    /// Added and removed refer to what `other` has compared to this scene.
+    ///
+    /// This is useful for golden tests of systems, by comparing a snapshot of the world before and after they run,
+    /// or to find what caused two worlds to diverge when their [`stable_hash`](Self::stable_hash) differs.
+    pub fn diff(&self, other: &DynamicScene) -> SceneDiff {
+        let entities: HashMap<Entity, &DynamicEntity> = self
+            .entities
+            .iter()
+            .map(|entity| (entity.entity, entity))
+            .collect();
+        let other_entities: HashMap<Entity, &DynamicEntity> = other
+            .entities
+            .iter()
+            .map(|entity| (entity.entity, entity))
+            .collect();
+
+        let mut diff = SceneDiff {
+            resources: diff_values(&self.resources, &other.resources),
+            ..Default::default()
+        };
+        for (&entity, dynamic_entity) in &entities {
+            match other_entities.get(&entity) {
+                None => diff.removed_entities.push(entity),
+                Some(other) => {
+                    let components = diff_values(&dynamic_entity.components, &other.components);
+                    if !components.is_empty() {
+                        diff.changed_entities
+                            .push(EntityDiff { entity, components });
+                    }
+                }
+            }
+        }
+        diff.added_entities = other_entities
+            .keys()
+            .filter(|&entity| !entities.contains_key(entity))
+            .copied()
+            .collect();
+
+        diff.added_entities.sort();
+        diff.removed_entities.sort();
+        diff.changed_entities.sort_by_key(|entity| entity.entity);
+        diff
+    }
+}
+
+/// An error returned by [`DynamicScene::stable_hash`].
+#[derive(Error, Display, Debug)]
+pub enum StableHashError {
+    /// The scene contains a value that can't be hashed.
+    #[display("scene contains the value `{type_path}`, which doesn't support `reflect_hash`. consider adding `#[reflect(Hash)]` to its type")]
+    Unhashable {
+        /// Type of the value that can't be hashed.
+        type_path: String,
+    },
+}
+
+/// The differences between two [`DynamicScene`]s, returned by [`DynamicScene::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SceneDiff {
+    /// The entities that are only in the other scene.
+    pub added_entities: Vec<Entity>,
+    /// The entities that are only in this scene.
+    pub removed_entities: Vec<Entity>,
+    /// The entities in both scenes whose components differ.
+    pub changed_entities: Vec<EntityDiff>,
+    /// The differences between the resources of the scenes.
+    pub resources: ValuesDiff,
+}
+
+impl SceneDiff {
+    /// Returns `true` if the scenes are equal.
+    pub fn is_empty(&self) -> bool {
+        self.added_entities.is_empty()
+            && self.removed_entities.is_empty()
+            && self.changed_entities.is_empty()
+            && self.resources.is_empty()
+    }
+}
+
+impl fmt::Display for SceneDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entity in &self.added_entities {
+            writeln!(f, "+ {entity}")?;
+        }
+        for entity in &self.removed_entities {
+            writeln!(f, "- {entity}")?;
+        }
+        for entity in &self.changed_entities {
+            writeln!(f, "~ {}", entity.entity)?;
+            entity.components.fmt_indented(f, "  ")?;
+        }
+        if !self.resources.is_empty() {
+            writeln!(f, "~ resources")?;
+            self.resources.fmt_indented(f, "  ")?;
+        }
+        Ok(())
+    }
+}
+
+/// The differences between the components of an entity in two [`DynamicScene`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityDiff {
+    /// The entity.
+    pub entity: Entity,
+    /// The differences between its components.
+    pub components: ValuesDiff,
+}
+
+/// The differences between two sets of components or resources, identified by their type path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValuesDiff {
+    /// The type paths of the values that are only in the other scene.
+    pub added: Vec<String>,
+    /// The type paths of the values that are only in this scene.
+    pub removed: Vec<String>,
+    /// The values in both scenes that differ.
+    pub changed: Vec<ChangedValue>,
+}
+
+impl ValuesDiff {
+    /// Returns `true` if the values are equal.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: &str) -> fmt::Result {
+        for type_path in &self.added {
+            writeln!(f, "{indent}+ {type_path}")?;
+        }
+        for type_path in &self.removed {
+            writeln!(f, "{indent}- {type_path}")?;
+        }
+        for value in &self.changed {
+            for path in &value.paths {
+                writeln!(f, "{indent}~ {}{path}", value.type_path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A component or resource whose value differs between two [`DynamicScene`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedValue {
+    /// The type path of the value.
+    pub type_path: String,
+    /// The [reflect paths](bevy_reflect::GetPath) of the fields that differ, like `.translation.x`.
+    ///
+    /// An empty path means that the whole value differs, for example when it's opaque or an enum changed variant.
+    /// Lists and arrays are reported as a whole if their length differ, and maps and sets are always reported as a whole.
+    pub paths: Vec<String>,
+}
+
+fn type_path(value: &dyn PartialReflect) -> &str {
+    value
+        .get_represented_type_info()
+        .map(bevy_reflect::TypeInfo::type_path)
+        .unwrap_or_else(|| value.reflect_type_path())
+}
+
+fn diff_values(
+    values: &[Box<dyn PartialReflect>],
+    others: &[Box<dyn PartialReflect>],
+) -> ValuesDiff {
+    let by_type: HashMap<&str, &dyn PartialReflect> = values
+        .iter()
+        .map(|value| (type_path(value.as_ref()), value.as_ref()))
+        .collect();
+    let others_by_type: HashMap<&str, &dyn PartialReflect> = others
+        .iter()
+        .map(|value| (type_path(value.as_ref()), value.as_ref()))
+        .collect();
+
+    let mut diff = ValuesDiff::default();
+    for (&type_path, &value) in &by_type {
+        match others_by_type.get(type_path) {
+            None => diff.removed.push(type_path.to_owned()),
+            Some(&other) => {
+                let mut paths = Vec::new();
+                diff_value(value, other, &mut String::new(), &mut paths);
+                if !paths.is_empty() {
+                    diff.changed.push(ChangedValue {
+                        type_path: type_path.to_owned(),
+                        paths,
+                    });
+                }
+            }
+        }
+    }
+    diff.added = others_by_type
+        .keys()
+        .filter(|type_path| !by_type.contains_key(*type_path))
+        .map(|&type_path| type_path.to_owned())
+        .collect();
+
+    diff.added.sort();
+    diff.removed.sort();
+    diff.changed.sort_by(|a, b| a.type_path.cmp(&b.type_path));
+    diff
+}
+
+/// Pushes the paths of the fields that differ between `a` and `b` to `paths`, prefixed with `path`.
+fn diff_value(
+    a: &dyn PartialReflect,
+    b: &dyn PartialReflect,
+    path: &mut String,
+    paths: &mut Vec<String>,
+) {
+    let mut diff_field = |name: fmt::Arguments, a: &dyn PartialReflect, b: &dyn PartialReflect| {
+        let len = path.len();
+        path.write_fmt(name).unwrap();
+        diff_value(a, b, path, paths);
+        path.truncate(len);
+    };
+
+    match (a.reflect_ref(), b.reflect_ref()) {
+        (ReflectRef::Struct(a), ReflectRef::Struct(b))
+            if a.field_len() == b.field_len()
+                && (0..a.field_len()).all(|i| b.field(a.name_at(i).unwrap()).is_some()) =>
+        {
+            for (i, field) in a.iter_fields().enumerate() {
+                let name = a.name_at(i).unwrap();
+                diff_field(format_args!(".{name}"), field, b.field(name).unwrap());
+            }
+        }
+        (ReflectRef::TupleStruct(a), ReflectRef::TupleStruct(b))
+            if a.field_len() == b.field_len() =>
+        {
+            for (i, (field, other)) in a.iter_fields().zip(b.iter_fields()).enumerate() {
+                diff_field(format_args!(".{i}"), field, other);
+            }
+        }
+        (ReflectRef::Tuple(a), ReflectRef::Tuple(b)) if a.field_len() == b.field_len() => {
+            for (i, (field, other)) in a.iter_fields().zip(b.iter_fields()).enumerate() {
+                diff_field(format_args!(".{i}"), field, other);
+            }
+        }
+        (ReflectRef::List(a), ReflectRef::List(b)) if a.len() == b.len() => {
+            for (i, (item, other)) in a.iter().zip(b.iter()).enumerate() {
+                diff_field(format_args!("[{i}]"), item, other);
+            }
+        }
+        (ReflectRef::Array(a), ReflectRef::Array(b)) if a.len() == b.len() => {
+            for (i, (item, other)) in a.iter().zip(b.iter()).enumerate() {
+                diff_field(format_args!("[{i}]"), item, other);
+            }
+        }
+        (ReflectRef::Enum(a), ReflectRef::Enum(b))
+            if a.variant_name() == b.variant_name() && a.field_len() == b.field_len() =>
+        {
+            for i in 0..a.field_len() {
+                let (field, other) = (a.field_at(i).unwrap(), b.field_at(i).unwrap());
+                match a.variant_type() {
+                    VariantType::Struct => {
+                        let name = a.name_at(i).unwrap();
+                        diff_field(format_args!(".{name}"), field, other);
+                    }
+                    _ => diff_field(format_args!(".{i}"), field, other),
+                }
+            }
+        }
+        _ => {
+            if !values_equal(a, b) {
+                paths.push(path.clone());
+            }
+        }
+    }
+}
+
+fn values_equal(a: &dyn PartialReflect, b: &dyn PartialReflect) -> bool {
+    if type_path(a) != type_path(b) {
+        return false;
+    }
+    a.reflect_partial_eq(b)
+        .unwrap_or_else(|| format!("{:?}", DebugReflect(a)) == format!("{:?}", DebugReflect(b)))
+}
+
+struct DebugReflect<'a>(&'a dyn PartialReflect);
+
+impl fmt::Debug for DebugReflect<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.debug(f)
+    }
+}
+
+/// A 64-bit FNV-1a hasher, which gives the same result on every platform.
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn write_len(hasher: &mut StableHasher, len: usize) {
+    hasher.write(&(len as u64).to_le_bytes());
+}
+
+fn write_str(hasher: &mut StableHasher, s: &str) {
+    write_len(hasher, s.len());
+    hasher.write(s.as_bytes());
+}
+
+/// Hashes components or resources, sorted by type path.
+fn hash_values(
+    hasher: &mut StableHasher,
+    values: &[Box<dyn PartialReflect>],
+) -> Result<(), StableHashError> {
+    let mut values: Vec<&dyn PartialReflect> = values.iter().map(AsRef::as_ref).collect();
+    values.sort_by_key(|value| type_path(*value));
+    write_len(hasher, values.len());
+    for value in values {
+        write_str(hasher, type_path(value));
+        hash_value(hasher, value)?;
+    }
+    Ok(())
+}
+
+fn hash_value(
+    hasher: &mut StableHasher,
+    value: &dyn PartialReflect,
+) -> Result<(), StableHashError> {
+    // The hash of each entry of maps and sets is computed separately, then sorted,
+    // since their iteration order isn't stable.
+    let unordered_hash = |hasher: &mut StableHasher, mut hashes: Vec<u64>| {
+        hashes.sort_unstable();
+        write_len(hasher, hashes.len());
+        for hash in hashes {
+            hasher.write(&hash.to_le_bytes());
+        }
+    };
+    let unhashable = || StableHashError::Unhashable {
+        type_path: type_path(value).to_owned(),
+    };
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            hasher.write(&[0]);
+            write_len(hasher, value.field_len());
+            for (i, field) in value.iter_fields().enumerate() {
+                write_str(hasher, value.name_at(i).unwrap());
+                hash_value(hasher, field)?;
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            hasher.write(&[1]);
+            write_len(hasher, value.field_len());
+            for field in value.iter_fields() {
+                hash_value(hasher, field)?;
+            }
+        }
+        ReflectRef::Tuple(value) => {
+            hasher.write(&[2]);
+            write_len(hasher, value.field_len());
+            for field in value.iter_fields() {
+                hash_value(hasher, field)?;
+            }
+        }
+        ReflectRef::List(value) => {
+            hasher.write(&[3]);
+            write_len(hasher, value.len());
+            for item in value.iter() {
+                hash_value(hasher, item)?;
+            }
+        }
+        ReflectRef::Array(value) => {
+            hasher.write(&[4]);
+            write_len(hasher, value.len());
+            for item in value.iter() {
+                hash_value(hasher, item)?;
+            }
+        }
+        ReflectRef::Map(value) => {
+            hasher.write(&[5]);
+            let hashes = value
+                .iter()
+                .map(|(key, value)| {
+                    let mut hasher = StableHasher::default();
+                    hash_value(&mut hasher, key)?;
+                    hash_value(&mut hasher, value)?;
+                    Ok(hasher.finish())
+                })
+                .collect::<Result<_, _>>()?;
+            unordered_hash(hasher, hashes);
+        }
+        ReflectRef::Set(value) => {
+            hasher.write(&[6]);
+            let hashes = value
+                .iter()
+                .map(|value| {
+                    let mut hasher = StableHasher::default();
+                    hash_value(&mut hasher, value)?;
+                    Ok(hasher.finish())
+                })
+                .collect::<Result<_, _>>()?;
+            unordered_hash(hasher, hashes);
+        }
+        ReflectRef::Enum(value) => {
+            hasher.write(&[7]);
+            write_str(hasher, value.variant_name());
+            write_len(hasher, value.field_len());
+            for (i, field) in value.iter_fields().enumerate() {
+                if let Some(name) = value.name_at(i) {
+                    write_str(hasher, name);
+                }
+                hash_value(hasher, field.value())?;
+            }
+        }
+        ReflectRef::Opaque(value) => {
+            hasher.write(&[8]);
+            write_str(hasher, type_path(value));
+            // Floats don't implement `Hash`, so they are hashed with their bits instead.
+            let hash = if let Some(&float) = value.try_downcast_ref::<f32>() {
+                u64::from(float.to_bits())
+            } else if let Some(&float) = value.try_downcast_ref::<f64>() {
+                float.to_bits()
+            } else {
+                value.reflect_hash().ok_or_else(unhashable)?
+            };
+            hasher.write(&hash.to_le_bytes());
+        }
+        // Functions, which only exist with `bevy_reflect/functions`, don't have a value that could be
+        // compared between worlds.
+        #[allow(unreachable_patterns)]
+        _ => return Err(unhashable()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicScene, DynamicSceneBuilder, SceneFilter, StableHashError};
+    use bevy_ecs::{
+        component::Component,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+    use bevy_utils::HashMap;
+    use core::any::TypeId;
+
+    #[derive(Component, Reflect, Default, Clone)]
+    #[reflect(Component)]
+    struct Stats {
+        health: f32,
+        tags: Vec<String>,
+        inventory: HashMap<String, u32>,
+    }
+
+    #[derive(Component, Reflect, Default, Clone)]
+    #[reflect(Component)]
+    struct Marker;
+
+    fn world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Stats>();
+        registry.write().register::<Marker>();
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn stable_hash() {
+        let stats = Stats {
+            health: 10.,
+            tags: vec!["a".into()],
+            inventory: [("sword".into(), 1), ("shield".into(), 2), ("bow".into(), 3)]
+                .into_iter()
+                .collect(),
+        };
+
+        let mut a = world();
+        a.spawn((stats.clone(), Marker));
+        let mut b = world();
+        // Inserting the components separately changes the order in which the scene stores them.
+        b.spawn(Marker).insert(stats.clone());
+        assert_eq!(
+            DynamicScene::from_world(&a).stable_hash().unwrap(),
+            DynamicScene::from_world(&b).stable_hash().unwrap()
+        );
+
+        let entity = b.iter_entities().next().unwrap().id();
+        b.get_mut::<Stats>(entity).unwrap().health = 10.5;
+        assert_ne!(
+            DynamicScene::from_world(&a).stable_hash().unwrap(),
+            DynamicScene::from_world(&b).stable_hash().unwrap()
+        );
+
+        let filtered_hash = |world: &World| {
+            DynamicSceneBuilder::from_world(world)
+                .with_component_filter(SceneFilter::deny_all().allow_by_id(TypeId::of::<Marker>()))
+                .extract_entities(world.iter_entities().map(|entity| entity.id()))
+                .build()
+                .stable_hash()
+                .unwrap()
+        };
+        assert_eq!(filtered_hash(&a), filtered_hash(&b));
+    }
+
+    #[test]
+    fn stable_hash_unhashable() {
+        #[derive(Component, Reflect, Clone)]
+        #[reflect(opaque, Component)]
+        struct Opaque;
+
+        let mut world = world();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Opaque>();
+        world.spawn(Opaque);
+        assert!(matches!(
+            DynamicScene::from_world(&world).stable_hash(),
+            Err(StableHashError::Unhashable { type_path }) if type_path.ends_with("Opaque")
+        ));
+    }
+
+    #[test]
+    fn diff() {
+        let mut world = world();
+        let changed = world.spawn(Stats::default()).id();
+        let removed = world.spawn(Marker).id();
+        let before = DynamicScene::from_world(&world);
+
+        world.despawn(removed);
+        let added = world.spawn(Marker).id();
+        world.entity_mut(changed).insert((
+            Stats {
+                health: 5.,
+                tags: vec!["a".into()],
+                inventory: HashMap::default(),
+            },
+            Marker,
+        ));
+        let after = DynamicScene::from_world(&world);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added_entities, [added]);
+        assert_eq!(diff.removed_entities, [removed]);
+        assert_eq!(diff.changed_entities.len(), 1);
+        let components = &diff.changed_entities[0].components;
+        assert_eq!(components.added.len(), 1);
+        assert!(components.added[0].ends_with("Marker"));
+        assert_eq!(components.changed.len(), 1);
+        assert_eq!(components.changed[0].paths, [".health", ".tags"]);
+        assert!(before.diff(&before).is_empty());
+    }
+}